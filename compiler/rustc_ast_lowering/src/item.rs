@@ -471,6 +471,7 @@ fn lower_item_kind(
             ItemKind::MacCall(..) => {
                 panic!("`TyMac` should have been expanded by now")
             }
+            ItemKind::Err => panic!("erroneous items are only produced by the resilient parser"),
         }
     }
 
@@ -364,6 +364,9 @@ fn print_associated_type(
                     state.print_visibility(&item.vis)
                 });
             }
+            ast::ItemKind::Err => {
+                self.word("/*ERROR*/");
+            }
         }
         self.ann.post(self, AnnNode::Item(item))
     }
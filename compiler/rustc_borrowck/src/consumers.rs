@@ -1,17 +1,34 @@
 //! This file provides API for compiler consumers.
+//!
+//! The borrowck facts of a body, returned by [`get_body_with_borrowck_facts`], are meant for
+//! external verifiers. Their representation may still change between versions of the compiler,
+//! but each breaking change of the fields of [`BodyWithBorrowckFacts`], of its methods, or of the
+//! types re-exported here increments [`BORROWCK_FACTS_VERSION`], so that tools can check at build
+//! time which version they support, for example with
+//! `const _: () = assert!(BORROWCK_FACTS_VERSION == 1);`.
 
 use rustc_hir::def_id::LocalDefId;
 use rustc_index::vec::IndexVec;
 use rustc_infer::infer::TyCtxtInferExt;
-use rustc_middle::mir::Body;
+use rustc_middle::mir::{Body, Location};
 use rustc_middle::ty::{self, TyCtxt};
 
 pub use super::{
+    borrow_set::{BorrowData, BorrowSet, TwoPhaseActivation},
+    dataflow::BorrowIndex,
     facts::{AllFacts as PoloniusInput, RustcFacts},
     location::{LocationTable, RichLocation},
     nll::PoloniusOutput,
     BodyWithBorrowckFacts,
 };
+pub use rustc_mir_dataflow::move_paths::{
+    Init, InitIndex, InitKind, InitLocation, LocationMap, MoveData, MoveOut, MoveOutIndex,
+    MovePath, MovePathIndex,
+};
+
+/// The version of the borrowck facts API, incremented on each breaking change of
+/// [`BodyWithBorrowckFacts`] or of the types it exposes.
+pub const BORROWCK_FACTS_VERSION: u32 = 1;
 
 /// This function computes Polonius facts for the given body. It makes a copy of
 /// the body because it needs to regenerate the region identifiers. This function
@@ -25,7 +42,8 @@
 ///     by overriding the `mir_borrowck` query. You can find a complete example
 ///     that shows how to do this at `src/test/run-make/obtain-borrowck/`.
 ///
-/// *   Polonius is highly unstable, so expect regular changes in its signature or other details.
+/// *   Polonius is highly unstable, so expect regular changes in the facts it computes; the
+///     changes of this API are tracked by [`BORROWCK_FACTS_VERSION`].
 pub fn get_body_with_borrowck_facts<'tcx>(
     tcx: TyCtxt<'tcx>,
     def: ty::WithOptConstParam<LocalDefId>,
@@ -37,3 +55,35 @@ pub fn get_body_with_borrowck_facts<'tcx>(
         *super::do_mir_borrowck(&infcx, input_body, promoted, true).1.unwrap()
     })
 }
+
+impl<'tcx> BodyWithBorrowckFacts<'tcx> {
+    /// Returns the loan created by the assignment at `location`, if any.
+    pub fn loan_created_at(&self, location: Location) -> Option<BorrowIndex> {
+        self.borrow_set.get_index_of(&location)
+    }
+
+    /// Returns the two-phase loans activated at `location`.
+    pub fn loans_activated_at(&self, location: Location) -> &[BorrowIndex] {
+        self.borrow_set.activations_at_location(location)
+    }
+
+    /// Returns the loans live at `location`, i.e. whose region contains `location`.
+    ///
+    /// The loans are computed on each call, in time linear in the number of loans of the body.
+    pub fn loans_live_at(&self, location: Location) -> impl Iterator<Item = BorrowIndex> + '_ {
+        self.borrow_set
+            .iter_enumerated()
+            .filter(move |(_, borrow)| self.regioncx.region_contains(borrow.region, location))
+            .map(|(index, _)| index)
+    }
+
+    /// Returns the moves out of a move path at `location`.
+    pub fn moves_at(&self, location: Location) -> &[MoveOutIndex] {
+        &self.move_data.loc_map[location]
+    }
+
+    /// Returns the initializations of a move path at `location`.
+    pub fn inits_at(&self, location: Location) -> &[InitIndex] {
+        &self.move_data.init_loc_map[location]
+    }
+}
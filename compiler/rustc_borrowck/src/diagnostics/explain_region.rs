@@ -0,0 +1,109 @@
+//! Explanation of region errors for `-Z borrowck-explain`.
+
+use rustc_errors::{Diagnostic, MultiSpan};
+use rustc_middle::mir::ConstraintCategory;
+use rustc_middle::ty::RegionVid;
+use rustc_session::config::BorrowckExplain;
+
+use std::fs;
+use std::path::PathBuf;
+
+use crate::nll::ConstraintDescription;
+use crate::MirBorrowckCtxt;
+
+impl<'cx, 'tcx> MirBorrowckCtxt<'cx, 'tcx> {
+    /// Adds to `diag` the path of outlives constraints forcing `fr: outlived_fr`, rendered on the
+    /// source, and writes the constraint graph of the body with `-Z borrowck-explain=dot`.
+    pub(super) fn explain_region_error(
+        &self,
+        diag: &mut Diagnostic,
+        fr: RegionVid,
+        outlived_fr: RegionVid,
+    ) {
+        let tcx = self.infcx.tcx;
+        let Some(explain) = tcx.sess.opts.debugging_opts.borrowck_explain else { return };
+        let Some((path, _)) = self.regioncx.find_constraint_paths_between_regions(fr, |r| {
+            self.regioncx.provides_universal_region(r, fr, outlived_fr)
+        }) else {
+            return;
+        };
+
+        let (mut located, mut unlocated) = (vec![], vec![]);
+        for (i, constraint) in path.iter().enumerate() {
+            let sup = self.explained_region_name(constraint.sup);
+            let sub = self.explained_region_name(constraint.sub);
+            let label = match constraint.category.description().trim_end() {
+                "" => format!("{}: `{}: {}`", i + 1, sup, sub),
+                cause => format!("{}: `{}: {}` due to {}", i + 1, sup, sub, cause),
+            };
+            if constraint.span.is_dummy()
+                || constraint.category == ConstraintCategory::BoringNoLocation
+            {
+                unlocated.push(label);
+            } else {
+                located.push((constraint.span, label));
+            }
+        }
+        let (fr_name, outlived_fr_name) =
+            (self.explained_region_name(fr), self.explained_region_name(outlived_fr));
+        let msg = format!(
+            "`{}` must outlive `{}` because of this path of {} outlives constraints",
+            fr_name,
+            outlived_fr_name,
+            path.len()
+        );
+        if located.is_empty() {
+            diag.note(&msg);
+        } else {
+            let mut spans = MultiSpan::from_spans(located.iter().map(|&(span, _)| span).collect());
+            for (span, label) in located {
+                spans.push_span_label(span, label);
+            }
+            diag.span_note(spans, &msg);
+        }
+        for label in unlocated {
+            diag.note(&format!("constraint {}, which has no location", label));
+        }
+
+        if explain == BorrowckExplain::Dot {
+            let def_path = tcx.def_path(self.body.source.def_id());
+            let dir = PathBuf::from(&tcx.sess.opts.debugging_opts.dump_mir_dir);
+            let file = dir.join(format!(
+                "{}.borrowck.r{}-r{}.dot",
+                def_path.to_filename_friendly_no_crate(),
+                fr.index(),
+                outlived_fr.index()
+            ));
+            let names = [(fr, fr_name), (outlived_fr, outlived_fr_name)];
+            let mut dot = vec![];
+            let written = self
+                .regioncx
+                .dump_graphviz_constraint_path(&path, &names, &mut dot)
+                .and_then(|()| fs::create_dir_all(&dir))
+                .and_then(|()| fs::write(&file, dot));
+            match written {
+                Ok(()) => diag.note(&format!(
+                    "the region constraint graph was written to `{}`",
+                    file.display()
+                )),
+                Err(e) => diag.note(&format!(
+                    "failed to write the region constraint graph to `{}`: {}",
+                    file.display(),
+                    e
+                )),
+            };
+        }
+    }
+
+    /// The name of `r` in the explanations, which is its name in the diagnostics for the
+    /// universal regions.
+    fn explained_region_name(&self, r: RegionVid) -> String {
+        if self.regioncx.universal_regions().is_universal_region(r)
+            && let Some(name) = self.give_region_a_name(r)
+        {
+            name.to_string()
+        } else {
+            format!("{:?}", r)
+        }
+    }
+}
@@ -31,6 +31,7 @@
 mod bound_region_errors;
 mod conflict_errors;
 mod explain_borrow;
+mod explain_region;
 mod move_errors;
 mod mutability_errors;
 mod region_errors;
@@ -282,7 +282,8 @@ pub(crate) fn report_region_error(
         // Check if we can use one of the "nice region errors".
         if let (Some(f), Some(o)) = (self.to_error_region(fr), self.to_error_region(outlived_fr)) {
             let nice = NiceRegionError::new_from_span(self.infcx, cause.span, o, f);
-            if let Some(diag) = nice.try_report_from_nll() {
+            if let Some(mut diag) = nice.try_report_from_nll() {
+                self.explain_region_error(&mut diag, fr, outlived_fr);
                 self.buffer_error(diag);
                 return;
             }
@@ -384,6 +385,7 @@ pub(crate) fn report_region_error(
             }
         }
 
+        self.explain_region_error(&mut diag, fr, outlived_fr);
         self.buffer_error(diag);
     }
 
@@ -446,6 +446,9 @@ fn do_mir_borrowck<'a, 'tcx>(
             input_facts: *polonius_input.expect("Polonius input facts were not generated"),
             output_facts,
             location_table: location_table_owned,
+            borrow_set,
+            regioncx,
+            move_data: mdpe.move_data,
         }))
     } else {
         None
@@ -470,6 +473,12 @@ pub struct BodyWithBorrowckFacts<'tcx> {
     pub output_facts: Rc<self::nll::PoloniusOutput>,
     /// The table that maps Polonius points to locations in the table.
     pub location_table: LocationTable,
+    /// The borrows (loans) of the body, with their two-phase activations.
+    pub borrow_set: Rc<BorrowSet<'tcx>>,
+    /// The move paths of the body, and the moves and initializations at each location.
+    pub move_data: MoveData<'tcx>,
+    /// The inferred regions, queried by `loans_live_at`.
+    regioncx: Rc<RegionInferenceContext<'tcx>>,
 }
 
 struct MirBorrowckCtxt<'cx, 'tcx> {
@@ -27,6 +27,17 @@ impl<'tcx> RegionInferenceContext<'tcx> {
 
         dot::render(&SccConstraints { regioncx: self, nodes_per_scc }, &mut w)
     }
+
+    /// Write out the region constraint graph, highlighting the constraints of `path` and the
+    /// regions at its ends. `names` are the names to show next to some regions.
+    crate fn dump_graphviz_constraint_path(
+        &self,
+        path: &[OutlivesConstraint<'tcx>],
+        names: &[(RegionVid, String)],
+        mut w: &mut dyn Write,
+    ) -> io::Result<()> {
+        dot::render(&ConstraintPath { regioncx: self, path, names }, &mut w)
+    }
 }
 
 struct RawConstraints<'a, 'tcx> {
@@ -138,3 +149,79 @@ fn target(&'this self, edge: &(ConstraintSccIndex, ConstraintSccIndex)) -> Const
         edge.1
     }
 }
+
+struct ConstraintPath<'a, 'tcx> {
+    regioncx: &'a RegionInferenceContext<'tcx>,
+    path: &'a [OutlivesConstraint<'tcx>],
+    names: &'a [(RegionVid, String)],
+}
+
+impl<'a, 'tcx> ConstraintPath<'a, 'tcx> {
+    fn path_index(&self, e: &OutlivesConstraint<'tcx>) -> Option<usize> {
+        self.path.iter().position(|constraint| constraint == e)
+    }
+}
+
+impl<'a, 'this, 'tcx> dot::Labeller<'this> for ConstraintPath<'a, 'tcx> {
+    type Node = RegionVid;
+    type Edge = OutlivesConstraint<'tcx>;
+
+    fn graph_id(&'this self) -> dot::Id<'this> {
+        dot::Id::new("RegionInferenceContext").unwrap()
+    }
+    fn node_id(&'this self, n: &RegionVid) -> dot::Id<'this> {
+        dot::Id::new(format!("r{}", n.index())).unwrap()
+    }
+    fn node_shape(&'this self, _node: &RegionVid) -> Option<dot::LabelText<'this>> {
+        Some(dot::LabelText::LabelStr(Cow::Borrowed("box")))
+    }
+    fn node_label(&'this self, n: &RegionVid) -> dot::LabelText<'this> {
+        match self.names.iter().find(|(r, _)| r == n) {
+            Some((_, name)) => dot::LabelText::LabelStr(format!("{:?} ({})", n, name).into()),
+            None => dot::LabelText::LabelStr(format!("{:?}", n).into()),
+        }
+    }
+    fn node_style(&'this self, n: &RegionVid) -> dot::Style {
+        let from = self.path.first().map(|constraint| constraint.sup);
+        let to = self.path.last().map(|constraint| constraint.sub);
+        if from == Some(*n) || to == Some(*n) {
+            dot::Style::Filled
+        } else {
+            dot::Style::None
+        }
+    }
+    fn edge_label(&'this self, e: &OutlivesConstraint<'tcx>) -> dot::LabelText<'this> {
+        match self.path_index(e) {
+            Some(i) => dot::LabelText::LabelStr(format!("{}: {:?}", i + 1, e.category).into()),
+            None => dot::LabelText::LabelStr(format!("{:?}", e.locations).into()),
+        }
+    }
+    fn edge_style(&'this self, e: &OutlivesConstraint<'tcx>) -> dot::Style {
+        if self.path_index(e).is_some() { dot::Style::Bold } else { dot::Style::None }
+    }
+}
+
+impl<'a, 'this, 'tcx> dot::GraphWalk<'this> for ConstraintPath<'a, 'tcx> {
+    type Node = RegionVid;
+    type Edge = OutlivesConstraint<'tcx>;
+
+    fn nodes(&'this self) -> dot::Nodes<'this, RegionVid> {
+        let vids: Vec<RegionVid> = self.regioncx.definitions.indices().collect();
+        vids.into()
+    }
+    fn edges(&'this self) -> dot::Edges<'this, OutlivesConstraint<'tcx>> {
+        // The path may go through constraints that are not in the graph, like the ones from
+        // `'static` or from member constraints.
+        let raw = &self.regioncx.constraints.outlives().raw;
+        let extra = self.path.iter().filter(|&constraint| !raw.contains(constraint));
+        raw.iter().chain(extra).cloned().collect::<Vec<_>>().into()
+    }
+
+    fn source(&'this self, edge: &OutlivesConstraint<'tcx>) -> RegionVid {
+        edge.sup
+    }
+
+    fn target(&'this self, edge: &OutlivesConstraint<'tcx>) -> RegionVid {
+        edge.sub
+    }
+}
@@ -27,6 +27,7 @@
 use super::linker::{self, Linker};
 use super::metadata::{create_rmeta_file, MetadataPosition};
 use super::rpath::{self, RPathConfig};
+use super::size_map;
 use crate::{
     looks_like_rust_object_file, CodegenResults, CompiledModule, CrateInfo, NativeLib,
     METADATA_FILENAME,
@@ -127,6 +128,14 @@ pub fn link_binary<'a, B: ArchiveBuilder<'a>>(
                 sess.parse_sess.span_diagnostic.emit_artifact_notification(&out_filename, "link");
             }
 
+            if sess.opts.debugging_opts.emit_size_map {
+                size_map::emit_size_map(
+                    sess,
+                    &codegen_results.crate_info.symbol_items,
+                    &out_filename,
+                );
+            }
+
             if sess.prof.enabled() {
                 if let Some(artifact_name) = out_filename.file_name() {
                     // Record size for self-profiling
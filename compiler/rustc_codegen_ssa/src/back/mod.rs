@@ -5,5 +5,6 @@
 pub mod lto;
 pub mod metadata;
 pub mod rpath;
+pub mod size_map;
 pub mod symbol_export;
 pub mod write;
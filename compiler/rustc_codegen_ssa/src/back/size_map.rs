@@ -0,0 +1,172 @@
+//! Binary size maps, for `-Z emit-size-map`.
+//!
+//! Before linking, the symbol name of every mono item of the crate, and of every symbol exported
+//! by its dependencies, is mapped to the item it is generated for: the crate defining the item,
+//! its path and its generic arguments. After linking, the symbols of the output are read back
+//! with their sizes, so that only the code and data that survived dead-code elimination are
+//! listed, and the map is written as JSON next to the output, as `{output}.size-map.json`.
+//!
+//! Inlined code is part of the symbol it was inlined into, so it is attributed to the item of
+//! that symbol. The symbols that are not generated for an item known to the crate graph, like the
+//! private items of dependencies or native code, are listed without an item.
+
+use object::{Object, ObjectSection, ObjectSymbol, SymbolKind};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::memmap::Mmap;
+use rustc_middle::middle::exported_symbols::ExportedSymbol;
+use rustc_middle::mir::mono::MonoItem;
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::subst::GenericArgKind;
+use rustc_middle::ty::{Instance, TyCtxt};
+use rustc_serialize::json::as_pretty_json;
+use rustc_session::Session;
+
+use std::fs::{self, File};
+use std::path::Path;
+
+use super::symbol_export::symbol_name_for_instance_in_crate;
+
+/// The item a symbol is generated for.
+#[derive(Debug, Encodable, Decodable)]
+pub struct SymbolItem {
+    pub crate_name: String,
+    pub path: String,
+    /// The generic arguments of the instance, without lifetimes.
+    pub generic_args: Vec<String>,
+}
+
+#[derive(Encodable)]
+struct SizeMapEntry<'a> {
+    symbol: &'a str,
+    size: u64,
+    item: Option<&'a SymbolItem>,
+}
+
+fn symbol_item<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> SymbolItem {
+    let def_id = instance.def_id();
+    with_no_trimmed_paths!(SymbolItem {
+        crate_name: tcx.crate_name(def_id.krate).to_string(),
+        path: tcx.def_path_str(def_id),
+        generic_args: instance
+            .substs
+            .iter()
+            .filter(|arg| !matches!(arg.unpack(), GenericArgKind::Lifetime(_)))
+            .map(|arg| arg.to_string())
+            .collect(),
+    })
+}
+
+/// Maps the symbol names of the mono items of the local crate, and of the symbols exported by its
+/// dependencies, to the items they are generated for.
+pub fn symbol_items(tcx: TyCtxt<'_>) -> FxHashMap<String, SymbolItem> {
+    let mut items = FxHashMap::default();
+    let (_, codegen_units) = tcx.collect_and_partition_mono_items(());
+    for cgu in codegen_units {
+        for &mono_item in cgu.items().keys() {
+            let instance = match mono_item {
+                MonoItem::Fn(instance) => instance,
+                MonoItem::Static(def_id) => Instance::mono(tcx, def_id),
+                MonoItem::GlobalAsm(_) => continue,
+            };
+            let name = mono_item.symbol_name(tcx).name.to_string();
+            items.insert(name, symbol_item(tcx, instance));
+        }
+    }
+
+    for &cnum in tcx.crates(()) {
+        for &(symbol, _) in tcx.exported_symbols(cnum) {
+            let instance = match symbol {
+                ExportedSymbol::NonGeneric(def_id) => Instance::mono(tcx, def_id),
+                ExportedSymbol::Generic(def_id, substs) => Instance::new(def_id, substs),
+                ExportedSymbol::DropGlue(ty) => Instance::resolve_drop_in_place(tcx, ty),
+                ExportedSymbol::NoDefId(_) => continue,
+            };
+            let name = symbol_name_for_instance_in_crate(tcx, symbol, cnum);
+            items.entry(name).or_insert_with(|| symbol_item(tcx, instance));
+        }
+    }
+
+    items
+}
+
+/// Writes the size map of the linked `out_filename`.
+pub fn emit_size_map(
+    sess: &Session,
+    symbol_items: &FxHashMap<String, SymbolItem>,
+    out_filename: &Path,
+) {
+    let mut map_filename = out_filename.as_os_str().to_owned();
+    map_filename.push(".size-map.json");
+
+    let result = read_symbol_sizes(out_filename).and_then(|sizes| {
+        let mut entries: Vec<_> = sizes
+            .iter()
+            .map(|(symbol, size)| {
+                // Mach-O prefixes the symbol names with an underscore.
+                let item = symbol_items
+                    .get(symbol)
+                    .or_else(|| symbol.strip_prefix('_').and_then(|name| symbol_items.get(name)));
+                SizeMapEntry { symbol, size: *size, item }
+            })
+            .collect();
+        entries.sort_by(|a, b| b.size.cmp(&a.size).then(a.symbol.cmp(b.symbol)));
+        fs::write(&map_filename, as_pretty_json(&entries).to_string()).map_err(|e| e.to_string())
+    });
+
+    if let Err(e) = result {
+        sess.err(&format!("failed to emit size map for `{}`: {}", out_filename.display(), e));
+    }
+}
+
+/// Reads the names and sizes of the symbols defined by the object file, or the archive of object
+/// files, at `path`.
+fn read_symbol_sizes(path: &Path) -> Result<Vec<(String, u64)>, String> {
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let data = unsafe { Mmap::map(file) }.map_err(|e| e.to_string())?;
+    let mut sizes = vec![];
+    if let Ok(archive) = object::read::archive::ArchiveFile::parse(&*data) {
+        for member in archive.members() {
+            let member = member.map_err(|e| e.to_string())?;
+            let member_data = member.data(&*data).map_err(|e| e.to_string())?;
+            // Members that are not object files, like the metadata of rlibs, define no symbols.
+            if let Ok(object) = object::File::parse(member_data) {
+                push_symbol_sizes(&object, &mut sizes);
+            }
+        }
+    } else {
+        let object = object::File::parse(&*data).map_err(|e| e.to_string())?;
+        push_symbol_sizes(&object, &mut sizes);
+    }
+    Ok(sizes)
+}
+
+fn push_symbol_sizes(object: &object::File<'_>, sizes: &mut Vec<(String, u64)>) {
+    let mut symbols: Vec<_> = object
+        .symbols()
+        .filter(|symbol| {
+            symbol.is_definition() && matches!(symbol.kind(), SymbolKind::Text | SymbolKind::Data)
+        })
+        .filter_map(|symbol| {
+            Some((symbol.section_index()?, symbol.address(), symbol.size(), symbol.name().ok()?))
+        })
+        .collect();
+    symbols.sort_by_key(|&(section, address, ..)| (section.0, address));
+
+    for (i, &(section, address, size, name)) in symbols.iter().enumerate() {
+        // Mach-O symbols have no size, so it is estimated from the address of the next symbol
+        // of the section, or from the end of the section.
+        let size = if size != 0 {
+            size
+        } else {
+            match symbols.get(i + 1) {
+                Some(&(next_section, next_address, ..)) if next_section == section => {
+                    next_address - address
+                }
+                _ => object
+                    .section_by_index(section)
+                    .map_or(0, |s| (s.address() + s.size()).saturating_sub(address)),
+            }
+        };
+        sizes.push((name.to_string(), size));
+    }
+}
@@ -43,6 +43,7 @@
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::sync::Arc;
 use std::thread;
+use std::time::{Duration, Instant};
 
 const PRE_LTO_BC_EXT: &str = "pre-lto.bc";
 
@@ -804,9 +805,29 @@ fn execute_optimize_work_item<B: ExtraBackendMethods>(
 ) -> Result<WorkItemResult<B>, FatalError> {
     let diag_handler = cgcx.create_diag_handler();
 
+    let start = Instant::now();
     unsafe {
         B::optimize(cgcx, &diag_handler, &module, module_config)?;
     }
+    if let Some(budget) = cgcx.opts.debugging_opts.time_budget {
+        let elapsed = start.elapsed();
+        if elapsed > Duration::from_millis(budget) {
+            // Reported per codegen unit rather than per item, see `rustc_session::time_budget`.
+            let msg = format!(
+                "LLVM optimization of codegen unit `{}` took {}ms, more than the budget of {}ms",
+                module.name,
+                elapsed.as_millis(),
+                budget
+            );
+            diag_handler
+                .struct_warn(&msg)
+                .help(
+                    "compile with a higher `-C codegen-units` to split the items into more units, \
+                    and list the items of each unit with `-Z print-mono-items=lazy`",
+                )
+                .emit();
+        }
+    }
 
     // After we've done the initial round of optimizations we need to
     // decide whether to synchronously codegen this module or ship it
@@ -1,4 +1,5 @@
 use crate::back::metadata::create_compressed_metadata_file;
+use crate::back::size_map;
 use crate::back::write::{
     compute_per_cgu_lto_type, start_async_codegen, submit_codegened_module_to_llvm,
     submit_post_lto_module_to_llvm, submit_pre_lto_module_to_llvm, ComputedLtoType, OngoingCodegen,
@@ -660,6 +661,9 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
             cgu_reuse = tcx.sess.time("find_cgu_reuse", || {
                 codegen_units.iter().map(|cgu| determine_cgu_reuse(tcx, &cgu)).collect()
             });
+            if tcx.sess.opts.debugging_opts.incremental_explain {
+                rustc_incremental::explain_cgu_reuse(tcx, &codegen_units, &cgu_reuse);
+            }
             // Pre compile some CGUs
             let (compiled_cgus, codegen_time) = pre_compile_cgus(&cgu_reuse);
             pre_compiled_cgus = Some(compiled_cgus);
@@ -848,6 +852,11 @@ pub fn new(tcx: TyCtxt<'_>, target_cpu: String) -> CrateInfo {
             dependency_formats: tcx.dependency_formats(()).clone(),
             windows_subsystem,
             debugger_visualizers: Default::default(),
+            symbol_items: if tcx.sess.opts.debugging_opts.emit_size_map {
+                size_map::symbol_items(tcx)
+            } else {
+                Default::default()
+            },
         };
         let debugger_visualizers = tcx.debugger_visualizers(LOCAL_CRATE).clone();
         if !debugger_visualizers.is_empty() {
@@ -21,6 +21,7 @@
 #[macro_use]
 extern crate rustc_middle;
 
+use crate::back::size_map::SymbolItem;
 use rustc_ast as ast;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync::Lrc;
@@ -158,6 +159,8 @@ pub struct CrateInfo {
     pub dependency_formats: Lrc<Dependencies>,
     pub windows_subsystem: Option<String>,
     pub debugger_visualizers: FxHashMap<CrateNum, Vec<DebuggerVisualizerFile>>,
+    /// The items of the symbols, if `-Z emit-size-map` is specified.
+    pub symbol_items: FxHashMap<String, SymbolItem>,
 }
 
 #[derive(Encodable, Decodable)]
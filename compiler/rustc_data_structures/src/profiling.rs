@@ -79,6 +79,13 @@
 //! invocation) and allocate the corresponding strings together with a mapping
 //! for `DepNodeIndex as StringId`.
 //!
+//!
+//! ## Built-in Formats
+//!
+//! With `-Z self-profile-format`, the profiler also writes the profile in a
+//! format that common tools understand without the `measureme` tools, see the
+//! `trace` module.
+//!
 //! [mm]: https://github.com/rust-lang/measureme/
 
 use crate::cold_path;
@@ -94,10 +101,15 @@
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+mod trace;
+
 pub use measureme::EventId;
 use measureme::{EventIdBuilder, Profiler, SerializableString, StringId};
 use parking_lot::RwLock;
 use smallvec::SmallVec;
+use trace::{Trace, TraceGuard};
+
+pub use trace::ProfileFormat;
 
 bitflags::bitflags! {
     struct EventFilter: u32 {
@@ -143,6 +155,7 @@ struct EventFilter: u32 {
 ];
 
 /// Something that uniquely identifies a query invocation.
+#[derive(Clone, Copy)]
 pub struct QueryInvocationId(pub u32);
 
 /// A reference to the SelfProfiler. It can be cloned and sent across thread
@@ -544,6 +557,9 @@ pub struct SelfProfiler {
     query_blocked_event_kind: StringId,
     query_cache_hit_event_kind: StringId,
     artifact_size_event_kind: StringId,
+
+    /// The events kept in memory for `-Z self-profile-format`.
+    trace: Option<Trace>,
 }
 
 impl SelfProfiler {
@@ -551,6 +567,7 @@ pub fn new(
         output_directory: &Path,
         crate_name: Option<&str>,
         event_filters: &Option<Vec<String>>,
+        format: Option<ProfileFormat>,
     ) -> Result<SelfProfiler, Box<dyn Error + Send + Sync>> {
         fs::create_dir_all(output_directory)?;
 
@@ -558,6 +575,10 @@ pub fn new(
         let filename = format!("{}-{}.rustc_profile", crate_name, process::id());
         let path = output_directory.join(&filename);
         let profiler = Profiler::new(&path)?;
+        let trace = format.map(|format| {
+            let filename = format!("{}-{}.{}", crate_name, process::id(), format.extension());
+            Trace::new(format, output_directory.join(filename))
+        });
 
         let query_event_kind = profiler.alloc_string("Query");
         let generic_activity_event_kind = profiler.alloc_string("GenericActivity");
@@ -612,6 +633,7 @@ pub fn new(
             query_blocked_event_kind,
             query_cache_hit_event_kind,
             artifact_size_event_kind,
+            trace,
         })
     }
 
@@ -659,10 +681,23 @@ pub fn bulk_map_query_invocation_id_to_single_string<I>(&self, from: I, to: Stri
     where
         I: Iterator<Item = QueryInvocationId> + ExactSizeIterator,
     {
-        let from = from.map(|qid| StringId::new_virtual(qid.0));
+        let from = from.map(|qid| {
+            if let Some(trace) = &self.trace {
+                trace.map_query(qid.0, to);
+            }
+            StringId::new_virtual(qid.0)
+        });
         self.profiler.bulk_map_virtual_to_single_concrete_string(from, to);
     }
 
+    /// Records the label of a query invocation for `-Z self-profile-format`, for the queries
+    /// whose invocation id is mapped to a string that also contains their key.
+    pub fn map_query_invocation_id_to_label(&self, from: QueryInvocationId, label: StringId) {
+        if let Some(trace) = &self.trace {
+            trace.map_query(from.0, label);
+        }
+    }
+
     pub fn query_key_recording_enabled(&self) -> bool {
         self.event_filter_mask.contains(EventFilter::QUERY_KEYS)
     }
@@ -672,8 +707,24 @@ pub fn event_id_builder(&self) -> EventIdBuilder<'_> {
     }
 }
 
+impl Drop for SelfProfiler {
+    fn drop(&mut self) {
+        let Some(trace) = &self.trace else { return };
+        let kinds = [
+            (self.query_event_kind, "Query"),
+            (self.generic_activity_event_kind, "GenericActivity"),
+            (self.incremental_load_result_event_kind, "IncrementalLoadResult"),
+            (self.incremental_result_hashing_event_kind, "IncrementalResultHashing"),
+            (self.query_blocked_event_kind, "QueryBlocked"),
+        ];
+        if let Err(err) = trace.write(&self.string_cache.read(), &kinds) {
+            eprintln!("failed to write self-profile `{}`: {}", trace.path().display(), err);
+        }
+    }
+}
+
 #[must_use]
-pub struct TimingGuard<'a>(Option<measureme::TimingGuard<'a>>);
+pub struct TimingGuard<'a>(Option<measureme::TimingGuard<'a>>, Option<TraceGuard<'a>>);
 
 impl<'a> TimingGuard<'a> {
     #[inline]
@@ -686,7 +737,11 @@ pub fn start(
         let raw_profiler = &profiler.profiler;
         let timing_guard =
             raw_profiler.start_recording_interval_event(event_kind, event_id, thread_id);
-        TimingGuard(Some(timing_guard))
+        let trace_guard = profiler
+            .trace
+            .as_ref()
+            .map(|trace| TraceGuard::start(trace, event_kind, event_id.to_string_id(), thread_id));
+        TimingGuard(Some(timing_guard), trace_guard)
     }
 
     #[inline]
@@ -698,11 +753,14 @@ pub fn finish_with_query_invocation_id(self, query_invocation_id: QueryInvocatio
                 guard.finish_with_override_event_id(event_id);
             });
         }
+        if let Some(mut trace_guard) = self.1 {
+            trace_guard.label = trace::RawLabel::Query(query_invocation_id.0);
+        }
     }
 
     #[inline]
     pub fn none() -> TimingGuard<'a> {
-        TimingGuard(None)
+        TimingGuard(None, None)
     }
 
     #[inline(always)]
@@ -0,0 +1,348 @@
+//! Built-in profile writers, for `-Z self-profile-format`.
+//!
+//! When a format is requested, the `SelfProfiler` also keeps the interval events it records in
+//! memory, and when it is dropped, writes them in that format next to the `measureme` profile, so
+//! that no external tool is needed to look at the profile. Events are only identified by their
+//! label, e.g. the name of a query or of a generic activity, since the labels can be resolved
+//! without decoding the `measureme` string table.
+
+use crate::fx::FxHashMap;
+
+use measureme::StringId;
+use parking_lot::Mutex;
+
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::process;
+use std::time::{Duration, Instant};
+
+#[cfg(test)]
+mod tests;
+
+/// The formats in which the self-profiler can write a profile, in addition to the `measureme` one.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ProfileFormat {
+    /// The Chrome trace event format, for `chrome://tracing`, Perfetto or speedscope.
+    Chrome,
+    /// Folded stacks weighted by self time in microseconds, for `flamegraph.pl` or inferno.
+    Folded,
+    /// A table of the time spent in each query and generic activity.
+    Summary,
+}
+
+impl ProfileFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ProfileFormat::Chrome => "json",
+            ProfileFormat::Folded => "folded",
+            ProfileFormat::Summary => "summary.txt",
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(super) enum RawLabel {
+    /// The `StringId` of the label of the event.
+    String(StringId),
+    /// The invocation id of a query, which is mapped to the label of the query at the end.
+    Query(u32),
+}
+
+/// An event as recorded, before its label is resolved.
+struct RawEvent {
+    kind: StringId,
+    label: RawLabel,
+    thread_id: u32,
+    start: Duration,
+    end: Duration,
+}
+
+/// An event with its label and category resolved.
+#[derive(Clone, PartialEq, Debug)]
+struct Event {
+    label: String,
+    category: &'static str,
+    thread_id: u32,
+    start: Duration,
+    end: Duration,
+}
+
+impl Event {
+    fn duration(&self) -> Duration {
+        self.end.saturating_sub(self.start)
+    }
+}
+
+pub(super) struct Trace {
+    format: ProfileFormat,
+    path: PathBuf,
+    start: Instant,
+    events: Mutex<Vec<RawEvent>>,
+    query_labels: Mutex<FxHashMap<u32, StringId>>,
+}
+
+impl Trace {
+    pub(super) fn new(format: ProfileFormat, path: PathBuf) -> Trace {
+        Trace {
+            format,
+            path,
+            start: Instant::now(),
+            events: Default::default(),
+            query_labels: Default::default(),
+        }
+    }
+
+    pub(super) fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    pub(super) fn map_query(&self, query_invocation_id: u32, label: StringId) {
+        self.query_labels.lock().insert(query_invocation_id, label);
+    }
+
+    /// Resolves the labels of the recorded events, using the `strings` allocated by the profiler
+    /// and the names of the event `kinds`, and writes the profile.
+    pub(super) fn write(
+        &self,
+        strings: &FxHashMap<String, StringId>,
+        kinds: &[(StringId, &'static str)],
+    ) -> io::Result<()> {
+        let labels: FxHashMap<StringId, &str> =
+            strings.iter().map(|(string, &id)| (id, &string[..])).collect();
+        let query_labels = self.query_labels.lock();
+        let events = self
+            .events
+            .lock()
+            .iter()
+            .map(|event| {
+                let label_id = match event.label {
+                    RawLabel::String(id) => Some(id),
+                    RawLabel::Query(id) => query_labels.get(&id).copied(),
+                };
+                let label = label_id.and_then(|id| labels.get(&id)).unwrap_or(&"<unknown>");
+                let category = kinds
+                    .iter()
+                    .find(|&&(kind, _)| kind == event.kind)
+                    .map_or("Unknown", |&(_, name)| name);
+                Event {
+                    label: label.to_string(),
+                    category,
+                    thread_id: event.thread_id,
+                    start: event.start,
+                    end: event.end,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let contents = match self.format {
+            ProfileFormat::Chrome => chrome_trace(&events),
+            ProfileFormat::Folded => folded_stacks(events),
+            ProfileFormat::Summary => summary(events),
+        };
+        fs::write(&self.path, contents)
+    }
+}
+
+/// Records an interval event of the `Trace` when dropped.
+pub(super) struct TraceGuard<'a> {
+    trace: &'a Trace,
+    kind: StringId,
+    pub(super) label: RawLabel,
+    thread_id: u32,
+    start: Duration,
+}
+
+impl<'a> TraceGuard<'a> {
+    pub(super) fn start(
+        trace: &'a Trace,
+        kind: StringId,
+        label: StringId,
+        thread_id: u32,
+    ) -> TraceGuard<'a> {
+        TraceGuard {
+            trace,
+            kind,
+            label: RawLabel::String(label),
+            thread_id,
+            start: trace.start.elapsed(),
+        }
+    }
+}
+
+impl Drop for TraceGuard<'_> {
+    fn drop(&mut self) {
+        let end = self.trace.start.elapsed();
+        self.trace.events.lock().push(RawEvent {
+            kind: self.kind,
+            label: self.label,
+            thread_id: self.thread_id,
+            start: self.start,
+            end,
+        });
+    }
+}
+
+/// The name of an event in the folded stacks and in the summary, which includes its category
+/// unless it is a query or a generic activity.
+fn display_name(event: &Event) -> String {
+    match event.category {
+        "Query" | "GenericActivity" => event.label.clone(),
+        category => format!("{} ({})", event.label, category),
+    }
+}
+
+/// Sorts `events` by thread and by start time, outer events first, and returns the index of
+/// the event each one is nested in, along with its self time, i.e. its duration minus the
+/// duration of the events directly nested in it.
+fn nest(events: &mut [Event]) -> Vec<(Option<usize>, Duration)> {
+    events.sort_by(|a, b| {
+        (a.thread_id, a.start).cmp(&(b.thread_id, b.start)).then(b.end.cmp(&a.end))
+    });
+    let mut nesting: Vec<(Option<usize>, Duration)> =
+        events.iter().map(|event| (None, event.duration())).collect();
+    let mut stack: Vec<usize> = vec![];
+    for (i, event) in events.iter().enumerate() {
+        while let Some(&outer) = stack.last() {
+            if events[outer].thread_id != event.thread_id || events[outer].end <= event.start {
+                stack.pop();
+            } else {
+                break;
+            }
+        }
+        if let Some(&outer) = stack.last() {
+            nesting[i].0 = Some(outer);
+            nesting[outer].1 = nesting[outer].1.saturating_sub(event.duration());
+        }
+        stack.push(i);
+    }
+    nesting
+}
+
+fn chrome_trace(events: &[Event]) -> String {
+    let pid = process::id();
+    let mut out = String::from("{\"traceEvents\":[\n");
+    for (i, event) in events.iter().enumerate() {
+        let separator = if i + 1 < events.len() { "," } else { "" };
+        writeln!(
+            out,
+            "{{\"name\":\"{}\",\"cat\":\"{}\",\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":{},\
+             \"tid\":{}}}{}",
+            escape_json(&event.label),
+            event.category,
+            event.start.as_micros(),
+            event.duration().as_micros(),
+            pid,
+            event.thread_id,
+            separator
+        )
+        .unwrap();
+    }
+    out.push_str("]}\n");
+    out
+}
+
+fn escape_json(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn folded_stacks(mut events: Vec<Event>) -> String {
+    let nesting = nest(&mut events);
+    let names: Vec<String> = events.iter().map(display_name).collect();
+    let mut stacks: FxHashMap<String, u128> = FxHashMap::default();
+    for (i, &(_, self_time)) in nesting.iter().enumerate() {
+        let mut frames = vec![&names[i][..]];
+        let mut outer = nesting[i].0;
+        while let Some(o) = outer {
+            frames.push(&names[o]);
+            outer = nesting[o].0;
+        }
+        frames.reverse();
+        // Semicolons separate the frames of folded stacks.
+        let stack = frames.join(";").replace(' ', "_");
+        *stacks.entry(stack).or_default() += self_time.as_micros();
+    }
+
+    let mut stacks: Vec<_> = stacks.into_iter().filter(|&(_, micros)| micros > 0).collect();
+    stacks.sort();
+    let mut out = String::new();
+    for (stack, micros) in stacks {
+        writeln!(out, "{} {}", stack, micros).unwrap();
+    }
+    out
+}
+
+#[derive(Default)]
+struct Totals {
+    count: usize,
+    self_time: Duration,
+    time: Duration,
+}
+
+fn summary(mut events: Vec<Event>) -> String {
+    let nesting = nest(&mut events);
+    let names: Vec<String> = events.iter().map(display_name).collect();
+    let mut totals: FxHashMap<&str, Totals> = FxHashMap::default();
+    let mut total_self_time = Duration::ZERO;
+    for (i, &(mut outer, self_time)) in nesting.iter().enumerate() {
+        let item = totals.entry(&names[i][..]).or_default();
+        item.count += 1;
+        item.self_time += self_time;
+        total_self_time += self_time;
+        // Recursive events are only counted once in the total time.
+        let mut recursive = false;
+        while let Some(o) = outer {
+            recursive |= names[o] == names[i];
+            outer = nesting[o].0;
+        }
+        if !recursive {
+            item.time += events[i].duration();
+        }
+    }
+
+    let mut totals: Vec<_> = totals.into_iter().collect();
+    totals.sort_by(|a, b| b.1.self_time.cmp(&a.1.self_time).then(a.0.cmp(b.0)));
+    let width = totals.iter().map(|(name, _)| name.len()).max().unwrap_or(0).max(4);
+    let mut out = String::new();
+    writeln!(
+        out,
+        "{:<width$}  {:>12}  {:>7}  {:>12}  {:>8}",
+        "Item",
+        "Self time",
+        "% self",
+        "Time",
+        "Count",
+        width = width
+    )
+    .unwrap();
+    for (name, totals) in totals {
+        let percent = if total_self_time.is_zero() {
+            0.0
+        } else {
+            totals.self_time.as_secs_f64() * 100.0 / total_self_time.as_secs_f64()
+        };
+        writeln!(
+            out,
+            "{:<width$}  {:>12}  {:>6.2}%  {:>12}  {:>8}",
+            name,
+            format!("{:.2?}", totals.self_time),
+            percent,
+            format!("{:.2?}", totals.time),
+            totals.count,
+            width = width
+        )
+        .unwrap();
+    }
+    writeln!(out, "Total self time: {:.2?}", total_self_time).unwrap();
+    out
+}
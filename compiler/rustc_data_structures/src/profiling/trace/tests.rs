@@ -0,0 +1,76 @@
+use super::*;
+
+fn event(label: &str, category: &'static str, thread_id: u32, start: u64, end: u64) -> Event {
+    Event {
+        label: label.to_owned(),
+        category,
+        thread_id,
+        start: Duration::from_micros(start),
+        end: Duration::from_micros(end),
+    }
+}
+
+fn events() -> Vec<Event> {
+    vec![
+        event("typeck", "Query", 1, 20, 50),
+        event("analysis", "GenericActivity", 1, 0, 100),
+        event("type_of", "Query", 1, 30, 40),
+        event("typeck", "IncrementalLoadResult", 1, 60, 70),
+        event("codegen_module", "GenericActivity", 2, 10, 80),
+    ]
+}
+
+#[test]
+fn nesting_and_self_times() {
+    let mut events = events();
+    let nesting = nest(&mut events);
+    let labels: Vec<_> = events.iter().map(|event| &event.label[..]).collect();
+    assert_eq!(labels, ["analysis", "typeck", "type_of", "typeck", "codegen_module"]);
+    assert_eq!(
+        nesting,
+        [
+            (None, Duration::from_micros(60)),
+            (Some(0), Duration::from_micros(20)),
+            (Some(1), Duration::from_micros(10)),
+            (Some(0), Duration::from_micros(10)),
+            (None, Duration::from_micros(70)),
+        ]
+    );
+}
+
+#[test]
+fn folded() {
+    assert_eq!(
+        folded_stacks(events()),
+        "analysis 60\n\
+         analysis;typeck 20\n\
+         analysis;typeck;type_of 10\n\
+         analysis;typeck_(IncrementalLoadResult) 10\n\
+         codegen_module 70\n"
+    );
+}
+
+#[test]
+fn summary_counts_recursive_events_once() {
+    let events = vec![
+        event("expand", "GenericActivity", 1, 0, 100),
+        event("expand", "GenericActivity", 1, 10, 60),
+        event("parse", "GenericActivity", 1, 20, 40),
+    ];
+    let summary = summary(events);
+    let lines: Vec<Vec<&str>> =
+        summary.lines().map(|line| line.split_whitespace().collect()).collect();
+    assert_eq!(lines[0], ["Item", "Self", "time", "%", "self", "Time", "Count"]);
+    assert_eq!(lines[1], ["expand", "80.00µs", "80.00%", "100.00µs", "2"]);
+    assert_eq!(lines[2], ["parse", "20.00µs", "20.00%", "20.00µs", "1"]);
+    assert_eq!(lines[3], ["Total", "self", "time:", "100.00µs"]);
+}
+
+#[test]
+fn chrome_labels_are_escaped() {
+    let trace = chrome_trace(&[event("a \"quoted\"\\label", "Query", 1, 1, 3)]);
+    assert!(trace.starts_with("{\"traceEvents\":[\n"));
+    assert!(trace.contains("\"name\":\"a \\\"quoted\\\"\\\\label\",\"cat\":\"Query\""));
+    assert!(trace.contains("\"ts\":1,\"dur\":2,"));
+    assert!(trace.ends_with("}\n]}\n"));
+}
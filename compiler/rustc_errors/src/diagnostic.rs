@@ -107,6 +107,10 @@ pub struct Diagnostic {
     /// If diagnostic is from Lint, custom hash function ignores notes
     /// otherwise hash is based on the all the fields
     pub is_lint: bool,
+
+    /// For lints, the path of the item they were emitted in, which identifies them in diagnostic
+    /// baselines along with their snippet.
+    pub item_path: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Encodable, Decodable)]
@@ -192,6 +196,7 @@ pub fn new_with_code<M: Into<DiagnosticMessage>>(
             args: vec![],
             sort_span: DUMMY_SP,
             is_lint: false,
+            item_path: None,
         }
     }
 
@@ -795,6 +800,11 @@ pub fn set_is_lint(&mut self) -> &mut Self {
         self
     }
 
+    pub fn set_item_path(&mut self, item_path: String) -> &mut Self {
+        self.item_path = Some(item_path);
+        self
+    }
+
     pub fn code(&mut self, s: DiagnosticId) -> &mut Self {
         self.code = Some(s);
         self
@@ -224,6 +224,12 @@ fn should_show_explain(&self) -> bool {
         true
     }
 
+    /// Checks if should show the "N warnings emitted" and "aborting due to N previous errors"
+    /// summaries
+    fn should_show_error_count(&self) -> bool {
+        true
+    }
+
     /// Checks if we can use colors in the current output stream.
     fn supports_color(&self) -> bool {
         false
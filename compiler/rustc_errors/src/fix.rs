@@ -0,0 +1,288 @@
+//! An emitter that applies machine-applicable suggestions, for `-Z apply-suggestions`.
+//!
+//! `FixEmitter` wraps the emitter selected by `--error-format` and forwards all diagnostics to it.
+//! Along the way, it collects the suggestions with `Applicability::MachineApplicable`, and when it
+//! is dropped at the end of the compilation, it writes the source files with these suggestions
+//! applied to its output directory. This includes a compilation aborted after errors, but not an
+//! ICE, after which the suggestions may be partial. This does what `rustfix` does for `cargo fix`,
+//! for build systems that invoke `rustc` directly.
+//!
+//! Suggestions are applied in the order they were emitted. A suggestion is skipped, and reported,
+//! if it overlaps with a suggestion that was already accepted. Suggestions with more than one
+//! substitution are alternatives between which only the user can choose, so they are ignored.
+
+use crate::emitter::Emitter;
+use crate::{Applicability, Diagnostic, FluentBundle, Level, SubstitutionPart};
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::sync::Lrc;
+use rustc_lint_defs::pluralize;
+use rustc_span::source_map::SourceMap;
+use rustc_span::{FileName, SourceFile, Span};
+use std::collections::hash_map::Entry;
+use std::fs;
+use std::ops::Range;
+use std::path::{Component, Path, PathBuf};
+
+#[cfg(test)]
+mod tests;
+
+pub struct FixEmitter {
+    inner: Box<dyn Emitter + Send>,
+    sm: Lrc<SourceMap>,
+    /// The directory to write the fixed source files to.
+    out_dir: PathBuf,
+    /// The machine-applicable substitutions emitted so far, with the message of their suggestion.
+    substitutions: Vec<(String, Vec<SubstitutionPart>)>,
+    /// Whether an error was emitted, after which the compilation can abort with a `FatalError`.
+    emitted_error: bool,
+    /// Whether a bug was emitted, i.e. whether the compilation is ending with an ICE.
+    emitted_bug: bool,
+}
+
+/// A replacement of the `range` of the original source of a file by `snippet`.
+#[derive(Clone, PartialEq, Debug)]
+struct Edit {
+    range: Range<usize>,
+    snippet: String,
+}
+
+impl Edit {
+    fn overlaps(&self, other: &Edit) -> bool {
+        // Two insertions at the same position conflict, as their order is ambiguous.
+        (self.range.start < other.range.end && other.range.start < self.range.end)
+            || (self.range.is_empty() && self.range == other.range)
+    }
+}
+
+impl FixEmitter {
+    pub fn new(inner: Box<dyn Emitter + Send>, sm: Lrc<SourceMap>, out_dir: PathBuf) -> Self {
+        FixEmitter {
+            inner,
+            sm,
+            out_dir,
+            substitutions: vec![],
+            emitted_error: false,
+            emitted_bug: false,
+        }
+    }
+
+    /// Converts the parts of a substitution to edits of the original sources, or returns `None`
+    /// if the substitution cannot be applied, e.g. because it is in a macro expansion.
+    fn edits(&self, parts: &[SubstitutionPart]) -> Option<Vec<(Lrc<SourceFile>, Edit)>> {
+        parts
+            .iter()
+            .map(|part| {
+                if part.span.is_dummy() || part.span.from_expansion() {
+                    return None;
+                }
+                let file = self.sm.lookup_source_file(part.span.lo());
+                if !file.contains(part.span.hi()) && file.end_pos != part.span.hi() {
+                    return None;
+                }
+                let range = file.original_relative_byte_pos(part.span.lo()).0 as usize
+                    ..file.original_relative_byte_pos(part.span.hi()).0 as usize;
+                Some((file, Edit { range, snippet: part.snippet.clone() }))
+            })
+            .collect()
+    }
+
+    /// Selects the substitutions to apply, and returns their edits grouped by file.
+    fn select_edits(&mut self) -> Vec<(Lrc<SourceFile>, Vec<Edit>)> {
+        let mut files: Vec<(Lrc<SourceFile>, Vec<Edit>)> = vec![];
+        for (msg, parts) in std::mem::take(&mut self.substitutions) {
+            let Some(edits) = self.edits(&parts) else { continue };
+            let accepted = |file: &Lrc<SourceFile>| {
+                files
+                    .iter()
+                    .find(|(f, _)| Lrc::ptr_eq(f, file))
+                    .map_or(&[][..], |(_, edits)| &edits[..])
+            };
+            // The same suggestion is often emitted several times, e.g. for each expansion of a
+            // macro.
+            if edits.iter().all(|(file, edit)| accepted(file).contains(edit)) {
+                continue;
+            }
+            if edits.iter().any(|(file, edit)| accepted(file).iter().any(|a| a.overlaps(edit))) {
+                self.report(
+                    Level::Warning,
+                    &format!("suggestion not applied as it overlaps with another one: {}", msg),
+                    parts[0].span,
+                );
+                continue;
+            }
+            for (file, edit) in edits {
+                match files.iter_mut().find(|(f, _)| Lrc::ptr_eq(f, &file)) {
+                    Some((_, file_edits)) => file_edits.push(edit),
+                    None => files.push((file, vec![edit])),
+                }
+            }
+        }
+        files
+    }
+
+    /// Applies the selected suggestions and writes the fixed files to `self.out_dir`.
+    fn apply(&mut self) {
+        let files = self.select_edits();
+        let mut applied = 0;
+        // The source files written to each output path, as distinct paths can have the same
+        // relative path, e.g. `../a/lib.rs` and `/x/a/lib.rs`.
+        let mut written: FxHashMap<PathBuf, PathBuf> = FxHashMap::default();
+        for (file, mut edits) in files {
+            let FileName::Real(ref name) = file.name else { continue };
+            let Some(path) = name.local_path() else { continue };
+            let src = match fs::read_to_string(path) {
+                Ok(src) if file.src_hash.matches(&src) => src,
+                Ok(_) => {
+                    self.report_file(path, "the file changed during the compilation");
+                    continue;
+                }
+                Err(e) => {
+                    self.report_file(path, &e.to_string());
+                    continue;
+                }
+            };
+
+            let out_path = self.out_dir.join(relative_path(path));
+            match written.entry(out_path.clone()) {
+                Entry::Occupied(other) => {
+                    let error = format!(
+                        "`{}` is already the fixed version of `{}`",
+                        out_path.display(),
+                        other.get().display()
+                    );
+                    self.report_file(path, &error);
+                    continue;
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(path.to_owned());
+                }
+            }
+
+            edits.sort_by_key(|edit| (edit.range.start, edit.range.end));
+            let fixed = apply_edits(&src, &edits);
+            let result = out_path
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|()| fs::write(&out_path, fixed));
+            match result {
+                Ok(()) => applied += edits.len(),
+                Err(e) => self.report_file(&out_path, &e.to_string()),
+            }
+        }
+        if applied > 0 {
+            self.report(
+                Level::Note,
+                &format!(
+                    "applied {} suggestion{} in `{}`",
+                    applied,
+                    pluralize!(applied),
+                    self.out_dir.display()
+                ),
+                Span::default(),
+            );
+        }
+    }
+
+    fn report_file(&mut self, path: &Path, error: &str) {
+        let msg = format!("could not apply suggestions to `{}`: {}", path.display(), error);
+        self.report(Level::Warning, &msg, Span::default());
+    }
+
+    fn report(&mut self, level: Level, msg: &str, span: Span) {
+        let mut diag = Diagnostic::new(level, msg);
+        if !span.is_dummy() {
+            diag.set_span(span);
+        }
+        self.inner.emit_diagnostic(&diag);
+    }
+}
+
+/// Applies `edits`, sorted by position and not overlapping, to `src`.
+fn apply_edits(src: &str, edits: &[Edit]) -> String {
+    let mut fixed = String::with_capacity(src.len());
+    let mut pos = 0;
+    for edit in edits {
+        fixed.push_str(&src[pos..edit.range.start]);
+        fixed.push_str(&edit.snippet);
+        pos = edit.range.end;
+    }
+    fixed.push_str(&src[pos..]);
+    fixed
+}
+
+/// Returns `path` without its root and parent directory components, so that it can be joined
+/// to the output directory. Distinct paths can have the same relative path.
+fn relative_path(path: &Path) -> PathBuf {
+    path.components().filter(|component| matches!(component, Component::Normal(_))).collect()
+}
+
+impl Emitter for FixEmitter {
+    fn emit_diagnostic(&mut self, diag: &Diagnostic) {
+        self.inner.emit_diagnostic(diag);
+        match diag.level {
+            Level::Bug | Level::DelayedBug => self.emitted_bug = true,
+            Level::Fatal | Level::Error { .. } => self.emitted_error = true,
+            _ => {}
+        }
+
+        let args = self.to_fluent_args(diag.args());
+        for suggestion in diag.suggestions.iter().flatten() {
+            if suggestion.applicability != Applicability::MachineApplicable {
+                continue;
+            }
+            if let [substitution] = &suggestion.substitutions[..] {
+                let msg = self.translate_message(&suggestion.msg, &args).into_owned();
+                self.substitutions.push((msg, substitution.parts.clone()));
+            }
+        }
+    }
+
+    fn emit_artifact_notification(&mut self, path: &Path, artifact_type: &str) {
+        self.inner.emit_artifact_notification(path, artifact_type)
+    }
+
+    fn emit_future_breakage_report(&mut self, diags: Vec<Diagnostic>) {
+        self.inner.emit_future_breakage_report(diags)
+    }
+
+    fn emit_unused_externs(&mut self, lint_level: rustc_lint_defs::Level, unused_externs: &[&str]) {
+        self.inner.emit_unused_externs(lint_level, unused_externs)
+    }
+
+    fn should_show_explain(&self) -> bool {
+        self.inner.should_show_explain()
+    }
+
+    fn should_show_error_count(&self) -> bool {
+        self.inner.should_show_error_count()
+    }
+
+    fn supports_color(&self) -> bool {
+        self.inner.supports_color()
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        self.inner.source_map()
+    }
+
+    fn fluent_bundle(&self) -> Option<&Lrc<FluentBundle>> {
+        self.inner.fluent_bundle()
+    }
+
+    fn fallback_fluent_bundle(&self) -> &FluentBundle {
+        self.inner.fallback_fluent_bundle()
+    }
+}
+
+impl Drop for FixEmitter {
+    fn drop(&mut self) {
+        // The compilation unwinds both when it aborts after errors, with a `FatalError`, and on an
+        // ICE, and the payload of the unwind is not available here. Only an unwind that follows an
+        // error, and no bug, can be an abort: otherwise, it is an ICE and the fixes may be partial.
+        if std::thread::panicking() && (self.emitted_bug || !self.emitted_error) {
+            return;
+        }
+        self.apply();
+    }
+}
@@ -0,0 +1,119 @@
+use super::*;
+
+use crate::LazyFallbackBundle;
+use rustc_span::source_map::FilePathMapping;
+use rustc_span::BytePos;
+
+use std::sync::{Arc, Mutex};
+
+/// An emitter that records the messages of the diagnostics it is given.
+struct Recorder {
+    messages: Arc<Mutex<Vec<String>>>,
+    fallback_bundle: LazyFallbackBundle,
+}
+
+impl Emitter for Recorder {
+    fn emit_diagnostic(&mut self, diag: &Diagnostic) {
+        let args = self.to_fluent_args(diag.args());
+        let msg = self.translate_messages(&diag.message, &args).into_owned();
+        self.messages.lock().unwrap().push(msg);
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        None
+    }
+
+    fn fluent_bundle(&self) -> Option<&Lrc<FluentBundle>> {
+        None
+    }
+
+    fn fallback_fluent_bundle(&self) -> &FluentBundle {
+        &**self.fallback_bundle
+    }
+}
+
+/// Selects the edits to apply among `substitutions`, made of `(lo, hi, snippet)` parts in a
+/// source file containing `code`, and returns them with the recorded warnings.
+fn test_select(code: &str, substitutions: &[&[(u32, u32, &str)]]) -> (Vec<Edit>, Vec<String>) {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), code.to_owned());
+        let fallback_bundle =
+            crate::fallback_fluent_bundle(rustc_error_messages::DEFAULT_LOCALE_RESOURCES, false);
+        let messages = Arc::new(Mutex::new(vec![]));
+        let recorder = Recorder { messages: messages.clone(), fallback_bundle };
+
+        let mut fe = FixEmitter::new(Box::new(recorder), sm, PathBuf::from("fixed"));
+        for (i, parts) in substitutions.iter().enumerate() {
+            let parts = parts
+                .iter()
+                .map(|&(lo, hi, snippet)| SubstitutionPart {
+                    span: Span::with_root_ctxt(BytePos(lo), BytePos(hi)),
+                    snippet: snippet.to_owned(),
+                })
+                .collect();
+            fe.substitutions.push((format!("suggestion {}", i), parts));
+        }
+        let mut files = fe.select_edits();
+        let edits = files.pop().map_or(vec![], |(_, edits)| edits);
+        assert!(files.is_empty());
+        let messages = messages.lock().unwrap().clone();
+        (edits, messages)
+    })
+}
+
+fn edit(range: Range<usize>, snippet: &str) -> Edit {
+    Edit { range, snippet: snippet.to_owned() }
+}
+
+#[test]
+fn apply_sorted_edits() {
+    let edits = [edit(0..0, "_"), edit(4..4, "mut "), edit(8..11, "")];
+    assert_eq!(apply_edits("let x = foo;", &edits), "_let mut x = ;");
+}
+
+#[test]
+fn duplicates_are_applied_once() {
+    let (edits, messages) = test_select("let x = 1;", &[&[(4, 5, "_x")], &[(4, 5, "_x")]]);
+    assert_eq!(edits, [edit(4..5, "_x")]);
+    assert!(messages.is_empty());
+}
+
+#[test]
+fn overlapping_suggestions_are_reported() {
+    let (edits, messages) =
+        test_select("let x = 1;", &[&[(4, 5, "_x")], &[(0, 10, "")], &[(9, 10, "")]]);
+    assert_eq!(edits, [edit(4..5, "_x"), edit(9..10, "")]);
+    assert_eq!(
+        messages,
+        ["suggestion not applied as it overlaps with another one: suggestion 1".to_owned()]
+    );
+}
+
+#[test]
+fn insertions_at_the_same_position_conflict() {
+    let (edits, messages) = test_select("let x = 1;", &[&[(4, 4, "mut ")], &[(4, 4, "ref ")]]);
+    assert_eq!(edits, [edit(4..4, "mut ")]);
+    assert_eq!(messages.len(), 1);
+}
+
+#[test]
+fn adjacent_edits_do_not_conflict() {
+    let (edits, messages) = test_select("let x = 1;", &[&[(4, 5, "_x")], &[(5, 5, ": i32")]]);
+    assert_eq!(edits, [edit(4..5, "_x"), edit(5..5, ": i32")]);
+    assert!(messages.is_empty());
+}
+
+#[test]
+fn suggestions_with_several_parts_are_atomic() {
+    let (edits, messages) =
+        test_select("let x = 1;", &[&[(8, 9, "2")], &[(4, 5, "y"), (8, 9, "3")]]);
+    assert_eq!(edits, [edit(8..9, "2")]);
+    assert_eq!(messages.len(), 1);
+}
+
+#[test]
+fn relative_output_paths() {
+    assert_eq!(relative_path(Path::new("/src/lib.rs")), Path::new("src/lib.rs"));
+    assert_eq!(relative_path(Path::new("../foo/./main.rs")), Path::new("foo/main.rs"));
+}
@@ -53,9 +53,11 @@
 mod diagnostic;
 mod diagnostic_builder;
 pub mod emitter;
+pub mod fix;
 pub mod json;
 mod lock;
 pub mod registry;
+pub mod sarif;
 mod snippet;
 mod styled_buffer;
 
@@ -376,6 +378,13 @@ impl error::Error for ExplicitBug {}
 pub use diagnostic_builder::{DiagnosticBuilder, EmissionGuarantee};
 use std::backtrace::Backtrace;
 
+/// Decides which diagnostics a [`Handler`] emits, beyond the lint levels. The filter sees the
+/// diagnostics replayed from the incremental cache as well as the new ones.
+pub trait DiagnosticFilter {
+    /// Returns whether `diagnostic` must not be emitted.
+    fn suppresses(&self, diagnostic: &Diagnostic) -> bool;
+}
+
 /// A handler deals with errors and other compiler output.
 /// Certain errors (fatal, bug, unimpl) may cause immediate exit,
 /// others log errors for later reporting.
@@ -448,6 +457,9 @@ struct HandlerInner {
     ///
     /// [RFC-2383]: https://rust-lang.github.io/rfcs/2383-lint-reasons.html
     fulfilled_expectations: FxHashSet<LintExpectationId>,
+
+    /// Suppresses some of the diagnostics after they are stored for incremental replay.
+    diagnostic_filter: Option<Lrc<dyn DiagnosticFilter + sync::Send + sync::Sync>>,
 }
 
 /// A key denoting where from a diagnostic was stashed.
@@ -586,10 +598,18 @@ pub fn with_emitter_and_flags(
                 check_unstable_expect_diagnostics: false,
                 unstable_expect_diagnostics: Vec::new(),
                 fulfilled_expectations: Default::default(),
+                diagnostic_filter: None,
             }),
         }
     }
 
+    pub fn set_diagnostic_filter(
+        &self,
+        filter: Lrc<dyn DiagnosticFilter + sync::Send + sync::Sync>,
+    ) {
+        self.inner.borrow_mut().diagnostic_filter = Some(filter);
+    }
+
     // This is here to not allow mutation of flags;
     // as of this writing it's only used in tests in librustc_middle.
     pub fn can_emit_warnings(&self) -> bool {
@@ -1107,6 +1127,12 @@ fn emit_diagnostic(&mut self, diagnostic: &mut Diagnostic) -> Option<ErrorGuaran
             return None;
         }
 
+        if let Some(filter) = &self.diagnostic_filter {
+            if filter.suppresses(diagnostic) {
+                return None;
+            }
+        }
+
         if let Some(ref code) = diagnostic.code {
             self.emitted_diagnostic_codes.insert(code.clone());
         }
@@ -1186,7 +1212,7 @@ fn print_error_count(&mut self, registry: &Registry) {
             1 => "aborting due to previous error".to_string(),
             count => format!("aborting due to {count} previous errors"),
         };
-        if self.treat_err_as_bug() {
+        if self.treat_err_as_bug() || !self.emitter.should_show_error_count() {
             return;
         }
 
@@ -0,0 +1,336 @@
+//! A SARIF emitter for errors.
+//!
+//! This converts errors to the results of a [SARIF 2.1.0] log, the format ingested by code
+//! scanning tools. Unlike the JSON emitter, it cannot stream diagnostics: a SARIF log is a single
+//! document, so the results are collected and written when the emitter is dropped.
+//!
+//! Each diagnostic becomes one result. Its primary spans become the locations of the result, its
+//! labelled secondary spans and the spans of its children become related locations, and each
+//! substitution of its suggestions becomes a fix. Children without a span are appended to the
+//! message of the result.
+//!
+//! [SARIF 2.1.0]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use crate::emitter::Emitter;
+use crate::registry::Registry;
+use crate::{CodeSuggestion, DiagnosticId, FluentBundle, LazyFallbackBundle, Level, MultiSpan};
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::sync::Lrc;
+use rustc_error_messages::FluentArgs;
+use rustc_serialize::json::{Json, ToJson};
+use rustc_span::source_map::{FilePathMapping, SourceMap};
+use rustc_span::Span;
+use std::io::{self, Write};
+use std::mem;
+
+#[cfg(test)]
+mod tests;
+
+pub struct SarifEmitter {
+    dst: Box<dyn Write + Send>,
+    registry: Option<Registry>,
+    sm: Lrc<SourceMap>,
+    fluent_bundle: Option<Lrc<FluentBundle>>,
+    fallback_bundle: LazyFallbackBundle,
+    /// The results emitted so far, written out when the emitter is dropped.
+    results: Vec<Json>,
+    /// The rules (error codes and lints) the results refer to, in order of first use.
+    rules: Vec<Json>,
+    /// The index of each rule in `rules`, by error code or lint name.
+    rule_indices: FxHashMap<String, usize>,
+}
+
+impl SarifEmitter {
+    pub fn stderr(
+        registry: Option<Registry>,
+        source_map: Lrc<SourceMap>,
+        fluent_bundle: Option<Lrc<FluentBundle>>,
+        fallback_bundle: LazyFallbackBundle,
+    ) -> SarifEmitter {
+        SarifEmitter::new(
+            Box::new(io::BufWriter::new(io::stderr())),
+            registry,
+            source_map,
+            fluent_bundle,
+            fallback_bundle,
+        )
+    }
+
+    pub fn basic(
+        fluent_bundle: Option<Lrc<FluentBundle>>,
+        fallback_bundle: LazyFallbackBundle,
+    ) -> SarifEmitter {
+        let file_path_mapping = FilePathMapping::empty();
+        SarifEmitter::stderr(
+            None,
+            Lrc::new(SourceMap::new(file_path_mapping)),
+            fluent_bundle,
+            fallback_bundle,
+        )
+    }
+
+    pub fn new(
+        dst: Box<dyn Write + Send>,
+        registry: Option<Registry>,
+        source_map: Lrc<SourceMap>,
+        fluent_bundle: Option<Lrc<FluentBundle>>,
+        fallback_bundle: LazyFallbackBundle,
+    ) -> SarifEmitter {
+        SarifEmitter {
+            dst,
+            registry,
+            sm: source_map,
+            fluent_bundle,
+            fallback_bundle,
+            results: vec![],
+            rules: vec![],
+            rule_indices: FxHashMap::default(),
+        }
+    }
+
+    /// Returns the index of the rule for `code` in `self.rules`, adding the rule if needed.
+    fn rule_index(&mut self, code: &DiagnosticId) -> usize {
+        let (id, help_uri) = match code {
+            DiagnosticId::Error(code) => {
+                (code, Some(format!("https://doc.rust-lang.org/error-index.html#{}", code)))
+            }
+            DiagnosticId::Lint { name, .. } => (name, None),
+        };
+        if let Some(&index) = self.rule_indices.get(id) {
+            return index;
+        }
+        let explanation = self
+            .registry
+            .as_ref()
+            .and_then(|registry| registry.try_find_description(id).ok().flatten());
+        self.rules.push(object([
+            ("id", id.to_json()),
+            ("fullDescription", explanation.map(message).to_json()),
+            ("helpUri", help_uri.to_json()),
+        ]));
+        self.rule_indices.insert(id.clone(), self.rules.len() - 1);
+        self.rules.len() - 1
+    }
+
+    fn result(&self, diag: &crate::Diagnostic, rule_index: Option<usize>) -> Json {
+        let args = self.to_fluent_args(diag.args());
+        let mut text = self.translate_messages(&diag.message, &args).into_owned();
+
+        let mut locations = vec![];
+        let mut related_locations = vec![];
+        for span_label in diag.span.span_labels() {
+            let label = span_label.label.as_ref().map(|label| self.translate_message(label, &args));
+            let location = self.location(span_label.span, label.as_deref());
+            if span_label.is_primary {
+                locations.extend(location);
+            } else {
+                related_locations.extend(location);
+            }
+        }
+
+        for child in &diag.children {
+            let child_text = format!(
+                "{}: {}",
+                child.level.to_str(),
+                self.translate_messages(&child.message, &args)
+            );
+            let span = child.render_span.as_ref().unwrap_or(&child.span);
+            let child_locations = self.locations(span, &child_text);
+            if child_locations.is_empty() {
+                text.push('\n');
+                text.push_str(&child_text);
+            }
+            related_locations.extend(child_locations);
+        }
+
+        let fixes = diag
+            .suggestions
+            .iter()
+            .flatten()
+            .flat_map(|suggestion| self.fixes(suggestion, &args))
+            .collect::<Vec<_>>();
+
+        object([
+            ("ruleId", rule_index.map(|index| self.rules[index]["id"].clone()).to_json()),
+            ("ruleIndex", rule_index.to_json()),
+            ("level", level(diag.level).to_json()),
+            ("message", message(&text)),
+            ("locations", locations.to_json()),
+            ("relatedLocations", related_locations.to_json()),
+            ("fixes", fixes.to_json()),
+        ])
+    }
+
+    /// Returns a location for each primary span of `span`, all with the message `text`.
+    fn locations(&self, span: &MultiSpan, text: &str) -> Vec<Json> {
+        span.primary_spans().iter().filter_map(|&span| self.location(span, Some(text))).collect()
+    }
+
+    /// Returns the location of `span`, unless it is a dummy span.
+    fn location(&self, span: Span, text: Option<&str>) -> Option<Json> {
+        let physical_location = object([
+            ("artifactLocation", self.artifact_location(span)?),
+            ("region", self.region(span)?),
+        ]);
+        Some(object([
+            ("physicalLocation", physical_location),
+            ("message", text.map(message).to_json()),
+        ]))
+    }
+
+    fn artifact_location(&self, span: Span) -> Option<Json> {
+        if span.is_dummy() {
+            return None;
+        }
+        let file = self.sm.lookup_source_file(span.lo());
+        // SARIF URIs always use forward slashes, relative paths are resolved against the
+        // directory the compiler was invoked from.
+        let uri = self.sm.filename_for_diagnostics(&file.name).to_string().replace('\\', "/");
+        Some(object([("uri", uri.to_json())]))
+    }
+
+    fn region(&self, span: Span) -> Option<Json> {
+        if span.is_dummy() {
+            return None;
+        }
+        let start = self.sm.lookup_char_pos(span.lo());
+        let end = self.sm.lookup_char_pos(span.hi());
+        Some(object([
+            ("startLine", start.line.to_json()),
+            ("startColumn", (start.col.0 + 1).to_json()),
+            ("endLine", end.line.to_json()),
+            ("endColumn", (end.col.0 + 1).to_json()),
+        ]))
+    }
+
+    /// Returns a fix for each substitution of `suggestion`, as substitutions are alternatives.
+    fn fixes(&self, suggestion: &CodeSuggestion, args: &FluentArgs<'_>) -> Vec<Json> {
+        let description = self.translate_message(&suggestion.msg, args);
+        suggestion
+            .substitutions
+            .iter()
+            .map(|substitution| {
+                // The replacements of a substitution, grouped by file.
+                let mut changes: Vec<(Json, Vec<Json>)> = vec![];
+                for part in &substitution.parts {
+                    let (Some(artifact_location), Some(region)) =
+                        (self.artifact_location(part.span), self.region(part.span))
+                    else {
+                        continue;
+                    };
+                    let replacement = object([
+                        ("deletedRegion", region),
+                        ("insertedContent", object([("text", part.snippet.to_json())])),
+                    ]);
+                    match changes.iter_mut().find(|(location, _)| *location == artifact_location) {
+                        Some((_, replacements)) => replacements.push(replacement),
+                        None => changes.push((artifact_location, vec![replacement])),
+                    }
+                }
+                let changes = changes
+                    .into_iter()
+                    .map(|(artifact_location, replacements)| {
+                        object([
+                            ("artifactLocation", artifact_location),
+                            ("replacements", replacements.to_json()),
+                        ])
+                    })
+                    .collect::<Vec<_>>();
+                object([
+                    ("description", message(&description)),
+                    ("artifactChanges", changes.to_json()),
+                    (
+                        "properties",
+                        object([(
+                            "applicability",
+                            format!("{:?}", suggestion.applicability).to_json(),
+                        )]),
+                    ),
+                ])
+            })
+            .collect()
+    }
+}
+
+impl Emitter for SarifEmitter {
+    fn emit_diagnostic(&mut self, diag: &crate::Diagnostic) {
+        // Failure notes, such as "Some errors have detailed explanations", only summarize the
+        // other diagnostics.
+        if diag.level == Level::FailureNote {
+            return;
+        }
+        let rule_index = diag.code.as_ref().map(|code| self.rule_index(code));
+        let result = self.result(diag, rule_index);
+        self.results.push(result);
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        Some(&self.sm)
+    }
+
+    fn fluent_bundle(&self) -> Option<&Lrc<FluentBundle>> {
+        self.fluent_bundle.as_ref()
+    }
+
+    fn fallback_fluent_bundle(&self) -> &FluentBundle {
+        &**self.fallback_bundle
+    }
+
+    fn should_show_explain(&self) -> bool {
+        false
+    }
+
+    fn should_show_error_count(&self) -> bool {
+        false
+    }
+}
+
+impl Drop for SarifEmitter {
+    fn drop(&mut self) {
+        let driver = object([
+            ("name", "rustc".to_json()),
+            ("informationUri", "https://www.rust-lang.org/".to_json()),
+            ("rules", mem::take(&mut self.rules).to_json()),
+        ]);
+        let run = object([
+            ("tool", object([("driver", driver)])),
+            ("columnKind", "unicodeCodePoints".to_json()),
+            ("results", mem::take(&mut self.results).to_json()),
+        ]);
+        let log = object([
+            ("$schema", "https://json.schemastore.org/sarif-2.1.0.json".to_json()),
+            ("version", "2.1.0".to_json()),
+            ("runs", vec![run].to_json()),
+        ]);
+        // Don't panic, this may run while unwinding from an ICE.
+        if let Err(e) = writeln!(&mut self.dst, "{}", log.pretty()).and_then(|_| self.dst.flush()) {
+            eprintln!("failed to print diagnostics: {:?}", e);
+        }
+    }
+}
+
+/// Builds a JSON object, leaving out the `null` fields as SARIF does not allow `null` for optional
+/// properties.
+fn object<const N: usize>(fields: [(&str, Json); N]) -> Json {
+    Json::Object(
+        fields
+            .into_iter()
+            .filter(|(_, value)| *value != Json::Null)
+            .map(|(key, value)| (key.to_string(), value))
+            .collect(),
+    )
+}
+
+fn message(text: &str) -> Json {
+    object([("text", text.to_json())])
+}
+
+fn level(level: Level) -> &'static str {
+    match level {
+        Level::Bug | Level::DelayedBug | Level::Fatal | Level::Error { .. } => "error",
+        Level::Warning => "warning",
+        Level::Note | Level::OnceNote | Level::Help | Level::FailureNote => "note",
+        Level::Allow | Level::Expect(_) => "none",
+    }
+}
@@ -0,0 +1,128 @@
+use super::*;
+
+use crate::{Applicability, Handler};
+use rustc_serialize::json;
+use rustc_span::BytePos;
+
+use std::path::Path;
+use std::str;
+use std::sync::{Arc, Mutex};
+
+struct Shared<T> {
+    data: Arc<Mutex<T>>,
+}
+
+impl<T: Write> Write for Shared<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.data.lock().unwrap().flush()
+    }
+}
+
+/// Emits the diagnostics of `f` for a source file containing `code`, and returns the results of
+/// the SARIF log.
+fn test_results(code: &str, f: impl FnOnce(&Handler)) -> (Json, Vec<Json>) {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), code.to_owned());
+        let fallback_bundle =
+            crate::fallback_fluent_bundle(rustc_error_messages::DEFAULT_LOCALE_RESOURCES, false);
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let se = SarifEmitter::new(
+            Box::new(Shared { data: output.clone() }),
+            None,
+            sm,
+            None,
+            fallback_bundle,
+        );
+        let handler = Handler::with_emitter(true, None, Box::new(se));
+        f(&handler);
+        // The log is written when the emitter is dropped.
+        drop(handler);
+
+        let bytes = output.lock().unwrap();
+        let log = json::from_str(str::from_utf8(&bytes).unwrap()).unwrap();
+        assert_eq!(log["version"].as_string(), Some("2.1.0"));
+        let run = log["runs"][0].clone();
+        let results = run["results"].as_array().unwrap().clone();
+        (run, results)
+    })
+}
+
+fn span(lo: u32, hi: u32) -> Span {
+    Span::with_root_ctxt(BytePos(lo), BytePos(hi))
+}
+
+#[test]
+fn result_with_code_and_labels() {
+    let (run, results) = test_results("let x: u8 = 256;\n", |handler| {
+        let mut err = handler.struct_span_err_with_code(
+            span(12, 15),
+            "literal out of range",
+            DiagnosticId::Error("E0000".to_string()),
+        );
+        err.span_label(span(12, 15), "this literal");
+        err.span_label(span(7, 9), "this type");
+        err.note("the range of `u8` is `0..=255`");
+        err.emit();
+    });
+
+    assert_eq!(results.len(), 1);
+    let result = &results[0];
+    assert_eq!(result["level"].as_string(), Some("error"));
+    assert_eq!(result["ruleId"].as_string(), Some("E0000"));
+    assert_eq!(run["tool"]["driver"]["rules"][0]["id"].as_string(), Some("E0000"));
+    assert_eq!(
+        result["message"]["text"].as_string(),
+        Some("literal out of range\nnote: the range of `u8` is `0..=255`")
+    );
+
+    let location = &result["locations"][0];
+    assert_eq!(location["message"]["text"].as_string(), Some("this literal"));
+    let physical_location = &location["physicalLocation"];
+    assert_eq!(physical_location["artifactLocation"]["uri"].as_string(), Some("test.rs"));
+    assert_eq!(physical_location["region"]["startLine"].as_u64(), Some(1));
+    assert_eq!(physical_location["region"]["startColumn"].as_u64(), Some(13));
+    assert_eq!(physical_location["region"]["endColumn"].as_u64(), Some(16));
+
+    let related_location = &result["relatedLocations"][0];
+    assert_eq!(related_location["message"]["text"].as_string(), Some("this type"));
+}
+
+#[test]
+fn suggestion_becomes_fix() {
+    let (_, results) = test_results("fn main() { let X = 1; }\n", |handler| {
+        let mut warn = handler.struct_span_warn(span(16, 17), "variable `X` should be snake case");
+        warn.span_suggestion(
+            span(16, 17),
+            "convert the identifier to snake case",
+            "x",
+            Applicability::MaybeIncorrect,
+        );
+        warn.emit();
+        let note = "For more information about this error, try `rustc --explain E0000`.";
+        handler.emit_diagnostic(&mut crate::Diagnostic::new(Level::FailureNote, note));
+    });
+
+    // The failure note is not a result.
+    assert_eq!(results.len(), 1);
+    let result = &results[0];
+    assert_eq!(result["level"].as_string(), Some("warning"));
+    assert!(result.find("ruleId").is_none());
+
+    let fix = &result["fixes"][0];
+    assert_eq!(
+        fix["description"]["text"].as_string(),
+        Some("convert the identifier to snake case")
+    );
+    assert_eq!(fix["properties"]["applicability"].as_string(), Some("MaybeIncorrect"));
+    let change = &fix["artifactChanges"][0];
+    assert_eq!(change["artifactLocation"]["uri"].as_string(), Some("test.rs"));
+    let replacement = &change["replacements"][0];
+    assert_eq!(replacement["insertedContent"]["text"].as_string(), Some("x"));
+    assert_eq!(replacement["deletedRegion"]["startColumn"].as_u64(), Some(17));
+}
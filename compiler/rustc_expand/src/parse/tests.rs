@@ -9,6 +9,7 @@
 use rustc_errors::PResult;
 use rustc_parse::new_parser_from_source_str;
 use rustc_parse::parser::ForceCollect;
+use rustc_parse::resilient::{parse_source_str_resilient, DelimError, PieceKind};
 use rustc_session::parse::ParseSess;
 use rustc_span::create_default_session_globals_then;
 use rustc_span::source_map::FilePathMapping;
@@ -328,6 +329,38 @@ fn out_of_line_mod() {
     });
 }
 
+#[test]
+fn resilient_parse() {
+    create_default_session_globals_then(|| {
+        let source = "// A comment.\nfn f() { let x = ; }\nstruct 1;\nfn g() {}\n}\n";
+        let parse = parse_source_str_resilient(
+            &sess(),
+            PathBuf::from("bogofile").into(),
+            source.to_string(),
+        );
+
+        let items = &parse.krate.items;
+        assert_eq!(items.len(), 3);
+        let ast::ItemKind::Fn(ref f) = items[0].kind else { panic!() };
+        assert!(f.body.as_ref().unwrap().stmts.iter().any(|stmt| matches!(
+            stmt.kind,
+            ast::StmtKind::Expr(ref expr) if matches!(expr.kind, ast::ExprKind::Err)
+        )));
+        assert!(matches!(items[1].kind, ast::ItemKind::Err));
+        assert_eq!(parse.text(items[1].span), "struct 1;");
+        assert!(matches!(items[2].kind, ast::ItemKind::Fn(..)));
+
+        assert!(matches!(
+            parse.delim_errors[..],
+            [DelimError::Unopened { delim: Delimiter::Brace, span }] if parse.text(span) == "}"
+        ));
+
+        assert_eq!(parse.pieces[0].kind, PieceKind::Comment);
+        let text: String = parse.pieces.iter().map(|piece| parse.text(piece.span)).collect();
+        assert_eq!(text, source);
+    });
+}
+
 #[test]
 fn eqmodws() {
     assert_eq!(matches_codepattern("", ""), true);
@@ -0,0 +1,93 @@
+//! Explanations of the codegen units that are recompiled, for `-Z incremental-explain`.
+//!
+//! When a node of the previous session cannot be marked green, the dep graph records which of
+//! its dependencies was found red. Following these records from the node of a codegen unit gives
+//! the chain of changed queries that caused its recompilation, down to the changed input. Each
+//! recompiled codegen unit is reported with its chain, then the changed inputs are summarized
+//! with the number of codegen units they caused to be recompiled.
+
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_errors::Diagnostic;
+use rustc_middle::dep_graph::{DepContext, DepNode, DepNodeExt, RedNode};
+use rustc_middle::mir::mono::CodegenUnit;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::cgu_reuse_tracker::CguReuse;
+
+/// Reports why the codegen units of `codegen_units` that are not reused according to `reuse`
+/// are recompiled.
+pub fn explain_cgu_reuse<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    codegen_units: &[&CodegenUnit<'tcx>],
+    reuse: &[CguReuse],
+) {
+    tcx.dep_graph.with_ignore(|| {
+        let mut changed_inputs: FxIndexMap<DepNode, usize> = Default::default();
+        let mut recompiled = 0;
+        for (cgu, _) in codegen_units.iter().zip(reuse).filter(|(_, r)| **r == CguReuse::No) {
+            recompiled += 1;
+            let mut diag = tcx
+                .sess
+                .struct_note_without_error(&format!("codegen unit `{}` is recompiled", cgu.name()));
+            if tcx.dep_graph.previous_work_product(&cgu.work_product_id()).is_none() {
+                diag.note("it was not saved by the previous session");
+                diag.emit();
+                continue;
+            }
+
+            let chain = tcx.dep_graph.explain_red_node(&cgu.codegen_dep_node(tcx));
+            for red in &chain {
+                note_red_node(tcx, &mut diag, red);
+            }
+            // Inputs that are re-evaluated in every session, such as `hir_crate`, change as
+            // soon as anything changes, so the last query that depends on them is more telling.
+            let input = chain
+                .iter()
+                .rev()
+                .find(|red| !tcx.is_eval_always(red.node.kind))
+                .or_else(|| chain.last());
+            match input {
+                Some(input) => {
+                    diag.help(&format!("the changed input is `{:?}`", input.node));
+                    *changed_inputs.entry(input.node).or_default() += 1;
+                }
+                None => {
+                    diag.note("no changed dependency was recorded for it");
+                }
+            }
+            diag.emit();
+        }
+
+        if recompiled == 0 {
+            return;
+        }
+        let mut diag = tcx.sess.struct_note_without_error(&format!(
+            "{} of {} codegen units are recompiled",
+            recompiled,
+            codegen_units.len()
+        ));
+        changed_inputs.sort_by(|_, a, _, b| b.cmp(a));
+        for (input, count) in changed_inputs {
+            diag.note(&format!(
+                "{} codegen unit{} recompiled because `{:?}` changed",
+                count,
+                if count == 1 { " is" } else { "s are" },
+                input
+            ));
+        }
+        diag.emit();
+    })
+}
+
+fn note_red_node(tcx: TyCtxt<'_>, diag: &mut Diagnostic, red: &RedNode) {
+    let msg = match red.new_fingerprint {
+        Some(new_fingerprint) => format!(
+            "`{:?}` changed, its hash went from {} to {}",
+            red.node, red.prev_fingerprint, new_fingerprint
+        ),
+        None => format!("`{:?}` changed", red.node),
+    };
+    match red.node.extract_def_id(tcx) {
+        Some(def_id) => diag.span_note(tcx.def_span(def_id), &msg),
+        None => diag.note(&msg),
+    };
+}
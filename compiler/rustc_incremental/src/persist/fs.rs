@@ -39,6 +39,11 @@
 //!    at the beginning of the session has become obsolete because we have just
 //!    published a more current version. Thus the compiler will delete it.
 //!
+//! If there is no finalized session directory to copy from, the new session
+//! directory can also be populated from the session that another working copy
+//! or machine published to the shared cache of `-Z incremental-shared-cache`,
+//! see the `shared` module.
+//!
 //! ## Garbage Collection
 //!
 //! Naively following the above protocol might lead to old session directories
@@ -110,6 +115,8 @@
 use rustc_fs_util::{link_or_copy, LinkOrCopy};
 use rustc_session::{Session, StableCrateId};
 
+use super::shared::{copy_from_shared_cache, publish_to_shared_cache};
+
 use std::fs as std_fs;
 use std::io::{self, ErrorKind};
 use std::mem;
@@ -256,7 +263,25 @@ pub fn prepare_session_directory(
         let source_directory = find_source_directory(&crate_dir, &source_directories_already_tried);
 
         let Some(source_directory) = source_directory else {
-            // There's nowhere to copy from, we're done
+            // There's nowhere to copy from locally, try the shared cache
+            if let Some(shared_dir) = &sess.opts.debugging_opts.incremental_shared_cache {
+                match copy_from_shared_cache(sess, shared_dir, &crate_dir, &session_dir) {
+                    Ok(true) => {
+                        debug!("successfully copied data from the shared cache");
+                        sess.init_incr_comp_session(session_dir, directory_lock, true);
+                        return Ok(());
+                    }
+                    Ok(false) => {}
+                    Err(err) => {
+                        sess.warn(&format!(
+                            "failed to read the shared incremental compilation cache `{}`: {}",
+                            shared_dir.display(),
+                            err
+                        ));
+                    }
+                }
+            }
+
             debug!(
                 "no source directory found. Continuing with empty session \
                     directory."
@@ -375,6 +400,22 @@ pub fn finalize_session_directory(sess: &Session, svh: Svh) {
         Ok(_) => {
             debug!("finalize_session_directory() - directory renamed successfully");
 
+            // Never publish a session with errors, even if it could not be deleted.
+            if sess.opts.debugging_opts.incremental_shared_cache_publish
+                && !sess.has_errors_or_delayed_span_bugs()
+            {
+                if let Some(shared_dir) = &sess.opts.debugging_opts.incremental_shared_cache {
+                    if let Err(err) = publish_to_shared_cache(sess, shared_dir, &new_path) {
+                        sess.warn(&format!(
+                            "failed to publish to the shared incremental compilation cache \
+                             `{}`: {}",
+                            shared_dir.display(),
+                            err
+                        ));
+                    }
+                }
+            }
+
             // This unlocks the directory
             sess.finalize_incr_comp_session(new_path);
         }
@@ -900,9 +941,126 @@ pub fn garbage_collect_session_directories(sess: &Session) -> io::Result<()> {
         mem::drop(lock);
     }
 
+    evict_least_recently_used(sess, &session_directory)
+}
+
+/// Deletes the least recently used finalized session directories of the
+/// whole incremental directory, across all crates, until it fits in the
+/// budget of `-Z incremental-max-size` and `-Z incremental-max-age`. The
+/// current session directory is never evicted, and neither are the ones that
+/// are being read by other processes, as their lock cannot be acquired.
+fn evict_least_recently_used(sess: &Session, current_session_dir: &Path) -> io::Result<()> {
+    let max_size = sess.opts.debugging_opts.incremental_max_size;
+    let max_age = sess.opts.debugging_opts.incremental_max_age;
+    if max_size.is_none() && max_age.is_none() {
+        return Ok(());
+    }
+
+    let incr_comp_dir = current_session_dir.parent().unwrap().parent().unwrap();
+    debug!("evict_least_recently_used() - incremental directory: {}", incr_comp_dir.display());
+
+    // Sessions are never reused in place, a new one is created each time a
+    // crate is compiled, so their timestamp is the time of their last use.
+    let mut total_size = 0;
+    let mut candidates = vec![];
+    for crate_dir in incr_comp_dir.read_dir()? {
+        let Ok(crate_dir) = crate_dir else { continue };
+        let Ok(session_dirs) = crate_dir.path().read_dir() else {
+            // Not a crate directory
+            continue;
+        };
+        for session_dir in session_dirs {
+            let Ok(session_dir) = session_dir else { continue };
+            let directory_name = session_dir.file_name();
+            let directory_name = directory_name.to_string_lossy();
+            if !is_session_directory(&directory_name) {
+                continue;
+            }
+            let path = session_dir.path();
+            let size = session_dir_size(&path);
+            total_size += size;
+            if !is_finalized(&directory_name) || path == current_session_dir {
+                continue;
+            }
+            if let Ok(timestamp) = extract_timestamp_from_session_dir(&directory_name) {
+                candidates.push((timestamp, path, size));
+            }
+        }
+    }
+
+    let evictions = select_evictions(candidates, total_size, max_size, max_age, SystemTime::now());
+    let mut evicted = 0;
+    for (path, size) in evictions {
+        let lock_file_path = lock_file_path(&path);
+        let Ok(lock) = flock::Lock::new(
+            &lock_file_path,
+            false, // don't wait
+            false, // don't create the lock-file
+            true,  // get an exclusive lock
+        ) else {
+            debug!("evict_least_recently_used() - not evicting, still in use: {}", path.display());
+            continue;
+        };
+
+        debug!("evict_least_recently_used() - evicting `{}`", path.display());
+        if let Err(err) = safe_remove_dir_all(&path) {
+            sess.warn(&format!(
+                "Failed to evict incremental compilation session directory `{}`: {}",
+                path.display(),
+                err
+            ));
+        } else {
+            delete_session_dir_lock_file(sess, &lock_file_path);
+            total_size -= size;
+            evicted += 1;
+        }
+        mem::drop(lock);
+    }
+
+    if sess.opts.debugging_opts.incremental_info {
+        eprintln!(
+            "[incremental] evicted {} session directories, {} bytes remain in the \
+             incremental directory",
+            evicted, total_size
+        );
+    }
+
     Ok(())
 }
 
+/// Selects the session directories to evict among the `candidates`, least
+/// recently used first, so that the `total_size` of the incremental directory
+/// fits in `max_size` and that no session older than `max_age` remains.
+fn select_evictions(
+    mut candidates: Vec<(SystemTime, PathBuf, u64)>,
+    mut total_size: u64,
+    max_size: Option<u64>,
+    max_age: Option<Duration>,
+    now: SystemTime,
+) -> Vec<(PathBuf, u64)> {
+    candidates.sort_by_key(|&(timestamp, ..)| timestamp);
+    let mut evictions = vec![];
+    for (timestamp, path, size) in candidates {
+        let too_large = max_size.map_or(false, |max_size| total_size > max_size);
+        let too_old = max_age.map_or(false, |max_age| {
+            now.duration_since(timestamp).map_or(false, |age| age > max_age)
+        });
+        if !too_large && !too_old {
+            // The remaining candidates are more recent
+            break;
+        }
+        total_size = total_size.saturating_sub(size);
+        evictions.push((path, size));
+    }
+    evictions
+}
+
+/// Returns the total size of the files of a session directory.
+fn session_dir_size(path: &Path) -> u64 {
+    let Ok(entries) = path.read_dir() else { return 0 };
+    entries.filter_map(|entry| entry.ok()?.metadata().ok()).map(|metadata| metadata.len()).sum()
+}
+
 fn delete_old(sess: &Session, path: &Path) {
     debug!("garbage_collect_session_directories() - deleting `{}`", path.display());
 
@@ -82,3 +82,42 @@ fn test_find_source_directory_in_iter() {
         None
     );
 }
+
+#[test]
+fn test_select_evictions() {
+    let day = Duration::from_secs(24 * 60 * 60);
+    let now = UNIX_EPOCH + 100 * day;
+    let candidates = || {
+        vec![
+            (now - 2 * day, PathBuf::from("2"), 20),
+            (now - 10 * day, PathBuf::from("10"), 10),
+            (now - 5 * day, PathBuf::from("5"), 30),
+        ]
+    };
+    let paths = |evictions: Vec<(PathBuf, u64)>| {
+        evictions.into_iter().map(|(path, _)| path).collect::<Vec<_>>()
+    };
+
+    // Least recently used first, until the size fits
+    assert_eq!(
+        paths(select_evictions(candidates(), 100, Some(70), None, now)),
+        [PathBuf::from("10"), PathBuf::from("5")]
+    );
+    assert!(select_evictions(candidates(), 100, Some(100), None, now).is_empty());
+
+    // Only the sessions that are too old
+    assert_eq!(
+        paths(select_evictions(candidates(), 100, None, Some(3 * day), now)),
+        [PathBuf::from("10"), PathBuf::from("5")]
+    );
+
+    // Both budgets
+    assert_eq!(
+        paths(select_evictions(candidates(), 100, Some(95), Some(7 * day), now)),
+        [PathBuf::from("10")]
+    );
+    assert_eq!(
+        paths(select_evictions(candidates(), 100, Some(10), Some(7 * day), now)),
+        [PathBuf::from("10"), PathBuf::from("5"), PathBuf::from("2")]
+    );
+}
@@ -8,6 +8,7 @@
 mod fs;
 mod load;
 mod save;
+mod shared;
 mod work_product;
 
 pub use fs::finalize_session_directory;
@@ -115,6 +115,12 @@ pub fn save_work_product_index(
         }
     }
 
+    // With a size budget, also clean out the object files that no work-product
+    // refers to.
+    if sess.opts.debugging_opts.incremental_max_size.is_some() {
+        work_product::delete_unused_workproduct_files(sess, &new_work_products);
+    }
+
     // Check that we did not delete one of the current work-products:
     debug_assert!({
         new_work_products
@@ -0,0 +1,154 @@
+//! The shared incremental compilation cache, for `-Z incremental-shared-cache`.
+//!
+//! The incremental directory of a crate only benefits the compilations that use it, so a branch
+//! that was already compiled on CI or in another working copy is compiled from scratch again.
+//! The shared cache is a directory to which the compilations with
+//! `-Z incremental-shared-cache-publish` publish their finalized session directories, and from
+//! which the other compilations start when their own incremental directory has no session to
+//! start from. These only ever read from it.
+//!
+//! The cache is content-addressed: the files of the published sessions are stored once, named
+//! after the hash of their contents, in `objects/`. The files of the last session published for
+//! a crate are listed in a manifest, `{crate-name-and-disambiguator}/{configuration}`, where the
+//! configuration is a hash of the compiler version and of the tracked options, so that debug and
+//! release builds, for example, do not replace each other. Each file is written under a temporary
+//! name and then renamed, so readers never see partial files and no locking is needed.
+//!
+//! Nothing in the cache depends on where the sources are checked out: crate directories are
+//! named after the stable crate id, and both the tracked options and the source file names are
+//! hashed as remapped by `--remap-path-prefix`. Working copies that remap their root to the same
+//! virtual path, e.g. with `-Z remap-cwd-prefix=.`, thus share their sessions.
+
+use super::file_format;
+
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::stable_hasher::StableHasher;
+use rustc_fs_util::link_or_copy;
+use rustc_session::Session;
+
+use std::fs as std_fs;
+use std::hash::Hasher;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use rand::{thread_rng, RngCore};
+
+const OBJECTS_DIR: &str = "objects";
+
+/// Returns the path of the manifest of `crate_dir` for the current configuration.
+fn manifest_path(sess: &Session, shared_dir: &Path, crate_dir: &Path) -> PathBuf {
+    let mut hasher = StableHasher::new();
+    hasher.write(file_format::rustc_version(sess.is_nightly_build()).as_bytes());
+    hasher.write_u64(sess.opts.dep_tracking_hash(false));
+    let configuration: Fingerprint = hasher.finish();
+    shared_dir.join(crate_dir.file_name().unwrap()).join(configuration.to_hex())
+}
+
+/// Links or copies the files of the session last published for `crate_dir` to the shared cache
+/// into `session_dir`. Returns `false` if no session was published for the current
+/// configuration.
+pub fn copy_from_shared_cache(
+    sess: &Session,
+    shared_dir: &Path,
+    crate_dir: &Path,
+    session_dir: &Path,
+) -> io::Result<bool> {
+    let manifest = match std_fs::read_to_string(manifest_path(sess, shared_dir, crate_dir)) {
+        Ok(manifest) => manifest,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(err) => return Err(err),
+    };
+
+    let objects_dir = shared_dir.join(OBJECTS_DIR);
+    let mut copied = vec![];
+    for line in manifest.lines() {
+        let entry = line.split_once('\t').filter(|(_, file_name)| {
+            // The manifest must not make us write outside of the session directory.
+            Path::new(file_name).file_name().map_or(false, |name| name == *file_name)
+        });
+        let result = match entry {
+            Some((hash, file_name)) => {
+                debug!("copying from shared cache: {}", file_name);
+                let target_path = session_dir.join(file_name);
+                link_or_copy(objects_dir.join(hash), &target_path).map(|_| target_path)
+            }
+            None => Err(io::Error::new(io::ErrorKind::InvalidData, "malformed manifest")),
+        };
+        match result {
+            Ok(target_path) => copied.push(target_path),
+            Err(err) => {
+                // Leave the session directory empty, as if nothing was found.
+                for path in copied {
+                    let _ = std_fs::remove_file(path);
+                }
+                return Err(err);
+            }
+        }
+    }
+
+    if sess.opts.debugging_opts.incremental_info {
+        eprintln!(
+            "[incremental] shared cache: {} files copied from `{}`",
+            copied.len(),
+            shared_dir.display()
+        );
+    }
+
+    Ok(true)
+}
+
+/// Publishes the files of the finalized `session_dir` to the shared cache, replacing the session
+/// previously published for its crate.
+pub fn publish_to_shared_cache(
+    sess: &Session,
+    shared_dir: &Path,
+    session_dir: &Path,
+) -> io::Result<()> {
+    let objects_dir = shared_dir.join(OBJECTS_DIR);
+    std_fs::create_dir_all(&objects_dir)?;
+
+    let mut file_names = session_dir
+        .read_dir()?
+        .map(|entry| entry.map(|entry| entry.file_name()))
+        .collect::<io::Result<Vec<_>>>()?;
+    file_names.sort();
+
+    let mut manifest = String::new();
+    for file_name in file_names {
+        let Some(file_name) = file_name.to_str() else {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "non UTF-8 file name"));
+        };
+        let contents = std_fs::read(session_dir.join(file_name))?;
+        let mut hasher = StableHasher::new();
+        hasher.write(&contents);
+        let hash = hasher.finish::<Fingerprint>().to_hex();
+
+        // Objects are never modified, so an existing one already has these contents.
+        let object_path = objects_dir.join(&hash);
+        if !object_path.exists() {
+            write_atomically(&object_path, &contents)?;
+        }
+        manifest.push_str(&format!("{}\t{}\n", hash, file_name));
+    }
+
+    let manifest_path = manifest_path(sess, shared_dir, session_dir.parent().unwrap());
+    std_fs::create_dir_all(manifest_path.parent().unwrap())?;
+    write_atomically(&manifest_path, manifest.as_bytes())?;
+
+    if sess.opts.debugging_opts.incremental_info {
+        eprintln!("[incremental] shared cache: session published to `{}`", shared_dir.display());
+    }
+
+    Ok(())
+}
+
+/// Writes `contents` to a temporary file next to `path` and renames it to `path`, so that
+/// concurrent readers either see the previous file or the complete new one.
+fn write_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let temp_path = path.with_extension(format!("tmp-{:x}", thread_rng().next_u32()));
+    std_fs::write(&temp_path, contents)?;
+    std_fs::rename(&temp_path, path).map_err(|err| {
+        let _ = std_fs::remove_file(&temp_path);
+        err
+    })
+}
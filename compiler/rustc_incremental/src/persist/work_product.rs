@@ -3,6 +3,7 @@
 //! [work products]: WorkProduct
 
 use crate::persist::fs::*;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_fs_util::link_or_copy;
 use rustc_middle::dep_graph::{WorkProduct, WorkProductId};
 use rustc_session::Session;
@@ -59,3 +60,31 @@ pub fn delete_workproduct_files(sess: &Session, work_product: &WorkProduct) {
         }
     }
 }
+
+/// Removes the object files of the session directory that are not saved by any of the
+/// `work_products`, such as the ones copied from a previous session whose work product index
+/// could not be loaded.
+pub fn delete_unused_workproduct_files(
+    sess: &Session,
+    work_products: &FxHashMap<WorkProductId, WorkProduct>,
+) {
+    let saved_files: FxHashSet<&str> =
+        work_products.values().filter_map(|wp| wp.saved_file.as_deref()).collect();
+    let session_dir = sess.incr_comp_session_dir();
+    let Ok(entries) = session_dir.read_dir() else { return };
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let file_name = entry.file_name();
+        let Some(file_name) = file_name.to_str() else { continue };
+        if !file_name.ends_with(".o") || saved_files.contains(file_name) {
+            continue;
+        }
+        let path = entry.path();
+        if let Err(err) = std_fs::remove_file(&path) {
+            sess.warn(&format!(
+                "file-system error deleting unused file `{}`: {}",
+                path.display(),
+                err
+            ));
+        }
+    }
+}
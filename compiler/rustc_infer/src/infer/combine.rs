@@ -24,6 +24,7 @@
 
 use super::equate::Equate;
 use super::glb::Glb;
+use super::inference_trace::InferenceStepKind;
 use super::lub::Lub;
 use super::sub::Sub;
 use super::type_variable::TypeVariableValue;
@@ -64,9 +65,13 @@ pub enum RelationDir {
 }
 
 impl<'infcx, 'tcx> InferCtxt<'infcx, 'tcx> {
+    /// Relates `a` and `b` once the type variables have been handled by `relation`. `span` is the
+    /// span of the cause of the relation, at which the unifications of integer and float
+    /// variables are recorded for `-Z explain-inference`.
     pub fn super_combine_tys<R>(
         &self,
         relation: &mut R,
+        span: Span,
         a: Ty<'tcx>,
         b: Ty<'tcx>,
     ) -> RelateResult<'tcx, Ty<'tcx>>
@@ -83,19 +88,23 @@ pub fn super_combine_tys<R>(
                     .int_unification_table()
                     .unify_var_var(a_id, b_id)
                     .map_err(|e| int_unification_error(a_is_expected, e))?;
+                self.record_inference_step(
+                    span,
+                    InferenceStepKind::Equate(ty::IntVar(a_id), ty::IntVar(b_id)),
+                );
                 Ok(a)
             }
             (&ty::Infer(ty::IntVar(v_id)), &ty::Int(v)) => {
-                self.unify_integral_variable(a_is_expected, v_id, IntType(v))
+                self.unify_integral_variable(span, a_is_expected, v_id, IntType(v))
             }
             (&ty::Int(v), &ty::Infer(ty::IntVar(v_id))) => {
-                self.unify_integral_variable(!a_is_expected, v_id, IntType(v))
+                self.unify_integral_variable(span, !a_is_expected, v_id, IntType(v))
             }
             (&ty::Infer(ty::IntVar(v_id)), &ty::Uint(v)) => {
-                self.unify_integral_variable(a_is_expected, v_id, UintType(v))
+                self.unify_integral_variable(span, a_is_expected, v_id, UintType(v))
             }
             (&ty::Uint(v), &ty::Infer(ty::IntVar(v_id))) => {
-                self.unify_integral_variable(!a_is_expected, v_id, UintType(v))
+                self.unify_integral_variable(span, !a_is_expected, v_id, UintType(v))
             }
 
             // Relate floating-point variables to other types
@@ -105,13 +114,17 @@ pub fn super_combine_tys<R>(
                     .float_unification_table()
                     .unify_var_var(a_id, b_id)
                     .map_err(|e| float_unification_error(relation.a_is_expected(), e))?;
+                self.record_inference_step(
+                    span,
+                    InferenceStepKind::Equate(ty::FloatVar(a_id), ty::FloatVar(b_id)),
+                );
                 Ok(a)
             }
             (&ty::Infer(ty::FloatVar(v_id)), &ty::Float(v)) => {
-                self.unify_float_variable(a_is_expected, v_id, v)
+                self.unify_float_variable(span, a_is_expected, v_id, v)
             }
             (&ty::Float(v), &ty::Infer(ty::FloatVar(v_id))) => {
-                self.unify_float_variable(!a_is_expected, v_id, v)
+                self.unify_float_variable(span, !a_is_expected, v_id, v)
             }
 
             // All other cases of inference are errors
@@ -265,6 +278,7 @@ fn unify_const_variable(
 
     fn unify_integral_variable(
         &self,
+        span: Span,
         vid_is_expected: bool,
         vid: ty::IntVid,
         val: ty::IntVarValue,
@@ -274,14 +288,17 @@ fn unify_integral_variable(
             .int_unification_table()
             .unify_var_value(vid, Some(val))
             .map_err(|e| int_unification_error(vid_is_expected, e))?;
-        match val {
-            IntType(v) => Ok(self.tcx.mk_mach_int(v)),
-            UintType(v) => Ok(self.tcx.mk_mach_uint(v)),
-        }
+        let ty = match val {
+            IntType(v) => self.tcx.mk_mach_int(v),
+            UintType(v) => self.tcx.mk_mach_uint(v),
+        };
+        self.record_inference_step(span, InferenceStepKind::Instantiate(ty::IntVar(vid), ty));
+        Ok(ty)
     }
 
     fn unify_float_variable(
         &self,
+        span: Span,
         vid_is_expected: bool,
         vid: ty::FloatVid,
         val: ty::FloatTy,
@@ -291,7 +308,9 @@ fn unify_float_variable(
             .float_unification_table()
             .unify_var_value(vid, Some(ty::FloatVarValue(val)))
             .map_err(|e| float_unification_error(vid_is_expected, e))?;
-        Ok(self.tcx.mk_mach_float(val))
+        let ty = self.tcx.mk_mach_float(val);
+        self.record_inference_step(span, InferenceStepKind::Instantiate(ty::FloatVar(vid), ty));
+        Ok(ty)
     }
 }
 
@@ -352,6 +371,10 @@ pub fn instantiate(
         let Generalization { ty: b_ty, needs_wf } = self.generalize(a_ty, b_vid, dir)?;
         debug!(?b_ty);
         self.infcx.inner.borrow_mut().type_variables().instantiate(b_vid, b_ty);
+        self.infcx.record_inference_step(
+            self.trace.cause.span,
+            InferenceStepKind::Instantiate(ty::TyVar(b_vid), b_ty),
+        );
 
         if needs_wf {
             self.obligations.push(Obligation::new(
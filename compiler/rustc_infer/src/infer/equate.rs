@@ -1,4 +1,5 @@
 use super::combine::{CombineFields, ConstEquateRelation, RelationDir};
+use super::inference_trace::InferenceStepKind;
 use super::Subtype;
 
 use rustc_middle::ty::relate::{self, Relate, RelateResult, TypeRelation};
@@ -82,6 +83,10 @@ fn tys(&mut self, a: Ty<'tcx>, b: Ty<'tcx>) -> RelateResult<'tcx, Ty<'tcx>> {
         match (a.kind(), b.kind()) {
             (&ty::Infer(TyVar(a_id)), &ty::Infer(TyVar(b_id))) => {
                 infcx.inner.borrow_mut().type_variables().equate(a_id, b_id);
+                infcx.record_inference_step(
+                    self.fields.trace.cause.span,
+                    InferenceStepKind::Equate(TyVar(a_id), TyVar(b_id)),
+                );
             }
 
             (&ty::Infer(TyVar(a_id)), _) => {
@@ -93,7 +98,8 @@ fn tys(&mut self, a: Ty<'tcx>, b: Ty<'tcx>) -> RelateResult<'tcx, Ty<'tcx>> {
             }
 
             (&ty::Opaque(a_def_id, _), &ty::Opaque(b_def_id, _)) if a_def_id == b_def_id => {
-                self.fields.infcx.super_combine_tys(self, a, b)?;
+                let span = self.fields.trace.cause.span;
+                self.fields.infcx.super_combine_tys(self, span, a, b)?;
             }
             (&ty::Opaque(did, ..), _) | (_, &ty::Opaque(did, ..))
                 if self.fields.define_opaque_types && did.is_local() =>
@@ -112,7 +118,8 @@ fn tys(&mut self, a: Ty<'tcx>, b: Ty<'tcx>) -> RelateResult<'tcx, Ty<'tcx>> {
             }
 
             _ => {
-                self.fields.infcx.super_combine_tys(self, a, b)?;
+                let span = self.fields.trace.cause.span;
+                self.fields.infcx.super_combine_tys(self, span, a, b)?;
             }
         }
 
@@ -0,0 +1,187 @@
+//! The trace of type inference explained by `-Z explain-inference`.
+//!
+//! When the option is set, the inference context records the steps that constrain its type
+//! variables: the unifications, the registered obligations and the fallback of unresolved
+//! variables. The trace is part of the undo log, so the steps of a rolled back snapshot, e.g. of
+//! a probe, are dropped with it. At the end of the type checking of a body, the trace of each
+//! type variable created at the given position is reported, i.e. the steps that touched the
+//! variable or a variable it was unified with, transitively.
+
+use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::undo_log::UndoLogs;
+use rustc_middle::ty::fold::{TypeFoldable, TypeVisitor};
+use rustc_middle::ty::{self, Ty};
+use rustc_session::config::SourcePosition;
+use rustc_session::Session;
+use rustc_span::{BytePos, FileName, Pos, Span};
+
+use std::ops::ControlFlow;
+
+use super::undo_log::UndoLog;
+use super::InferCtxt;
+
+/// A step of type inference, recorded with `-Z explain-inference`.
+#[derive(Clone, Debug)]
+pub struct InferenceStep<'tcx> {
+    /// The span of the expression or obligation that caused the step, if any.
+    pub span: Span,
+    pub kind: InferenceStepKind<'tcx>,
+}
+
+#[derive(Clone, Debug)]
+pub enum InferenceStepKind<'tcx> {
+    /// Two type, integer or float variables were unified.
+    Equate(ty::InferTy, ty::InferTy),
+    /// A variable was instantiated with a type, which may contain fresh type variables.
+    Instantiate(ty::InferTy, Ty<'tcx>),
+    /// An obligation was registered.
+    Obligation(ty::Predicate<'tcx>),
+    /// An unresolved variable fell back to a type.
+    Fallback(Ty<'tcx>, Ty<'tcx>),
+}
+
+impl<'tcx> InferenceStep<'tcx> {
+    fn describe(&self) -> String {
+        match self.kind {
+            InferenceStepKind::Equate(a, b) => format!("unified `{:?}` with `{:?}`", a, b),
+            InferenceStepKind::Instantiate(var, ty) => {
+                format!("instantiated `{:?}` with `{:?}`", var, ty)
+            }
+            InferenceStepKind::Obligation(predicate) => {
+                format!("registered the obligation `{:?}`", predicate.kind().skip_binder())
+            }
+            InferenceStepKind::Fallback(ty, fallback) => {
+                format!("`{:?}` fell back to `{:?}`", ty, fallback)
+            }
+        }
+    }
+
+    /// The inference variables mentioned by the step.
+    fn vars(&self) -> Vec<ty::InferTy> {
+        let mut collector = InferVarCollector(vec![]);
+        match self.kind {
+            InferenceStepKind::Equate(a, b) => collector.0.extend([a, b]),
+            InferenceStepKind::Instantiate(var, ty) => {
+                collector.0.push(var);
+                ty.visit_with(&mut collector);
+            }
+            InferenceStepKind::Obligation(predicate) => {
+                predicate.visit_with(&mut collector);
+            }
+            InferenceStepKind::Fallback(ty, _) => {
+                ty.visit_with(&mut collector);
+            }
+        }
+        collector.0
+    }
+
+    /// Whether the step relates its variables together, rather than only constraining them.
+    fn is_unification(&self) -> bool {
+        matches!(self.kind, InferenceStepKind::Equate(..) | InferenceStepKind::Instantiate(..))
+    }
+}
+
+struct InferVarCollector(Vec<ty::InferTy>);
+
+impl<'tcx> TypeVisitor<'tcx> for InferVarCollector {
+    fn visit_ty(&mut self, ty: Ty<'tcx>) -> ControlFlow<Self::BreakTy> {
+        if let ty::Infer(var) = *ty.kind() {
+            self.0.push(var);
+        }
+        ty.super_visit_with(self)
+    }
+}
+
+/// Returns the position of the source map that `position` designates, if any.
+pub fn source_position_to_pos(sess: &Session, position: &SourcePosition) -> Option<BytePos> {
+    let files = sess.source_map().files();
+    let file = files.iter().find(|file| match &file.name {
+        FileName::Real(name) => name.local_path_if_available().ends_with(&position.file),
+        _ => false,
+    })?;
+    let line = file.get_line(position.line - 1)?;
+    let offset = line.char_indices().map(|(i, _)| i).chain([line.len()]).nth(position.col - 1)?;
+    Some(file.lines[position.line - 1] + BytePos::from_usize(offset))
+}
+
+impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
+    /// Records a step of type inference, if `-Z explain-inference` is set.
+    pub fn record_inference_step(&self, span: Span, kind: InferenceStepKind<'tcx>) {
+        if self.tcx.sess.opts.debugging_opts.explain_inference.is_some() {
+            let mut inner = self.inner.borrow_mut();
+            inner.inference_trace.push(InferenceStep { span, kind });
+            inner.undo_log.push(UndoLog::PushInferenceStep);
+        }
+    }
+
+    /// Reports the trace of the type variables created at the position given to
+    /// `-Z explain-inference`, as notes.
+    pub fn explain_inference(&self) {
+        let sess = self.tcx.sess;
+        let Some(position) = &sess.opts.debugging_opts.explain_inference else { return };
+        let Some(pos) = source_position_to_pos(sess, position) else { return };
+        let point = Span::with_root_ctxt(pos, pos);
+
+        let num_vars = self.inner.borrow_mut().type_variables().num_vars();
+        let mut roots = FxHashSet::default();
+        let vids: Vec<_> = (0..num_vars)
+            .map(ty::TyVid::from_usize)
+            .filter(|&vid| {
+                let origin_span = self.inner.borrow_mut().type_variables().var_origin(vid).span;
+                origin_span.contains(point) && roots.insert(self.root_var(vid))
+            })
+            .collect();
+        if vids.is_empty() {
+            return;
+        }
+
+        let trace = self.inner.borrow().inference_trace.clone();
+        let steps: Vec<_> =
+            trace.iter().map(|step| (step, self.root_infer_vars(step.vars()))).collect();
+        for vid in vids {
+            // The variables unified with `vid`, through the types they were instantiated with.
+            let mut related = FxHashSet::from_iter([ty::TyVar(self.root_var(vid))]);
+            let mut changed = true;
+            while changed {
+                changed = false;
+                for (step, vars) in &steps {
+                    if step.is_unification() && vars.iter().any(|var| related.contains(var)) {
+                        for &var in vars {
+                            changed |= related.insert(var);
+                        }
+                    }
+                }
+            }
+
+            let mut diag = sess.struct_note_without_error(&format!(
+                "inference trace of `{:?}`, resolved to `{:?}`",
+                vid,
+                self.resolve_vars_if_possible(self.tcx.mk_ty_var(vid))
+            ));
+            diag.set_span(self.inner.borrow_mut().type_variables().var_origin(vid).span);
+            let touching =
+                steps.iter().filter(|(_, vars)| vars.iter().any(|v| related.contains(v)));
+            for (i, (step, _)) in touching.enumerate() {
+                let msg = format!("{}: {}", i + 1, step.describe());
+                if step.span.is_dummy() {
+                    diag.note(&msg);
+                } else {
+                    diag.span_note(step.span, &msg);
+                }
+            }
+            diag.emit();
+        }
+    }
+
+    fn root_infer_vars(&self, vars: Vec<ty::InferTy>) -> Vec<ty::InferTy> {
+        let mut inner = self.inner.borrow_mut();
+        vars.into_iter()
+            .map(|var| match var {
+                ty::TyVar(vid) => ty::TyVar(inner.type_variables().root_var(vid)),
+                ty::IntVar(vid) => ty::IntVar(inner.int_unification_table().find(vid)),
+                ty::FloatVar(vid) => ty::FloatVar(inner.float_unification_table().find(vid)),
+                fresh => fresh,
+            })
+            .collect()
+    }
+}
@@ -106,7 +106,8 @@ pub fn super_lattice_tys<'a, 'tcx: 'a, L>(
         }
 
         (&ty::Opaque(a_def_id, _), &ty::Opaque(b_def_id, _)) if a_def_id == b_def_id => {
-            infcx.super_combine_tys(this, a, b)
+            let span = this.cause().span;
+            infcx.super_combine_tys(this, span, a, b)
         }
         (&ty::Opaque(did, ..), _) | (_, &ty::Opaque(did, ..))
             if this.define_opaque_types() && did.is_local() =>
@@ -119,6 +120,9 @@ pub fn super_lattice_tys<'a, 'tcx: 'a, L>(
             Ok(a)
         }
 
-        _ => infcx.super_combine_tys(this, a, b),
+        _ => {
+            let span = this.cause().span;
+            infcx.super_combine_tys(this, span, a, b)
+        }
     }
 }
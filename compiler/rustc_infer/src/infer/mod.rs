@@ -39,6 +39,7 @@
 
 use self::combine::CombineFields;
 use self::free_regions::RegionRelations;
+use self::inference_trace::InferenceStep;
 use self::lexical_region_resolve::LexicalRegionResolutions;
 use self::outlives::env::OutlivesEnvironment;
 use self::region_constraints::{GenericKind, RegionConstraintData, VarInfos, VerifyBound};
@@ -57,6 +58,7 @@
 mod fudge;
 mod glb;
 mod higher_ranked;
+pub mod inference_trace;
 pub mod lattice;
 mod lexical_region_resolve;
 mod lub;
@@ -187,6 +189,9 @@ pub struct InferCtxtInner<'tcx> {
     /// that all type inference variables have been bound and so forth.
     region_obligations: Vec<(hir::HirId, RegionObligation<'tcx>)>,
 
+    /// The steps of inference recorded for `-Z explain-inference`, see [`inference_trace`].
+    inference_trace: Vec<InferenceStep<'tcx>>,
+
     undo_log: InferCtxtUndoLogs<'tcx>,
 
     /// Caches for opaque type inference.
@@ -204,6 +209,7 @@ fn new() -> InferCtxtInner<'tcx> {
             float_unification_storage: ut::UnificationTableStorage::new(),
             region_constraint_storage: Some(RegionConstraintStorage::new()),
             region_obligations: vec![],
+            inference_trace: vec![],
             opaque_type_storage: Default::default(),
         }
     }
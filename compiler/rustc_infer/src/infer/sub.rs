@@ -123,7 +123,8 @@ fn tys(&mut self, a: Ty<'tcx>, b: Ty<'tcx>) -> RelateResult<'tcx, Ty<'tcx>> {
             }
 
             (&ty::Opaque(a_def_id, _), &ty::Opaque(b_def_id, _)) if a_def_id == b_def_id => {
-                self.fields.infcx.super_combine_tys(self, a, b)?;
+                let span = self.fields.trace.cause.span;
+                self.fields.infcx.super_combine_tys(self, span, a, b)?;
                 Ok(a)
             }
             (&ty::Opaque(did, ..), _) | (_, &ty::Opaque(did, ..))
@@ -155,7 +156,8 @@ fn tys(&mut self, a: Ty<'tcx>, b: Ty<'tcx>) -> RelateResult<'tcx, Ty<'tcx>> {
             }
 
             _ => {
-                self.fields.infcx.super_combine_tys(self, a, b)?;
+                let span = self.fields.trace.cause.span;
+                self.fields.infcx.super_combine_tys(self, span, a, b)?;
                 Ok(a)
             }
         }
@@ -28,6 +28,7 @@ pub(crate) enum UndoLog<'tcx> {
     RegionUnificationTable(sv::UndoLog<ut::Delegate<RegionVidKey<'tcx>>>),
     ProjectionCache(traits::UndoLog<'tcx>),
     PushRegionObligation,
+    PushInferenceStep,
 }
 
 macro_rules! impl_from {
@@ -81,6 +82,9 @@ fn reverse(&mut self, undo: UndoLog<'tcx>) {
             UndoLog::PushRegionObligation => {
                 self.region_obligations.pop();
             }
+            UndoLog::PushInferenceStep => {
+                self.inference_trace.pop();
+            }
         }
     }
 }
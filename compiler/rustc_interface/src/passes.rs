@@ -14,6 +14,7 @@
 use rustc_expand::base::{ExtCtxt, LintStoreExpand, ResolverExpand};
 use rustc_hir::def_id::{StableCrateId, LOCAL_CRATE};
 use rustc_hir::Crate;
+use rustc_lint::plugin::LintPluginRegistry;
 use rustc_lint::{EarlyCheckNode, LintStore};
 use rustc_metadata::creader::CStore;
 use rustc_metadata::{encode_metadata, EncodedMetadata};
@@ -229,6 +230,18 @@ pub fn register_plugins<'a>(
         }
     });
 
+    let lint_plugins = sess.time("lint_plugin_loading", || plugin::load::load_lint_plugins(sess));
+    sess.time("lint_plugin_registration", || {
+        for (path, register) in &lint_plugins {
+            register(&mut LintPluginRegistry::new(sess, path, &mut lint_store));
+        }
+    });
+    // A plugin whose names clash with the registered ones would run its passes twice, or without
+    // the lints they emit.
+    if let Some(reported) = sess.has_errors() {
+        return Err(reported);
+    }
+
     Ok((krate, lint_store))
 }
 
@@ -629,6 +642,15 @@ fn write_out_deps(
         });
         files.extend(extra_tracked_files);
 
+        // The lint configuration sets the lint levels and the rules of `-Z lint-rules` add lints,
+        // so changing them must rebuild the crate.
+        if let Some(config) = &sess.lint_config {
+            files.push(escape_dep_filename(&config.path.display().to_string()));
+        }
+        for path in &sess.opts.debugging_opts.lint_rules {
+            files.push(escape_dep_filename(&path.display().to_string()));
+        }
+
         if sess.binary_dep_depinfo() {
             if let Some(ref backend) = sess.opts.debugging_opts.codegen_backend {
                 if backend.contains('.') {
@@ -992,6 +1014,11 @@ fn analysis(tcx: TyCtxt<'_>, (): ()) -> Result<()> {
                         tcx.hir()
                             .par_for_each_module(|module| tcx.ensure().check_mod_deathness(module));
                     },
+                    {
+                        sess.time("unused_pub_items_checking", || {
+                            rustc_passes::unused_pub::check_unused_pub_items(tcx)
+                        });
+                    },
                     {
                         sess.time("lint_checking", || {
                             rustc_lint::check_crate(tcx, || {
@@ -1105,6 +1132,8 @@ pub fn start_codegen<'tcx>(
 
     info!("Post-codegen\n{:?}", tcx.debug_stats());
 
+    report_time_budget(tcx);
+
     if tcx.sess.opts.output_types.contains_key(&OutputType::Mir) {
         if let Err(e) = rustc_mir_transform::dump_mir::emit_mir(tcx, outputs) {
             tcx.sess.err(&format!("could not emit MIR: {}", e));
@@ -1115,6 +1144,32 @@ pub fn start_codegen<'tcx>(
     codegen
 }
 
+/// Warns about the items that took more than the `-Z time-budget` to compile.
+fn report_time_budget(tcx: TyCtxt<'_>) {
+    let Some(budget) = &tcx.sess.time_budget else { return };
+    for (def_id, cost) in budget.over_budget() {
+        let mut diag = tcx.sess.struct_span_warn(
+            tcx.def_span(def_id),
+            &format!(
+                "`{}` took {}ms to compile, more than the budget of {}ms",
+                tcx.def_path_str(def_id),
+                cost.total.as_millis(),
+                budget.budget().as_millis()
+            ),
+        );
+        let mut activities: Vec<_> = cost.by_activity.into_iter().collect();
+        activities.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        for (activity, time) in activities {
+            if time.is_zero() {
+                break;
+            }
+            // With a sub-millisecond precision, so that small budgets still get a breakdown.
+            diag.note(&format!("`{}`: {:.1?}", activity, time));
+        }
+        diag.emit();
+    }
+}
+
 fn get_recursion_limit(krate_attrs: &[ast::Attribute], sess: &Session) -> Limit {
     if let Some(attr) = krate_attrs
         .iter()
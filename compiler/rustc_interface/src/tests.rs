@@ -1,18 +1,22 @@
 use crate::interface::parse_cfgspecs;
 
 use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::profiling::ProfileFormat;
 use rustc_errors::{emitter::HumanReadableErrorType, registry, ColorConfig};
 use rustc_session::config::InstrumentCoverage;
+use rustc_session::config::MirDumpFormat;
 use rustc_session::config::Strip;
 use rustc_session::config::{build_configuration, build_session_options, to_crate_config};
 use rustc_session::config::{
     rustc_optgroups, ErrorOutputType, ExternLocation, LocationDetail, Options, Passes,
+    RemapPathPrefix,
 };
 use rustc_session::config::{
-    BranchProtection, Externs, OomStrategy, OutputType, OutputTypes, PAuthKey, PacRet,
-    SymbolManglingVersion, WasiExecModel,
+    BorrowckExplain, BranchProtection, Externs, OomStrategy, OutputType, OutputTypes, PAuthKey,
+    PacRet, SymbolManglingVersion, WasiExecModel,
 };
 use rustc_session::config::{CFGuard, ExternEntry, LinkerPluginLto, LtoCli, SwitchWithOptPath};
+use rustc_session::config::{FaultKind, InjectFault, MonoItemsReportFormat, SourcePosition};
 use rustc_session::lint::Level;
 use rustc_session::search_paths::SearchPath;
 use rustc_session::utils::{CanonicalizedPath, NativeLib, NativeLibKind};
@@ -29,6 +33,7 @@
 use std::iter::FromIterator;
 use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 type CfgSpecs = FxHashSet<(String, Option<String>)>;
 
@@ -327,6 +332,21 @@ fn test_search_paths_tracking_hash_different_order() {
     assert_same_hash(&v1, &v4);
 }
 
+#[test]
+fn test_remap_path_prefix_tracking_hash_different_local_paths() {
+    let mut v1 = Options::default();
+    let mut v2 = Options::default();
+    let mut v3 = Options::default();
+
+    v1.remap_path_prefix = RemapPathPrefix::new(vec![("/home/alice/rust".into(), "src".into())]);
+    v2.remap_path_prefix = RemapPathPrefix::new(vec![("/ci/build/rust".into(), "src".into())]);
+    v3.remap_path_prefix = RemapPathPrefix::new(vec![("/home/alice/rust".into(), "rust".into())]);
+
+    // Only the virtual prefixes matter, so that the incremental cache can be relocated.
+    assert_same_hash(&v1, &v2);
+    assert_non_crate_hash_different(&v1, &v3);
+}
+
 #[test]
 fn test_native_libs_tracking_hash_different_values() {
     let mut v1 = Options::default();
@@ -621,7 +641,10 @@ macro_rules! tracked {
 
     // Make sure that changing a [TRACKED_NO_CRATE_HASH] option leaves the crate hash unchanged but changes the incremental hash.
     // This list is in alphabetical order.
-    tracked!(remap_path_prefix, vec![("/home/bors/rust".into(), "src".into())]);
+    tracked!(
+        remap_path_prefix,
+        RemapPathPrefix::new(vec![("/home/bors/rust".into(), "src".into())])
+    );
     tracked!(
         real_rust_source_base_dir,
         Some("/home/bors/rust/.rustup/toolchains/nightly/lib/rustlib/src/rust".into())
@@ -643,6 +666,7 @@ macro_rules! untracked {
 
     // Make sure that changing an [UNTRACKED] option leaves the hash unchanged.
     // This list is in alphabetical order.
+    untracked!(apply_suggestions, Some(PathBuf::from("abc")));
     untracked!(assert_incr_state, Some(String::from("loaded")));
     untracked!(ast_json, true);
     untracked!(ast_json_noexpand, true);
@@ -656,21 +680,30 @@ macro_rules! untracked {
     untracked!(dump_mir_dataflow, true);
     untracked!(dump_mir_dir, String::from("abc"));
     untracked!(dump_mir_exclude_pass_number, true);
+    untracked!(dump_mir_format, MirDumpFormat::Json);
     untracked!(dump_mir_graphviz, true);
+    untracked!(emit_size_map, true);
     untracked!(emit_stack_sizes, true);
     untracked!(future_incompat_test, true);
     untracked!(hir_stats, true);
     untracked!(identify_regions, true);
+    untracked!(incremental_explain, true);
     untracked!(incremental_ignore_spans, true);
     untracked!(incremental_info, true);
+    untracked!(incremental_max_age, Some(Duration::from_secs(30 * 24 * 60 * 60)));
+    untracked!(incremental_max_size, Some(1 << 30));
+    untracked!(incremental_shared_cache, Some(PathBuf::from("abc")));
+    untracked!(incremental_shared_cache_publish, true);
     untracked!(incremental_verify_ich, true);
     untracked!(input_stats, true);
     untracked!(keep_hygiene_data, true);
     untracked!(link_native_libraries, false);
+    untracked!(lint_config_discovery, true);
     untracked!(llvm_time_trace, true);
     untracked!(ls, true);
     untracked!(macro_backtrace, true);
     untracked!(meta_stats, true);
+    untracked!(mono_items_report, Some(MonoItemsReportFormat::Json));
     untracked!(nll_facts, true);
     untracked!(no_analysis, true);
     untracked!(no_interleave_lints, true);
@@ -689,12 +722,14 @@ macro_rules! untracked {
     untracked!(save_analysis, true);
     untracked!(self_profile, SwitchWithOptPath::Enabled(None));
     untracked!(self_profile_events, Some(vec![String::new()]));
+    untracked!(self_profile_format, Some(ProfileFormat::Chrome));
     untracked!(span_debug, true);
     untracked!(span_free_formats, true);
     untracked!(temps_dir, Some(String::from("abc")));
     untracked!(terminal_width, Some(80));
     untracked!(threads, 99);
     untracked!(time, true);
+    untracked!(time_budget, Some(500));
     untracked!(time_llvm_passes, true);
     untracked!(time_passes, true);
     untracked!(trace_macros, true);
@@ -721,6 +756,7 @@ macro_rules! tracked {
     tracked!(asm_comments, true);
     tracked!(assume_incomplete_release, true);
     tracked!(binary_dep_depinfo, true);
+    tracked!(borrowck_explain, Some(BorrowckExplain::Dot));
     tracked!(
         branch_protection,
         Some(BranchProtection {
@@ -736,11 +772,19 @@ macro_rules! tracked {
     tracked!(dep_info_omit_d_target, true);
     tracked!(drop_tracking, true);
     tracked!(dual_proc_macros, true);
+    tracked!(
+        explain_inference,
+        Some(SourcePosition { file: "main.rs".to_string(), line: 2, col: 13 })
+    );
     tracked!(fewer_names, Some(true));
     tracked!(force_unstable_if_unmarked, true);
     tracked!(fuel, Some(("abc".to_string(), 99)));
     tracked!(function_sections, Some(false));
     tracked!(human_readable_cgu_names, true);
+    tracked!(
+        inject_fault,
+        Some(InjectFault { target: String::from("typeck"), n: 1, kind: FaultKind::Error })
+    );
     tracked!(inline_in_all_cgus, Some(true));
     tracked!(inline_mir, Some(true));
     tracked!(inline_mir_hint_threshold, Some(123));
@@ -748,6 +792,8 @@ macro_rules! tracked {
     tracked!(instrument_coverage, Some(InstrumentCoverage::All));
     tracked!(instrument_mcount, true);
     tracked!(link_only, true);
+    tracked!(lint_plugin, vec![PathBuf::from("liblints.so")]);
+    tracked!(lint_rules, vec![PathBuf::from("rules.txt")]);
     tracked!(llvm_plugins, vec![String::from("plugin_name")]);
     tracked!(location_detail, LocationDetail { file: true, line: false, column: false });
     tracked!(merge_functions, Some(MergeFunctions::Disabled));
@@ -792,6 +838,7 @@ macro_rules! tracked {
     tracked!(thinlto, Some(true));
     tracked!(thir_unsafeck, true);
     tracked!(tls_model, Some(TlsModel::GeneralDynamic));
+    tracked!(trait_solver_trace, true);
     tracked!(trap_unreachable, Some(false));
     tracked!(treat_err_as_bug, NonZeroUsize::new(1));
     tracked!(tune_cpu, Some(String::from("abc")));
@@ -233,6 +233,11 @@ pub fn register_lints(&mut self, lints: &[&'static Lint]) {
         }
     }
 
+    /// Returns whether `name` is the name of a lint or of a lint group, whatever its status.
+    crate fn is_lint_or_group_name(&self, name: &str) -> bool {
+        self.by_name.contains_key(name) || self.lint_groups.contains_key(name)
+    }
+
     pub fn register_group_alias(&mut self, lint_name: &'static str, alias: &'static str) {
         self.lint_groups.insert(
             alias,
@@ -89,11 +89,20 @@ fn visit_param(&mut self, param: &'a ast::Param) {
     }
 
     fn visit_item(&mut self, it: &'a ast::Item) {
+        let config_push = match &it.kind {
+            ast::ItemKind::Mod(_, ast::ModKind::Loaded(_, ast::Inline::No, spans)) => {
+                Some(self.context.builder.push_lint_config(spans.inner_span, false))
+            }
+            _ => None,
+        };
         self.with_lint_attrs(it.id, &it.attrs, |cx| {
             run_early_pass!(cx, check_item, it);
             ast_visit::walk_item(cx, it);
             run_early_pass!(cx, check_item_post, it);
-        })
+        });
+        if let Some(config_push) = config_push {
+            self.context.builder.pop(config_push);
+        }
     }
 
     fn visit_foreign_item(&mut self, it: &'a ast::ForeignItem) {
@@ -341,6 +350,8 @@ pub trait EarlyCheckNode<'a>: Copy {
     fn attrs<'b>(self) -> &'b [ast::Attribute]
     where
         'a: 'b;
+    /// The span of the contents of the crate root, to which the lint configuration file applies.
+    fn crate_root_span(self) -> Option<Span>;
     fn check<'b>(self, cx: &mut EarlyContextAndPass<'b, impl EarlyLintPass>)
     where
         'a: 'b;
@@ -356,6 +367,9 @@ fn attrs<'b>(self) -> &'b [ast::Attribute]
     {
         &self.attrs
     }
+    fn crate_root_span(self) -> Option<Span> {
+        Some(self.spans.inner_span)
+    }
     fn check<'b>(self, cx: &mut EarlyContextAndPass<'b, impl EarlyLintPass>)
     where
         'a: 'b,
@@ -376,6 +390,9 @@ fn attrs<'b>(self) -> &'b [ast::Attribute]
     {
         self.1
     }
+    fn crate_root_span(self) -> Option<Span> {
+        None
+    }
     fn check<'b>(self, cx: &mut EarlyContextAndPass<'b, impl EarlyLintPass>)
     where
         'a: 'b,
@@ -405,7 +422,12 @@ fn early_lint_node<'a>(
         pass,
     };
 
+    let config_push =
+        check_node.crate_root_span().map(|span| cx.context.builder.push_lint_config(span, true));
     cx.with_lint_attrs(check_node.id(), check_node.attrs(), |cx| check_node.check(cx));
+    if let Some(config_push) = config_push {
+        cx.context.builder.pop(config_push);
+    }
     cx.context.buffered
 }
 
@@ -20,7 +20,8 @@
 use rustc_session::parse::{add_feature_diagnostics, feature_err};
 use rustc_session::Session;
 use rustc_span::symbol::{sym, Symbol};
-use rustc_span::{Span, DUMMY_SP};
+use rustc_span::{FileName, Span, DUMMY_SP};
+use std::path::Path;
 use tracing::debug;
 
 fn lint_levels(tcx: TyCtxt<'_>, (): ()) -> LintLevelMap {
@@ -32,12 +33,15 @@ fn lint_levels(tcx: TyCtxt<'_>, (): ()) -> LintLevelMap {
 
     builder.levels.id_to_set.reserve(krate.owners.len() + 1);
 
+    let config_push =
+        builder.levels.push_lint_config(tcx.hir().root_module().spans.inner_span, true);
     let push =
         builder.levels.push(tcx.hir().attrs(hir::CRATE_HIR_ID), true, Some(hir::CRATE_HIR_ID));
 
     builder.levels.register_id(hir::CRATE_HIR_ID);
     tcx.hir().walk_toplevel_module(&mut builder);
     builder.levels.pop(push);
+    builder.levels.pop(config_push);
 
     builder.levels.update_unstable_expectation_ids();
     builder.levels.build_map()
@@ -129,6 +133,66 @@ fn process_command_line(&mut self, sess: &Session, store: &LintStore) {
         }
     }
 
+    /// Pushes the lint levels that the lint configuration file sets for the source file of `span`:
+    /// those of its `[lints]` table if `is_crate_node`, then those of the `[paths]` tables
+    /// matching the file. This is done before `push` for the crate root and out-of-line modules,
+    /// so that their attributes override the configuration. Don't forget to call `pop`!
+    pub(crate) fn push_lint_config(&mut self, span: Span, is_crate_node: bool) -> BuilderPush {
+        let prev = self.cur;
+        let sess = self.sess;
+        let Some(config) = &sess.lint_config else {
+            return BuilderPush { prev, changed: false };
+        };
+        self.cur = self.sets.list.push(LintSet { specs: FxHashMap::default(), parent: prev });
+
+        let file = match sess.source_map().span_to_filename(span) {
+            FileName::Real(name) => name.local_path().map(Path::to_path_buf),
+            _ => None,
+        };
+        let crate_lints = if is_crate_node { &config.lints[..] } else { &[] };
+        let path_lints = file.iter().flat_map(|file| config.path_lints(file));
+        let config_path = Symbol::intern(&config.path.display().to_string());
+        for entry in crate_lints.iter().chain(path_lints) {
+            let (tool_name, lint_name) = match entry.name.split_once("::") {
+                Some((tool_name, lint_name)) => (Some(Symbol::intern(tool_name)), lint_name),
+                None => (None, &entry.name[..]),
+            };
+            let ids = match self.store.check_lint_name(lint_name, tool_name, self.registered_tools)
+            {
+                CheckLintNameResult::Ok(ids)
+                | CheckLintNameResult::Tool(Ok(ids) | Err((Some(ids), _))) => ids,
+                // The tool is not running, see the same case in `push`.
+                CheckLintNameResult::Tool(Err((None, _))) => continue,
+                CheckLintNameResult::Warning(msg, _) => {
+                    if self.warn_about_weird_lints {
+                        sess.warn(&format!("{} (in `{}`)", msg, config_path));
+                    }
+                    continue;
+                }
+                CheckLintNameResult::NoLint(_) | CheckLintNameResult::NoTool => {
+                    if self.warn_about_weird_lints {
+                        sess.warn(&format!("unknown lint `{}` in `{}`", entry.name, config_path));
+                    }
+                    continue;
+                }
+            };
+            let reason = entry.reason.as_deref().map(Symbol::intern);
+            let src = LintLevelSource::Config(Symbol::intern(&entry.name), config_path, reason);
+            for &id in ids {
+                if self.check_gated_lint(id, DUMMY_SP) {
+                    self.insert_spec(id, (entry.level, src));
+                }
+            }
+        }
+
+        if self.current_specs().is_empty() {
+            self.sets.list.pop();
+            self.cur = prev;
+        }
+
+        BuilderPush { prev, changed: prev != self.cur }
+    }
+
     /// Attempts to insert the `id` to `level_src` map entry. If unsuccessful
     /// (e.g. if a forbid was already inserted on the same scope), then emits a
     /// diagnostic with no change to `specs`.
@@ -154,6 +218,7 @@ fn insert_spec(&mut self, id: LintId, (level, src): LevelAndSource) {
                     LintLevelSource::Default => false,
                     LintLevelSource::Node(symbol, _, _) => self.store.is_lint_group(symbol),
                     LintLevelSource::CommandLine(symbol, _) => self.store.is_lint_group(symbol),
+                    LintLevelSource::Config(symbol, _, _) => self.store.is_lint_group(symbol),
                 };
                 debug!(
                     "fcw_warning={:?}, specs.get(&id) = {:?}, old_src={:?}, id_name={:?}",
@@ -181,6 +246,9 @@ fn insert_spec(&mut self, id: LintId, (level, src): LevelAndSource) {
                         LintLevelSource::CommandLine(_, _) => {
                             diag.note("`forbid` lint level was set on command line");
                         }
+                        LintLevelSource::Config(_, path, _) => {
+                            diag.note(&format!("`forbid` lint level was set in `{}`", path));
+                        }
                     }
                 };
                 if !fcw_warning {
@@ -699,14 +767,31 @@ fn with_lint_attrs<F>(&mut self, id: hir::HirId, f: F)
         F: FnOnce(&mut Self),
     {
         let is_crate_hir = id == hir::CRATE_HIR_ID;
+        let config_push = self
+            .out_of_line_module_span(id)
+            .map(|inner_span| self.levels.push_lint_config(inner_span, false));
         let attrs = self.tcx.hir().attrs(id);
         let push = self.levels.push(attrs, is_crate_hir, Some(id));
 
-        if push.changed {
+        if push.changed || config_push.as_ref().map_or(false, |push| push.changed) {
             self.levels.register_id(id);
         }
         f(self);
         self.levels.pop(push);
+        if let Some(config_push) = config_push {
+            self.levels.pop(config_push);
+        }
+    }
+
+    /// Returns the span of the contents of the module `id`, if it is an out-of-line module.
+    fn out_of_line_module_span(&self, id: hir::HirId) -> Option<Span> {
+        let Some(hir::Node::Item(item)) = self.tcx.hir().find(id) else { return None };
+        let hir::ItemKind::Mod(module) = item.kind else { return None };
+        let source_map = self.tcx.sess.source_map();
+        let inner_span = module.spans.inner_span;
+        let out_of_line =
+            source_map.span_to_filename(item.span) != source_map.span_to_filename(inner_span);
+        out_of_line.then_some(inner_span)
     }
 }
 
@@ -10,7 +10,9 @@
 //! all other analyses. The `LintPass`es built into rustc are defined
 //! within [rustc_session::lint::builtin],
 //! which has further comments on how to add such a lint.
-//! rustc can also load user-defined lint plugins via the plugin mechanism.
+//! rustc can also load user-defined lint plugins via the plugin mechanism,
+//! or with `-Z lint-plugin`, see the [plugin] module. Lints that only ban
+//! the use of some items can be declared in a file given with `-Z lint-rules`.
 //!
 //! Some of rustc's lints are defined elsewhere in the compiler and work by
 //! calling `add_lint()` on the overall `Session` object. This works when
@@ -55,6 +57,7 @@
 mod internal;
 mod late;
 mod levels;
+mod lint_rules;
 mod methods;
 mod non_ascii_idents;
 mod non_fmt_panic;
@@ -62,6 +65,7 @@
 mod noop_method_call;
 mod pass_by_value;
 mod passes;
+pub mod plugin;
 mod redundant_semicolon;
 mod traits;
 mod types;
@@ -85,6 +89,7 @@
 use enum_intrinsics_non_enums::EnumIntrinsicsNonEnums;
 use hidden_unicode_codepoints::*;
 use internal::*;
+use lint_rules::LintRules;
 use methods::*;
 use non_ascii_idents::*;
 use non_fmt_panic::NonPanicFmt;
@@ -183,6 +188,8 @@ macro_rules! late_lint_passes {
                 EnumIntrinsicsNonEnums: EnumIntrinsicsNonEnums,
                 InvalidAtomicOrdering: InvalidAtomicOrdering,
                 NamedAsmLabels: NamedAsmLabels,
+                // Loads its rules in `check_crate`
+                LintRules: LintRules::default(),
             ]
         );
     };
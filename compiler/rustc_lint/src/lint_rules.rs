@@ -0,0 +1,250 @@
+//! Declarative lint rules, loaded with `-Z lint-rules=<path>`.
+//!
+//! Most house rules only ban the use of some items, which does not need a compiled lint plugin. A
+//! rules file lists such bans, one per line:
+//!
+//! ```text
+//! # Comments start with `#`.
+//! forbid std::process::exit outside crate::main "return an `ExitCode` from `main` instead"
+//! forbid core::result::Result::unwrap in crate::net
+//! ```
+//!
+//! `forbid <path>` bans every use of the item defined at `<path>`: calls, method calls, and any
+//! other expression resolving to it. `in <path>` restricts the ban to the uses in the items under
+//! `<path>`, and `outside <path>` exempts the uses in the items under `<path>`; both can be
+//! repeated. The optional quoted reason is added to the diagnostics.
+//!
+//! The rules are matched against the resolved definitions, so imports and renames do not matter,
+//! but the paths must be the ones of the definitions rather than of re-exports. The paths of the
+//! local crate start with `crate`, and `std`, `core` and `alloc` are interchangeable, since `std`
+//! re-exports most of the other two.
+
+use crate::{LateContext, LateLintPass, LintContext};
+use rustc_hir::def::Res;
+use rustc_hir::def_id::{DefId, LOCAL_CRATE};
+use rustc_hir::{Expr, ExprKind};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_span::symbol::Symbol;
+
+use std::fs;
+use std::path::PathBuf;
+
+declare_lint! {
+    /// The `lint_rules` lint detects uses of the items forbidden by the rules
+    /// files given with `-Z lint-rules`.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore (needs a rules file)
+    /// fn helper() {
+    ///     std::process::exit(1);
+    /// }
+    /// ```
+    ///
+    /// with the rules file:
+    ///
+    /// ```text
+    /// forbid std::process::exit outside crate::main
+    /// ```
+    ///
+    /// produces:
+    ///
+    /// ```text
+    /// error: use of `std::process::exit` is forbidden by a lint rule
+    ///  --> src/main.rs:2:5
+    ///   |
+    /// 2 |     std::process::exit(1);
+    ///   |     ^^^^^^^^^^^^^^^^^^
+    ///   |
+    ///   = note: `#[deny(lint_rules)]` on by default
+    ///   = note: the rule is defined at `rules.txt:1`
+    /// ```
+    ///
+    /// ### Explanation
+    ///
+    /// Projects often ban some items, like functions that should only be
+    /// called from one place, or methods that a module must not call. The
+    /// lint rules express such bans without writing a lint plugin.
+    pub LINT_RULES,
+    Deny,
+    "detects uses of items forbidden by the rules of `-Z lint-rules`"
+}
+
+/// A rule of a rules file.
+#[derive(Debug, PartialEq)]
+pub struct LintRule {
+    /// The path of the forbidden item.
+    pub path: Vec<String>,
+    /// The paths of the items the ban is restricted to, if any.
+    pub only_in: Vec<Vec<String>>,
+    /// The paths of the items where the item is allowed.
+    pub outside: Vec<Vec<String>>,
+    pub reason: Option<String>,
+    /// The line of the rule in its file.
+    pub line: usize,
+}
+
+impl LintRule {
+    fn applies_in(&self, scope: &[String]) -> bool {
+        (self.only_in.is_empty() || self.only_in.iter().any(|prefix| scope.starts_with(prefix)))
+            && !self.outside.iter().any(|prefix| scope.starts_with(prefix))
+    }
+}
+
+/// Canonicalizes the crate name starting a path, see the module documentation.
+fn canonical_crate(name: &str, local_crate: Symbol) -> String {
+    match name {
+        "core" | "alloc" => "std".to_string(),
+        _ if name == local_crate.as_str() => "crate".to_string(),
+        _ => name.to_string(),
+    }
+}
+
+fn parse_path(path: &str) -> Result<Vec<String>, String> {
+    let segments: Vec<String> = path.split("::").map(|segment| segment.to_string()).collect();
+    if segments.iter().any(|segment| segment.is_empty()) {
+        return Err(format!("invalid path `{}`", path));
+    }
+    Ok(segments)
+}
+
+/// Parses the rules of a rules file.
+pub fn parse_lint_rules(src: &str) -> Result<Vec<LintRule>, String> {
+    let mut rules = vec![];
+    for (i, line) in src.lines().enumerate() {
+        let line_number = i + 1;
+        let error = |msg: String| format!("line {}: {}", line_number, msg);
+
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (line, reason) = match line.find('"') {
+            Some(start) => match line[start + 1..].strip_suffix('"') {
+                Some(reason) if !reason.contains('"') => (&line[..start], Some(reason.to_string())),
+                _ => {
+                    return Err(error("the reason must be a quoted string ending the line".into()))
+                }
+            },
+            None => (line, None),
+        };
+
+        let mut words = line.split_whitespace();
+        let path = match (words.next(), words.next()) {
+            (Some("forbid"), Some(path)) => parse_path(path).map_err(error)?,
+            (Some("forbid"), None) => return Err(error("expected a path after `forbid`".into())),
+            (Some(word), _) => return Err(error(format!("expected `forbid`, found `{}`", word))),
+            (None, _) => return Err(error("expected `forbid`".into())),
+        };
+        let mut rule =
+            LintRule { path, only_in: vec![], outside: vec![], reason, line: line_number };
+        while let Some(word) = words.next() {
+            let scopes = match word {
+                "in" => &mut rule.only_in,
+                "outside" => &mut rule.outside,
+                _ => {
+                    return Err(error(format!("expected `in` or `outside`, found `{}`", word)));
+                }
+            };
+            let Some(scope) = words.next() else {
+                return Err(error(format!("expected a path after `{}`", word)));
+            };
+            scopes.push(parse_path(scope).map_err(error)?);
+        }
+        rules.push(rule);
+    }
+    Ok(rules)
+}
+
+/// Checks the rules of the files given with `-Z lint-rules`.
+#[derive(Default)]
+pub struct LintRules {
+    /// The rules, with the file they are defined in.
+    rules: Vec<(LintRule, PathBuf)>,
+}
+
+impl_lint_pass!(LintRules => [LINT_RULES]);
+
+impl LintRules {
+    fn canonical_path(cx: &LateContext<'_>, def_id: DefId) -> Vec<String> {
+        let local_crate = cx.tcx.crate_name(LOCAL_CRATE);
+        let mut path = cx.get_def_path(def_id).into_iter();
+        let krate = path.next().map(|krate| canonical_crate(krate.as_str(), local_crate));
+        krate.into_iter().chain(path.map(|segment| segment.to_string())).collect()
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for LintRules {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        let local_crate = cx.tcx.crate_name(LOCAL_CRATE);
+        for file in &cx.sess().opts.debugging_opts.lint_rules {
+            let rules = fs::read_to_string(file)
+                .map_err(|e| e.to_string())
+                .and_then(|src| parse_lint_rules(&src));
+            let rules = match rules {
+                Ok(rules) => rules,
+                Err(e) => {
+                    let msg = format!("failed to load lint rules from `{}`: {}", file.display(), e);
+                    cx.sess().err(&msg);
+                    continue;
+                }
+            };
+            for mut rule in rules {
+                for path in std::iter::once(&mut rule.path)
+                    .chain(&mut rule.only_in)
+                    .chain(&mut rule.outside)
+                {
+                    path[0] = canonical_crate(&path[0], local_crate);
+                }
+                self.rules.push((rule, file.clone()));
+            }
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if self.rules.is_empty() {
+            return;
+        }
+        let def_id = match expr.kind {
+            ExprKind::Path(ref qpath) => match cx.qpath_res(qpath, expr.hir_id) {
+                Res::Def(_, def_id) => def_id,
+                _ => return,
+            },
+            ExprKind::MethodCall(..) => {
+                match cx.typeck_results().type_dependent_def_id(expr.hir_id) {
+                    Some(def_id) => def_id,
+                    None => return,
+                }
+            }
+            _ => return,
+        };
+        let path = Self::canonical_path(cx, def_id);
+        if !self.rules.iter().any(|(rule, _)| rule.path == path) {
+            return;
+        }
+
+        // Closures are part of the item they are defined in.
+        let Some(body) = cx.enclosing_body else { return };
+        let owner = cx.tcx.hir().body_owner_def_id(body).to_def_id();
+        let scope = Self::canonical_path(cx, cx.tcx.typeck_root_def_id(owner));
+        let rule = self.rules.iter().find(|(rule, _)| rule.path == path && rule.applies_in(&scope));
+        let Some((rule, file)) = rule else { return };
+
+        let span = match expr.kind {
+            ExprKind::MethodCall(segment, ..) => segment.ident.span,
+            _ => expr.span,
+        };
+        cx.struct_span_lint(LINT_RULES, span, |lint| {
+            let path = with_no_trimmed_paths!(cx.tcx.def_path_str(def_id));
+            let mut err = lint.build(&format!("use of `{}` is forbidden by a lint rule", path));
+            if let Some(reason) = &rule.reason {
+                err.note(reason);
+            }
+            err.note(&format!("the rule is defined at `{}:{}`", file.display(), rule.line));
+            err.emit();
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,62 @@
+use super::{parse_lint_rules, LintRule};
+
+fn path(path: &str) -> Vec<String> {
+    path.split("::").map(|segment| segment.to_string()).collect()
+}
+
+#[test]
+fn parse_rules() {
+    let src = r#"
+# A comment.
+forbid std::process::exit outside crate::main "return from `main` instead"
+
+forbid core::result::Result::unwrap in crate::net in crate::fs outside crate::fs::tests
+"#;
+    assert_eq!(
+        parse_lint_rules(src),
+        Ok(vec![
+            LintRule {
+                path: path("std::process::exit"),
+                only_in: vec![],
+                outside: vec![path("crate::main")],
+                reason: Some("return from `main` instead".to_string()),
+                line: 3,
+            },
+            LintRule {
+                path: path("core::result::Result::unwrap"),
+                only_in: vec![path("crate::net"), path("crate::fs")],
+                outside: vec![path("crate::fs::tests")],
+                reason: None,
+                line: 5,
+            },
+        ])
+    );
+}
+
+#[test]
+fn parse_errors() {
+    let error = |src| parse_lint_rules(src).unwrap_err();
+    assert_eq!(error("allow std::process::exit"), "line 1: expected `forbid`, found `allow`");
+    assert_eq!(error("\nforbid"), "line 2: expected a path after `forbid`");
+    assert_eq!(error("forbid std::"), "line 1: invalid path `std::`");
+    assert_eq!(error("forbid exit in"), "line 1: expected a path after `in`");
+    assert_eq!(
+        error("forbid exit within main"),
+        "line 1: expected `in` or `outside`, found `within`"
+    );
+    assert_eq!(
+        error(r#"forbid exit "unterminated"#),
+        "line 1: the reason must be a quoted string ending the line"
+    );
+    assert_eq!(error(r#""only a reason""#), "line 1: expected `forbid`");
+}
+
+#[test]
+fn scopes() {
+    let rule = &parse_lint_rules("forbid exit in crate::net outside crate::net::tests").unwrap()[0];
+    assert!(rule.applies_in(&path("crate::net::connect")));
+    assert!(!rule.applies_in(&path("crate::net::tests::connect")));
+    assert!(!rule.applies_in(&path("crate::main")));
+    // Scopes are matched by segments, not by characters.
+    assert!(!rule.applies_in(&path("crate::network")));
+}
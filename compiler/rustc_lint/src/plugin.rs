@@ -0,0 +1,143 @@
+//! Lint plugins, loaded with `-Z lint-plugin=<path>`.
+//!
+//! A lint plugin is a dynamic library that declares its lints and lint passes with
+//! [`declare_lint_plugin!`]. Unlike the deprecated `#![plugin]` registrars, which are handed the
+//! whole `LintStore`, lint plugins only see a [`LintPluginRegistry`], which exposes the parts of
+//! the store that adding lints needs, so that the store can change without breaking them.
+//!
+//! The declaration starts with the version of the plugin interface, which is the only field read
+//! before the plugin is known to be compatible: the layout of the declaration is `repr(C)`, so it
+//! can be read from a plugin built against any version of the interface. The compiler then checks
+//! that the plugin was built by the same compiler, since lint passes are Rust trait objects and
+//! the Rust ABI is not stable, and only then calls its registration function.
+//!
+//! ```ignore (requires a dylib crate)
+//! #![feature(rustc_private)]
+//!
+//! extern crate rustc_driver;
+//! #[macro_use]
+//! extern crate rustc_lint;
+//! #[macro_use]
+//! extern crate rustc_session;
+//!
+//! use rustc_lint::plugin::LintPluginRegistry;
+//!
+//! declare_lint!(HOUSE_RULE, Warn, "enforce a house rule");
+//! declare_lint_pass!(HouseRule => [HOUSE_RULE]);
+//! impl rustc_lint::LateLintPass<'_> for HouseRule {}
+//!
+//! fn register(registry: &mut LintPluginRegistry<'_>) {
+//!     registry.register_lints(&[&HOUSE_RULE]);
+//!     registry.register_late_pass(|| Box::new(HouseRule));
+//! }
+//!
+//! declare_lint_plugin!(register);
+//! ```
+
+use crate::passes::{EarlyLintPassObject, LateLintPassObject};
+use crate::LintStore;
+use rustc_data_structures::sync;
+use rustc_session::lint::{Lint, LintId};
+use rustc_session::Session;
+use std::path::Path;
+
+/// The version of the lint plugin interface, incremented on each change of
+/// [`LintPluginDeclaration`] or [`LintPluginRegistry`].
+pub const LINT_PLUGIN_VERSION: u32 = 1;
+
+/// The version of the compiler that lint plugins must have been built by.
+pub const RUSTC_VERSION: &str = match option_env!("CFG_VERSION") {
+    Some(version) => version,
+    None => "unknown version",
+};
+
+/// The name of the static holding the [`LintPluginDeclaration`] of a lint plugin.
+pub const LINT_PLUGIN_DECLARATION_SYMBOL: &[u8] = b"__rustc_lint_plugin_declaration";
+
+/// The declaration of a lint plugin, created by [`declare_lint_plugin!`].
+#[repr(C)]
+pub struct LintPluginDeclaration {
+    /// Must stay the first field, see the module documentation.
+    pub version: u32,
+    pub rustc_version: &'static str,
+    pub register: fn(&mut LintPluginRegistry<'_>),
+}
+
+/// The interface through which lint plugins register their lints and lint passes.
+pub struct LintPluginRegistry<'a> {
+    sess: &'a Session,
+    /// The path of the plugin registering, to name it in errors.
+    plugin: &'a Path,
+    lint_store: &'a mut LintStore,
+}
+
+impl<'a> LintPluginRegistry<'a> {
+    pub fn new(
+        sess: &'a Session,
+        plugin: &'a Path,
+        lint_store: &'a mut LintStore,
+    ) -> LintPluginRegistry<'a> {
+        LintPluginRegistry { sess, plugin, lint_store }
+    }
+
+    /// Registers the `lints`, reporting an error instead for those whose name is already taken.
+    pub fn register_lints(&mut self, lints: &[&'static Lint]) {
+        for &lint in lints {
+            if self.check_name_is_free("lint", &lint.name_lower()) {
+                self.lint_store.register_lints(&[lint]);
+            }
+        }
+    }
+
+    /// Registers a lint group, so that all the `lints` can be allowed or denied at once.
+    pub fn register_group(&mut self, name: &'static str, lints: &[&'static Lint]) {
+        if self.check_name_is_free("lint group", name) {
+            let lints = lints.iter().map(|&lint| LintId::of(lint)).collect();
+            self.lint_store.register_group(true, name, None, lints);
+        }
+    }
+
+    /// Reports an error if `name` is already the name of a lint or of a lint group, which happens
+    /// when the same plugin is loaded twice or two plugins declare the same name.
+    fn check_name_is_free(&self, kind: &str, name: &str) -> bool {
+        if !self.lint_store.is_lint_or_group_name(name) {
+            return true;
+        }
+        self.sess.err(&format!(
+            "lint plugin `{}` registers the {} `{}`, but this name is already registered",
+            self.plugin.display(),
+            kind,
+            name
+        ));
+        false
+    }
+
+    pub fn register_early_pass(
+        &mut self,
+        pass: impl Fn() -> EarlyLintPassObject + 'static + sync::Send + sync::Sync,
+    ) {
+        self.lint_store.register_early_pass(pass);
+    }
+
+    pub fn register_late_pass(
+        &mut self,
+        pass: impl Fn() -> LateLintPassObject + 'static + sync::Send + sync::Sync,
+    ) {
+        self.lint_store.register_late_pass(pass);
+    }
+}
+
+/// Declares the function registering the lints of a lint plugin, of type
+/// `fn(&mut LintPluginRegistry<'_>)`.
+#[macro_export]
+macro_rules! declare_lint_plugin {
+    ($register:path) => {
+        #[no_mangle]
+        pub static __rustc_lint_plugin_declaration: $crate::plugin::LintPluginDeclaration =
+            $crate::plugin::LintPluginDeclaration {
+                version: $crate::plugin::LINT_PLUGIN_VERSION,
+                rustc_version: $crate::plugin::RUSTC_VERSION,
+                register: $register,
+            };
+    };
+}
@@ -439,6 +439,71 @@
     crate_level_only
 }
 
+declare_lint! {
+    /// The `unused_pub_items` lint detects public items of the crates of a
+    /// workspace that are never used by the other crates of the workspace.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore (needs extern crate)
+    /// // In the `util` crate, which is compiled with `-W unused-pub-items`.
+    /// pub fn used() {}
+    /// pub fn unused() {}
+    ///
+    /// // In the binary crate.
+    /// #![deny(unused_pub_items)]
+    /// fn main() {
+    ///     util::used();
+    /// }
+    /// ```
+    ///
+    /// This will produce:
+    ///
+    /// ```text
+    /// error: public function `util::unused` is never used outside of the crate `util`
+    ///  --> util/src/lib.rs:2:1
+    ///   |
+    /// 2 | pub fn unused() {}
+    ///   | ^^^^^^^^^^^^^^^
+    ///   |
+    /// note: the lint level is defined here
+    ///  --> src/main.rs:1:9
+    ///   |
+    /// 1 | #![deny(unused_pub_items)]
+    ///   |         ^^^^^^^^^^^^^^^^
+    /// ```
+    ///
+    /// ### Explanation
+    ///
+    /// The [`dead_code`] lint cannot tell if a public item is used, since
+    /// it may be used by the crates depending on the crate defining it. In a
+    /// workspace, all the crates depending on a library are usually known,
+    /// and the public items that none of them use can be made private or
+    /// removed. When this lint is enabled in an executable, the items used by
+    /// each crate of its crate graph, as recorded in their metadata, are
+    /// compared with the public items of the library crates that were
+    /// themselves compiled with this lint enabled. Those crates are the ones
+    /// of the workspace: Cargo caps the lints of the other dependencies to
+    /// "allow". Each of those crates, and the executable itself, is also
+    /// reported when it depends directly on a crate, of the workspace or not,
+    /// without using any of its public items.
+    ///
+    /// Only the paths to the items, the methods called and the associated
+    /// items of the types and traits are counted as uses. Macros, trait
+    /// implementations and the items only used within their own crate are
+    /// not counted.
+    ///
+    /// This lint is "allow" by default because the items used by the other
+    /// executables of the workspace, by tests or by examples are reported
+    /// when they are not used by the executable enabling the lint.
+    ///
+    /// [`dead_code`]: warn-by-default.md#dead-code
+    pub UNUSED_PUB_ITEMS,
+    Allow,
+    "public items that are never used by the other crates of the workspace",
+    crate_level_only
+}
+
 declare_lint! {
     /// The `unused_qualifications` lint detects unnecessarily qualified
     /// names.
@@ -3137,6 +3202,7 @@
         UNUSED_IMPORTS,
         UNUSED_EXTERN_CRATES,
         UNUSED_CRATE_DEPENDENCIES,
+        UNUSED_PUB_ITEMS,
         UNUSED_QUALIFICATIONS,
         UNKNOWN_LINTS,
         UNFULFILLED_LINT_EXPECTATIONS,
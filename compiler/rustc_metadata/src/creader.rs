@@ -47,6 +47,9 @@ pub struct CStore {
 
     /// Unused externs of the crate
     unused_externs: Vec<Symbol>,
+
+    /// The crates passed with `--extern` that the crate never references, and so never loads.
+    unloaded_externs: Vec<Symbol>,
 }
 
 impl std::fmt::Debug for CStore {
@@ -194,6 +197,10 @@ fn crate_dependencies_in_reverse_postorder(&self, cnum: CrateNum) -> Vec<CrateNu
         self.has_global_allocator
     }
 
+    crate fn unloaded_externs(&self) -> &[Symbol] {
+        &self.unloaded_externs
+    }
+
     pub fn report_unused_deps(&self, tcx: TyCtxt<'_>) {
         let json_unused_externs = tcx.sess.opts.json_unused_externs;
 
@@ -243,6 +250,7 @@ pub fn new(
                 has_global_allocator: false,
                 stable_crate_ids,
                 unused_externs: Vec::new(),
+                unloaded_externs: Vec::new(),
             },
             used_extern_options: Default::default(),
         }
@@ -920,6 +928,7 @@ fn report_unused_deps(&mut self, krate: &ast::Crate) {
             }
 
             // Got a real unused --extern
+            self.cstore.unloaded_externs.push(name_interned);
             if self.sess.opts.json_unused_externs.is_enabled() {
                 self.cstore.unused_externs.push(name_interned);
                 continue;
@@ -1329,6 +1329,18 @@ fn get_traits(self) -> impl Iterator<Item = DefId> + 'a {
         self.root.traits.decode(self).map(move |index| self.local_def_id(index))
     }
 
+    fn get_crate_dependencies(self) -> impl Iterator<Item = CrateNum> + 'a {
+        self.root.crate_dependencies.decode(self)
+    }
+
+    fn get_unloaded_crate_dependencies(self) -> impl Iterator<Item = Symbol> + 'a {
+        self.root.unloaded_crate_dependencies.decode(self)
+    }
+
+    fn get_used_foreign_items(self) -> impl Iterator<Item = DefId> + 'a {
+        self.root.used_foreign_items.decode(self)
+    }
+
     /// Decodes all trait impls in the crate (for rustdoc).
     fn get_trait_impls(self) -> impl Iterator<Item = (DefId, DefId, Option<SimplifiedType>)> + 'a {
         self.cdata.trait_impls.iter().flat_map(move |(&(trait_cnum_raw, trait_index), impls)| {
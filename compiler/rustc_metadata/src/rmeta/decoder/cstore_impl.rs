@@ -211,6 +211,12 @@ fn into_args(self) -> (DefId, SimplifiedType) {
     extra_filename => { cdata.root.extra_filename.clone() }
 
     traits_in_crate => { tcx.arena.alloc_from_iter(cdata.get_traits()) }
+    crate_dependencies => { tcx.arena.alloc_from_iter(cdata.get_crate_dependencies()) }
+    unloaded_crate_dependencies => {
+        tcx.arena.alloc_from_iter(cdata.get_unloaded_crate_dependencies())
+    }
+    used_foreign_items => { tcx.arena.alloc_from_iter(cdata.get_used_foreign_items()) }
+    checks_unused_pub_items => { cdata.root.checks_unused_pub_items }
     implementations_of_trait => { cdata.get_implementations_of_trait(tcx, other) }
     crate_incoherent_impls => { cdata.get_incoherent_impls(tcx, other) }
 
@@ -382,6 +388,10 @@ pub(in crate::rmeta) fn provide(providers: &mut Providers) {
             assert_eq!(cnum, LOCAL_CRATE);
             CStore::from_tcx(tcx).has_global_allocator()
         },
+        unloaded_crate_dependencies: |tcx, cnum| {
+            assert_eq!(cnum, LOCAL_CRATE);
+            tcx.arena.alloc_slice(CStore::from_tcx(tcx).unloaded_externs())
+        },
         postorder_cnums: |tcx, ()| {
             tcx.arena
                 .alloc_slice(&CStore::from_tcx(tcx).crate_dependencies_in_postorder(LOCAL_CRATE))
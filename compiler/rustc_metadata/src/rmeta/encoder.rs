@@ -629,6 +629,14 @@ fn encode_crate_root(&mut self) -> Lazy<CrateRoot<'tcx>> {
         i = self.position();
         let incoherent_impls = self.encode_incoherent_impls();
         let incoherent_impls_bytes = self.position() - i;
+
+        // Encode the direct dependencies of this crate and the def IDs of the items of other
+        // crates it uses, for the `unused_pub_items` lint.
+        i = self.position();
+        let crate_dependencies = self.encode_crate_dependencies();
+        let unloaded_crate_dependencies = self.encode_unloaded_crate_dependencies();
+        let used_foreign_items = self.encode_used_foreign_items();
+        let used_foreign_items_bytes = self.position() - i;
         // Encode MIR.
         i = self.position();
         self.encode_mir();
@@ -730,6 +738,7 @@ fn encode_crate_root(&mut self) -> Lazy<CrateRoot<'tcx>> {
             no_builtins: tcx.sess.contains_name(&attrs, sym::no_builtins),
             panic_runtime: tcx.sess.contains_name(&attrs, sym::panic_runtime),
             profiler_runtime: tcx.sess.contains_name(&attrs, sym::profiler_runtime),
+            checks_unused_pub_items: tcx.checks_unused_pub_items(LOCAL_CRATE),
             symbol_mangling_version: tcx.sess.opts.get_symbol_mangling_version(),
 
             crate_deps,
@@ -744,6 +753,9 @@ fn encode_crate_root(&mut self) -> Lazy<CrateRoot<'tcx>> {
             traits,
             impls,
             incoherent_impls,
+            crate_dependencies,
+            unloaded_crate_dependencies,
+            used_foreign_items,
             exported_symbols,
             interpret_alloc_index,
             tables,
@@ -774,6 +786,7 @@ fn encode_crate_root(&mut self) -> Lazy<CrateRoot<'tcx>> {
             eprintln!("               traits bytes: {}", traits_bytes);
             eprintln!("                impls bytes: {}", impls_bytes);
             eprintln!("     incoherent_impls bytes: {}", incoherent_impls_bytes);
+            eprintln!("   used foreign items bytes: {}", used_foreign_items_bytes);
             eprintln!("         exp. symbols bytes: {}", exported_symbols_bytes);
             eprintln!("       def-path table bytes: {}", def_path_table_bytes);
             eprintln!("      def-path hashes bytes: {}", def_path_hash_map_bytes);
@@ -1813,6 +1826,31 @@ fn encode_traits(&mut self) -> Lazy<[DefIndex]> {
         self.lazy(self.tcx.traits_in_crate(LOCAL_CRATE).iter().map(|def_id| def_id.index))
     }
 
+    fn encode_crate_dependencies(&mut self) -> Lazy<[CrateNum]> {
+        empty_proc_macro!(self);
+        // Only the crates checked by the lint are counted as users of their dependencies.
+        if !self.tcx.checks_unused_pub_items(LOCAL_CRATE) {
+            return Lazy::empty();
+        }
+        self.lazy(self.tcx.crate_dependencies(LOCAL_CRATE))
+    }
+
+    fn encode_unloaded_crate_dependencies(&mut self) -> Lazy<[Symbol]> {
+        empty_proc_macro!(self);
+        if !self.tcx.checks_unused_pub_items(LOCAL_CRATE) {
+            return Lazy::empty();
+        }
+        self.lazy(self.tcx.unloaded_crate_dependencies(LOCAL_CRATE))
+    }
+
+    fn encode_used_foreign_items(&mut self) -> Lazy<[DefId]> {
+        empty_proc_macro!(self);
+        if !self.tcx.checks_unused_pub_items(LOCAL_CRATE) {
+            return Lazy::empty();
+        }
+        self.lazy(self.tcx.used_foreign_items(LOCAL_CRATE))
+    }
+
     /// Encodes an index, mapping each trait to its (local) implementations.
     fn encode_impls(&mut self) -> Lazy<[TraitImpls]> {
         debug!("EncodeContext::encode_traits_and_impls()");
@@ -216,6 +216,9 @@ macro_rules! Lazy {
     traits: Lazy<[DefIndex]>,
     impls: Lazy<[TraitImpls]>,
     incoherent_impls: Lazy<[IncoherentImpls]>,
+    crate_dependencies: Lazy<[CrateNum]>,
+    unloaded_crate_dependencies: Lazy<[Symbol]>,
+    used_foreign_items: Lazy<[DefId]>,
     interpret_alloc_index: Lazy<[u32]>,
     proc_macro_data: Option<ProcMacroData>,
 
@@ -238,6 +241,7 @@ macro_rules! Lazy {
     no_builtins: bool,
     panic_runtime: bool,
     profiler_runtime: bool,
+    checks_unused_pub_items: bool,
     symbol_mangling_version: SymbolManglingVersion,
 }
 
@@ -31,6 +31,9 @@ pub enum LintLevelSource {
     /// The provided `Level` is the level specified on the command line.
     /// (The actual level may be lower due to `--cap-lints`.)
     CommandLine(Symbol, Level),
+
+    /// Lint level was set by a lint configuration file.
+    Config(Symbol, Symbol /* path of the file */, Option<Symbol> /* reason */),
 }
 
 impl LintLevelSource {
@@ -39,6 +42,7 @@ pub fn name(&self) -> Symbol {
             LintLevelSource::Default => symbol::kw::Default,
             LintLevelSource::Node(name, _, _) => name,
             LintLevelSource::CommandLine(name, _) => name,
+            LintLevelSource::Config(name, _, _) => name,
         }
     }
 
@@ -47,6 +51,7 @@ pub fn span(&self) -> Span {
             LintLevelSource::Default => DUMMY_SP,
             LintLevelSource::Node(_, span, _) => span,
             LintLevelSource::CommandLine(_, _) => DUMMY_SP,
+            LintLevelSource::Config(_, _, _) => DUMMY_SP,
         }
     }
 }
@@ -241,6 +246,11 @@ pub fn build(mut self, msg: &str) -> DiagnosticBuilder<'a, G> {
     pub fn new(err: DiagnosticBuilder<'a, G>) -> LintDiagnosticBuilder<'a, G> {
         LintDiagnosticBuilder(err)
     }
+
+    /// Record the path of the item the lint is emitted in.
+    pub fn set_item_path(&mut self, item_path: String) {
+        self.0.set_item_path(item_path);
+    }
 }
 
 impl<'a> LintDiagnosticBuilder<'a, ErrorGuaranteed> {
@@ -285,6 +295,22 @@ pub fn explain_lint_level_source(
                 ));
             }
         }
+        LintLevelSource::Config(lint_config_name, path, reason) => {
+            if let Some(rationale) = reason {
+                err.note(rationale.as_str());
+            }
+            if lint_config_name.as_str() == name {
+                err.note_once(&format!("`{}` set to `{}` in `{}`", name, level.as_str(), path));
+            } else {
+                err.note_once(&format!(
+                    "`{}` set to `{}` by `{}` in `{}`",
+                    name,
+                    level.as_str(),
+                    lint_config_name,
+                    path
+                ));
+            }
+        }
         LintLevelSource::Node(lint_attr_name, src, reason) => {
             if let Some(rationale) = reason {
                 err.note(rationale.as_str());
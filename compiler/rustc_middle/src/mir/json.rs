@@ -0,0 +1,259 @@
+//! Machine-readable JSON dumps of MIR bodies, written by `-Z dump-mir-format=json`.
+//!
+//! Unlike the human-readable `.mir` files, the output of this module follows a
+//! versioned schema, so that external tools can consume MIR without linking
+//! against the compiler. Any change to the shape of the output must bump
+//! [`MIR_JSON_VERSION`].
+
+use crate::mir::*;
+use crate::ty::{self, TyCtxt};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_serialize::json::as_pretty_json;
+use rustc_span::Span;
+use std::fmt::Display;
+use std::io::{self, Write};
+
+/// The version of the JSON schema written by [`write_mir_fn_json`].
+pub const MIR_JSON_VERSION: u32 = 1;
+
+// The following data types are provided just for serialisation.
+
+#[derive(Encodable)]
+struct MirBody {
+    /// The version of this schema, see `MIR_JSON_VERSION`.
+    version: u32,
+    /// The path of the item the body belongs to.
+    def_path: String,
+    /// The index of the promoted constant, if this is the body of one.
+    promoted: Option<usize>,
+    /// The name of the pass after or before which the body was dumped.
+    pass_name: String,
+    /// Either `before` or `after`, relative to `pass_name`.
+    disambiguator: String,
+    /// The `MirPhase` the body is in.
+    phase: String,
+    span: Option<MirSpan>,
+    /// The number of arguments; locals `1..=arg_count` are the arguments.
+    arg_count: usize,
+    locals: Vec<MirLocal>,
+    basic_blocks: Vec<MirBasicBlock>,
+}
+
+#[derive(Encodable)]
+struct MirLocal {
+    /// The index of the local, as in `_0`, `_1`, ...
+    index: usize,
+    /// One of `return`, `arg`, `var` or `temp`.
+    kind: &'static str,
+    /// The name of the user variable stored in this local, if any.
+    name: Option<String>,
+    ty: String,
+    mutable: bool,
+    span: Option<MirSpan>,
+}
+
+#[derive(Encodable)]
+struct MirBasicBlock {
+    /// The index of the block, as in `bb0`, `bb1`, ...
+    index: usize,
+    is_cleanup: bool,
+    statements: Vec<MirStatement>,
+    terminator: Option<MirTerminator>,
+}
+
+#[derive(Encodable)]
+struct MirStatement {
+    /// The name of the `StatementKind` variant, e.g. `Assign`.
+    kind: &'static str,
+    /// The statement as it is written in `.mir` files.
+    text: String,
+    span: Option<MirSpan>,
+}
+
+#[derive(Encodable)]
+struct MirTerminator {
+    /// The name of the `TerminatorKind` variant, e.g. `SwitchInt`.
+    kind: &'static str,
+    /// The terminator as it is written in `.mir` files, without its successors.
+    text: String,
+    successors: Vec<MirSuccessor>,
+    span: Option<MirSpan>,
+}
+
+#[derive(Encodable)]
+struct MirSuccessor {
+    /// The label of the edge, e.g. `return`, `unwind` or the value switched on.
+    label: String,
+    /// The index of the target block.
+    target: usize,
+}
+
+#[derive(Encodable)]
+struct MirSpan {
+    file_name: String,
+    /// 1-based.
+    line_start: usize,
+    line_end: usize,
+    /// 1-based, character offset.
+    column_start: usize,
+    column_end: usize,
+}
+
+/// Write the MIR of `body` as a JSON document following the schema version
+/// `MIR_JSON_VERSION`.
+pub fn write_mir_fn_json<'tcx, W>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    pass_name: &str,
+    disambiguator: &dyn Display,
+    w: &mut W,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    // Print full paths, so that the output does not depend on which other items are in scope.
+    let body = ty::print::with_no_trimmed_paths!(body_to_json(tcx, body, pass_name, disambiguator));
+    writeln!(w, "{}", as_pretty_json(&body))
+}
+
+fn body_to_json<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    pass_name: &str,
+    disambiguator: &dyn Display,
+) -> MirBody {
+    // See notes on #41697 in `pretty.rs`.
+    let def_path =
+        ty::print::with_forced_impl_filename_line!(tcx.def_path_str(body.source.def_id()));
+
+    let mut local_names = FxHashMap::default();
+    for var_debug_info in &body.var_debug_info {
+        if let VarDebugInfoContents::Place(place) = var_debug_info.value {
+            if let Some(local) = place.as_local() {
+                local_names.entry(local).or_insert(var_debug_info.name);
+            }
+        }
+    }
+
+    let locals = body
+        .local_decls
+        .iter_enumerated()
+        .map(|(local, decl)| MirLocal {
+            index: local.index(),
+            kind: match body.local_kind(local) {
+                LocalKind::ReturnPointer => "return",
+                LocalKind::Arg => "arg",
+                LocalKind::Var => "var",
+                LocalKind::Temp => "temp",
+            },
+            name: local_names.get(&local).map(|name| name.to_string()),
+            ty: decl.ty.to_string(),
+            mutable: decl.mutability == Mutability::Mut,
+            span: mir_span(tcx, decl.source_info.span),
+        })
+        .collect();
+
+    let basic_blocks = body
+        .basic_blocks()
+        .iter_enumerated()
+        .map(|(block, data)| MirBasicBlock {
+            index: block.index(),
+            is_cleanup: data.is_cleanup,
+            statements: data
+                .statements
+                .iter()
+                .map(|statement| MirStatement {
+                    kind: statement_kind_name(&statement.kind),
+                    text: format!("{:?}", statement),
+                    span: mir_span(tcx, statement.source_info.span),
+                })
+                .collect(),
+            terminator: data.terminator.as_ref().map(|terminator| {
+                let mut text = String::new();
+                // Writing into a `String` cannot fail.
+                terminator.kind.fmt_head(&mut text).unwrap();
+                let successors = terminator
+                    .kind
+                    .fmt_successor_labels()
+                    .into_iter()
+                    .zip(terminator.successors())
+                    .map(|(label, target)| MirSuccessor {
+                        label: label.into_owned(),
+                        target: target.index(),
+                    })
+                    .collect();
+                MirTerminator {
+                    kind: terminator_kind_name(&terminator.kind),
+                    text,
+                    successors,
+                    span: mir_span(tcx, terminator.source_info.span),
+                }
+            }),
+        })
+        .collect();
+
+    MirBody {
+        version: MIR_JSON_VERSION,
+        def_path,
+        promoted: body.source.promoted.map(|promoted| promoted.index()),
+        pass_name: pass_name.to_string(),
+        disambiguator: disambiguator.to_string(),
+        phase: format!("{:?}", body.phase),
+        span: mir_span(tcx, body.span),
+        arg_count: body.arg_count,
+        locals,
+        basic_blocks,
+    }
+}
+
+fn mir_span(tcx: TyCtxt<'_>, span: Span) -> Option<MirSpan> {
+    if span.is_dummy() {
+        return None;
+    }
+    let source_map = tcx.sess.source_map();
+    let start = source_map.lookup_char_pos(span.lo());
+    let end = source_map.lookup_char_pos(span.hi());
+    Some(MirSpan {
+        file_name: start.file.name.prefer_remapped().to_string(),
+        line_start: start.line,
+        line_end: end.line,
+        column_start: start.col.0 + 1,
+        column_end: end.col.0 + 1,
+    })
+}
+
+fn statement_kind_name(kind: &StatementKind<'_>) -> &'static str {
+    match kind {
+        StatementKind::Assign(..) => "Assign",
+        StatementKind::FakeRead(..) => "FakeRead",
+        StatementKind::SetDiscriminant { .. } => "SetDiscriminant",
+        StatementKind::Deinit(..) => "Deinit",
+        StatementKind::StorageLive(..) => "StorageLive",
+        StatementKind::StorageDead(..) => "StorageDead",
+        StatementKind::Retag(..) => "Retag",
+        StatementKind::AscribeUserType(..) => "AscribeUserType",
+        StatementKind::Coverage(..) => "Coverage",
+        StatementKind::CopyNonOverlapping(..) => "CopyNonOverlapping",
+        StatementKind::Nop => "Nop",
+    }
+}
+
+fn terminator_kind_name(kind: &TerminatorKind<'_>) -> &'static str {
+    match kind {
+        TerminatorKind::Goto { .. } => "Goto",
+        TerminatorKind::SwitchInt { .. } => "SwitchInt",
+        TerminatorKind::Resume => "Resume",
+        TerminatorKind::Abort => "Abort",
+        TerminatorKind::Return => "Return",
+        TerminatorKind::Unreachable => "Unreachable",
+        TerminatorKind::Drop { .. } => "Drop",
+        TerminatorKind::DropAndReplace { .. } => "DropAndReplace",
+        TerminatorKind::Call { .. } => "Call",
+        TerminatorKind::Assert { .. } => "Assert",
+        TerminatorKind::Yield { .. } => "Yield",
+        TerminatorKind::GeneratorDrop => "GeneratorDrop",
+        TerminatorKind::FalseEdge { .. } => "FalseEdge",
+        TerminatorKind::FalseUnwind { .. } => "FalseUnwind",
+        TerminatorKind::InlineAsm { .. } => "InlineAsm",
+    }
+}
@@ -1703,6 +1703,36 @@
         separate_provide_extern
     }
 
+    /// The crates that a crate depends on directly, through an `extern crate` item or an
+    /// `--extern` path, for the `unused_pub_items` lint. The injected standard library is not
+    /// included.
+    query crate_dependencies(_: CrateNum) -> &'tcx [CrateNum] {
+        desc { "collecting the direct dependencies of a crate" }
+        separate_provide_extern
+    }
+
+    /// The crates passed to a crate with `--extern` that it never references, and so never loads,
+    /// for the `unused_pub_items` lint.
+    query unloaded_crate_dependencies(_: CrateNum) -> &'tcx [Symbol] {
+        desc { "collecting the `--extern` dependencies that a crate never loads" }
+        separate_provide_extern
+    }
+
+    /// The items of other crates that a crate uses, by path or through a method call, for the
+    /// `unused_pub_items` lint. The variants, constructors and associated items are reported as
+    /// their enum, struct, trait or self type.
+    query used_foreign_items(_: CrateNum) -> &'tcx [DefId] {
+        desc { "collecting the items of other crates used by a crate" }
+        separate_provide_extern
+    }
+
+    /// Whether the public items of a crate are checked by the `unused_pub_items` lint, i.e.
+    /// whether the crate was compiled with the lint enabled.
+    query checks_unused_pub_items(_: CrateNum) -> bool {
+        desc { "checking if the crate checks its unused public items" }
+        separate_provide_extern
+    }
+
     /// The list of symbols exported from the given crate.
     ///
     /// - All names contained in `exported_symbols(cnum)` are guaranteed to
@@ -2747,7 +2747,9 @@ pub fn struct_span_lint_hir(
         decorate: impl for<'a> FnOnce(LintDiagnosticBuilder<'a, ()>),
     ) {
         let (level, src) = self.lint_level_at_node(lint, hir_id);
-        struct_lint_level(self.sess, lint, level, src, Some(span.into()), decorate);
+        struct_lint_level(self.sess, lint, level, src, Some(span.into()), |lint| {
+            decorate(self.with_lint_item_path(lint, hir_id))
+        });
     }
 
     pub fn struct_lint_node(
@@ -2757,7 +2759,23 @@ pub fn struct_lint_node(
         decorate: impl for<'a> FnOnce(LintDiagnosticBuilder<'a, ()>),
     ) {
         let (level, src) = self.lint_level_at_node(lint, id);
-        struct_lint_level(self.sess, lint, level, src, None, decorate);
+        struct_lint_level(self.sess, lint, level, src, None, |lint| {
+            decorate(self.with_lint_item_path(lint, id))
+        });
+    }
+
+    /// Records the item of `hir_id` in the lint, for `--diagnostic-baseline`. This is done even
+    /// without a baseline, as the lint can be replayed from the incremental cache into a
+    /// compilation that has one.
+    fn with_lint_item_path<'a>(
+        self,
+        mut lint: LintDiagnosticBuilder<'a, ()>,
+        hir_id: HirId,
+    ) -> LintDiagnosticBuilder<'a, ()> {
+        let item_path =
+            ty::print::with_no_trimmed_paths!(self.def_path_str(hir_id.owner.to_def_id()));
+        lint.set_item_path(item_path);
+        lint
     }
 
     pub fn in_scope_traits(self, id: HirId) -> Option<&'tcx [TraitCandidate]> {
@@ -126,6 +126,7 @@ pub fn run_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>, passes: &[&dyn
             dump_mir(tcx, body, start_phase, &name, cnt, false);
         }
 
+        tcx.sess.inject_fault(|| name.to_string());
         pass.run_pass(tcx, body);
 
         if mutate_condition!(dump_enabled, 278) {
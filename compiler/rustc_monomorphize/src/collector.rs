@@ -181,6 +181,7 @@
 
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync::{par_iter, MTLock, MTRef, ParallelIterator};
+use rustc_errors::Diagnostic;
 use rustc_hir as hir;
 use rustc_hir::def::DefKind;
 use rustc_hir::def_id::{DefId, DefIdMap, LocalDefId};
@@ -307,6 +308,7 @@ pub fn collect_crate_mono_items(
                     dummy_spanned(root),
                     visited,
                     &mut recursion_depths,
+                    &mut Vec::new(),
                     recursion_limit,
                     inlining_map,
                 );
@@ -354,11 +356,15 @@ fn collect_roots(tcx: TyCtxt<'_>, mode: MonoItemCollectionMode) -> Vec<MonoItem<
 
 /// Collect all monomorphized items reachable from `starting_point`, and emit a note diagnostic if a
 /// post-monorphization error is encountered during a collection step.
+///
+/// `instantiation_path` is the chain of functions, with the spans of their uses, whose collection
+/// led to `starting_point`. It is reported when the recursion or type-length limit is reached.
 fn collect_items_rec<'tcx>(
     tcx: TyCtxt<'tcx>,
     starting_point: Spanned<MonoItem<'tcx>>,
     visited: MTRef<'_, MTLock<FxHashSet<MonoItem<'tcx>>>>,
     recursion_depths: &mut DefIdMap<usize>,
+    instantiation_path: &mut Vec<Spanned<Instance<'tcx>>>,
     recursion_limit: Limit,
     inlining_map: MTRef<'_, MTLock<InliningMap<'tcx>>>,
 ) {
@@ -425,10 +431,13 @@ fn collect_items_rec<'tcx>(
                 instance,
                 starting_point.span,
                 recursion_depths,
+                instantiation_path,
                 recursion_limit,
             ));
-            check_type_length_limit(tcx, instance);
+            check_type_length_limit(tcx, instance, starting_point.span, instantiation_path);
 
+            let _budget_guard =
+                tcx.sess.time_budget_activity("monomorphization", Some(instance.def_id()));
             rustc_data_structures::stack::ensure_sufficient_stack(|| {
                 collect_neighbours(tcx, instance, &mut neighbors);
             });
@@ -486,8 +495,22 @@ fn collect_items_rec<'tcx>(
 
     record_accesses(tcx, starting_point.node, neighbors.iter().map(|i| &i.node), inlining_map);
 
+    if let MonoItem::Fn(instance) = starting_point.node {
+        instantiation_path.push(respan(starting_point.span, instance));
+    }
     for neighbour in neighbors {
-        collect_items_rec(tcx, neighbour, visited, recursion_depths, recursion_limit, inlining_map);
+        collect_items_rec(
+            tcx,
+            neighbour,
+            visited,
+            recursion_depths,
+            instantiation_path,
+            recursion_limit,
+            inlining_map,
+        );
+    }
+    if let MonoItem::Fn(_) = starting_point.node {
+        instantiation_path.pop();
     }
 
     if let Some((def_id, depth)) = recursion_depth_reset {
@@ -529,24 +552,32 @@ fn shrunk_instance_name<'tcx>(
 ) -> (String, Option<PathBuf>) {
     let s = instance.to_string();
 
+    if let Some(shrunk) = shrink(&s, before, after) {
+        let path = tcx.output_filenames(()).temp_path_ext("long-type.txt", None);
+        let written_to_path = std::fs::write(&path, s).ok().map(|_| path);
+
+        (shrunk, written_to_path)
+    } else {
+        (s, None)
+    }
+}
+
+/// Returns `s` with its middle elided, keeping `before` and `after` characters, if that is really
+/// shorter.
+fn shrink(s: &str, before: usize, after: usize) -> Option<String> {
     // Only use the shrunk version if it's really shorter.
     // This also avoids the case where before and after slices overlap.
     if s.chars().nth(before + after + 1).is_some() {
         // An iterator of all byte positions including the end of the string.
         let positions = || s.char_indices().map(|(i, _)| i).chain(iter::once(s.len()));
 
-        let shrunk = format!(
+        Some(format!(
             "{before}...{after}",
             before = &s[..positions().nth(before).unwrap_or(s.len())],
             after = &s[positions().rev().nth(after).unwrap_or(0)..],
-        );
-
-        let path = tcx.output_filenames(()).temp_path_ext("long-type.txt", None);
-        let written_to_path = std::fs::write(&path, s).ok().map(|_| path);
-
-        (shrunk, written_to_path)
+        ))
     } else {
-        (s, None)
+        None
     }
 }
 
@@ -555,6 +586,7 @@ fn check_recursion_limit<'tcx>(
     instance: Instance<'tcx>,
     span: Span,
     recursion_depths: &mut DefIdMap<usize>,
+    instantiation_path: &[Spanned<Instance<'tcx>>],
     recursion_limit: Limit,
 ) -> (DefId, usize) {
     let def_id = instance.def_id();
@@ -583,6 +615,7 @@ fn check_recursion_limit<'tcx>(
         if let Some(path) = written_to_path {
             err.note(&format!("the full type name has been written to '{}'", path.display()));
         }
+        note_instantiation_path(tcx, &mut err, instantiation_path, respan(span, instance));
         err.emit()
     }
 
@@ -591,8 +624,8 @@ fn check_recursion_limit<'tcx>(
     (def_id, recursion_depth)
 }
 
-fn check_type_length_limit<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) {
-    let type_length = instance
+fn type_length(instance: Instance<'_>) -> usize {
+    instance
         .substs
         .iter()
         .flat_map(|arg| arg.walk())
@@ -600,7 +633,16 @@ fn check_type_length_limit<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) {
             GenericArgKind::Type(_) | GenericArgKind::Const(_) => true,
             GenericArgKind::Lifetime(_) => false,
         })
-        .count();
+        .count()
+}
+
+fn check_type_length_limit<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+    span: Span,
+    instantiation_path: &[Spanned<Instance<'tcx>>],
+) {
+    let type_length = type_length(instance);
     debug!(" => type length={}", type_length);
 
     // Rust code can easily create exponentially-long types using only a
@@ -616,6 +658,7 @@ fn check_type_length_limit<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) {
         if let Some(path) = written_to_path {
             diag.note(&format!("the full type name has been written to '{}'", path.display()));
         }
+        note_instantiation_path(tcx, &mut diag, instantiation_path, respan(span, instance));
         diag.help(&format!(
             "consider adding a `#![type_length_limit=\"{}\"]` attribute to your crate",
             type_length
@@ -624,6 +667,139 @@ fn check_type_length_limit<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) {
     }
 }
 
+/// Notes the chain of instantiations that led to `instance`, for the errors of the recursion and
+/// type-length limits.
+///
+/// The part of the chain that repeats, from one instantiation of the item of `instance` (see
+/// `cycle_item`) to the next, is collapsed: only its first repetition, with the smallest types, is
+/// shown, along with the use whose types grow the most, which is where boxing or a trait object
+/// would break the cycle.
+fn note_instantiation_path<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    diag: &mut Diagnostic,
+    instantiation_path: &[Spanned<Instance<'tcx>>],
+    instance: Spanned<Instance<'tcx>>,
+) {
+    let chain: Vec<_> = instantiation_path.iter().copied().chain(iter::once(instance)).collect();
+    let items: Vec<_> = chain.iter().map(|i| cycle_item(i.node)).collect();
+    let last = chain.len() - 1;
+    let fn_path = |i: usize| format!("`{}`", tcx.def_path_str(chain[i].node.def_id()));
+    let short = |s: String| shrink(&s, 32, 32).unwrap_or(s);
+    let fn_name = |i: usize| short(chain[i].node.to_string());
+
+    let Some(start) = items[..last].iter().rposition(|&item| item == items[last]) else {
+        if last > 0 {
+            let path: Vec<_> = (0..=last).map(fn_path).collect();
+            diag.note(&format!("instantiated through {}", path.join(" -> ")));
+        }
+        return;
+    };
+    let len = last - start;
+    let mut first = start;
+    while first >= len && items[first - len..first] == items[start..last] {
+        first -= len;
+    }
+
+    if first > 0 {
+        let path: Vec<_> = (0..=first).map(fn_path).collect();
+        diag.note(&format!("the cycle is reached through {}", path.join(" -> ")));
+    }
+    diag.note(&format!(
+        "the instantiations grow through the following cycle of uses, repeated {} times",
+        (last - first) / len
+    ));
+    let edges: Vec<_> = (first..first + len)
+        .map(|k| {
+            let (caller, callee) = (chain[k].node, chain[k + 1].node);
+            let span = chain[k + 1].span;
+            let span = match callee.def {
+                // The uses in drop glue are all in `drop_in_place`, so point at the definitions
+                // that make the dropped type drop the next one instead.
+                ty::InstanceDef::DropGlue(_, Some(dropped))
+                    if span.is_dummy() || matches!(caller.def, ty::InstanceDef::DropGlue(..)) =>
+                {
+                    drop_glue_span(tcx, caller, dropped)
+                }
+                _ if span.is_dummy() => tcx.def_span(callee.def_id()),
+                _ => span,
+            };
+            (k, span, type_length(callee).saturating_sub(type_length(caller)))
+        })
+        .collect();
+    // The use growing the types the most is the one to break the cycle at.
+    let growth = edges
+        .iter()
+        .rev()
+        .filter(|&&(_, _, grown)| grown > 0)
+        .max_by_key(|&&(_, _, grown)| grown)
+        .map(|&(k, _, _)| k);
+    for (k, span, _) in edges {
+        let uses = match (chain[k].node.def, chain[k + 1].node.def) {
+            (
+                ty::InstanceDef::DropGlue(_, Some(ty)),
+                ty::InstanceDef::DropGlue(_, Some(dropped)),
+            ) => format!(
+                "dropping `{}` drops `{}` here",
+                short(ty.to_string()),
+                short(dropped.to_string())
+            ),
+            _ => format!("`{}` uses `{}` here", fn_name(k), fn_name(k + 1)),
+        };
+        if growth != Some(k) {
+            diag.span_note(span, &uses);
+            continue;
+        }
+        // Boxing does not help a type that contains itself with larger generic arguments: its
+        // drop glue grows the types whatever the indirection.
+        if let ty::InstanceDef::DropGlue(..) = chain[k + 1].node.def {
+            diag.span_note(span, &format!("{}, growing the types the most", uses));
+            continue;
+        }
+        let is_future = chain[k + 1].node.substs.types().any(|ty| {
+            ty.walk()
+                .any(|arg| matches!(arg.unpack(), GenericArgKind::Type(ty) if ty.is_generator()))
+        });
+        let advice = if is_future {
+            "consider boxing the future with `Box::pin` to break the cycle"
+        } else {
+            "consider boxing the value or using a trait object (`dyn Trait`) to break the cycle"
+        };
+        diag.span_help(span, &format!("{}, growing the types the most; {}", uses, advice));
+    }
+}
+
+/// The item whose instantiations are looked for in the cycles of `note_instantiation_path`: the
+/// dropped ADT for drop glue, as all the drop glues are instantiations of `drop_in_place`.
+fn cycle_item(instance: Instance<'_>) -> DefId {
+    match instance.def {
+        ty::InstanceDef::DropGlue(_, Some(ty)) => match *ty.kind() {
+            ty::Adt(adt, _) => adt.did(),
+            _ => instance.def_id(),
+        },
+        _ => instance.def_id(),
+    }
+}
+
+/// The definition that makes the drop glue of `caller` drop `dropped`: the field of the ADT
+/// dropped by `caller` of that type, or else the definition of the type `dropped` itself.
+fn drop_glue_span<'tcx>(tcx: TyCtxt<'tcx>, caller: Instance<'tcx>, dropped: Ty<'tcx>) -> Span {
+    if let ty::InstanceDef::DropGlue(_, Some(ty)) = caller.def {
+        if let ty::Adt(adt, substs) = *ty.kind() {
+            let field = adt.all_fields().find(|field| {
+                tcx.normalize_erasing_regions(ty::ParamEnv::reveal_all(), field.ty(tcx, substs))
+                    == dropped
+            });
+            if let Some(field) = field {
+                return tcx.def_span(field.did);
+            }
+        }
+    }
+    match *dropped.kind() {
+        ty::Adt(adt, _) => tcx.def_span(adt.did()),
+        _ => tcx.def_span(caller.def_id()),
+    }
+}
+
 struct MirNeighborCollector<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
     body: &'a mir::Body<'tcx>,
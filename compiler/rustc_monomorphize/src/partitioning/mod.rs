@@ -94,6 +94,7 @@
 
 mod default;
 mod merging;
+mod report;
 
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync;
@@ -404,6 +405,10 @@ fn collect_and_partition_mono_items<'tcx>(
         }
     }
 
+    if let Some(format) = tcx.sess.opts.debugging_opts.mono_items_report {
+        report::print_mono_items_report(tcx, format, &items, &inlining_map, codegen_units);
+    }
+
     let mono_items: DefIdSet = items
         .iter()
         .filter_map(|mono_item| match *mono_item {
@@ -0,0 +1,145 @@
+//! The report of `-Z mono-items-report`.
+//!
+//! A generic definition is instantiated once for each list of generic arguments it is used with,
+//! and the instantiations that are inlining candidates are copied into every codegen unit that
+//! uses them. The report groups the instantiations collected for the crate by generic definition,
+//! with the estimated size of each, i.e. the size of its MIR times its number of copies, and the
+//! crates whose code requested it, i.e. the crates of the items that use it according to the
+//! `InliningMap`. Definitions are sorted by their total estimated size.
+
+use std::collections::BTreeMap;
+
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir::def_id::{CrateNum, DefId, LOCAL_CRATE};
+use rustc_middle::mir::mono::{CodegenUnit, MonoItem};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::TyCtxt;
+use rustc_serialize::json::as_pretty_json;
+use rustc_session::config::MonoItemsReportFormat;
+use rustc_span::symbol::Symbol;
+
+use crate::collector::InliningMap;
+
+#[derive(Encodable)]
+struct Definition {
+    name: String,
+    defined_in: String,
+    total_size_estimate: usize,
+    /// The number of instantiations requested by each crate.
+    requested_by: BTreeMap<String, usize>,
+    instantiations: Vec<Instantiation>,
+}
+
+#[derive(Encodable)]
+struct Instantiation {
+    name: String,
+    /// The estimated size of one copy.
+    size_estimate: usize,
+    copies: usize,
+    requested_by: Vec<String>,
+}
+
+fn item_crate(item: MonoItem<'_>) -> CrateNum {
+    match item {
+        MonoItem::Fn(instance) => instance.def_id().krate,
+        MonoItem::Static(def_id) => def_id.krate,
+        MonoItem::GlobalAsm(_) => LOCAL_CRATE,
+    }
+}
+
+pub(super) fn print_mono_items_report<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    format: MonoItemsReportFormat,
+    items: &FxHashSet<MonoItem<'tcx>>,
+    inlining_map: &InliningMap<'tcx>,
+    codegen_units: &[CodegenUnit<'tcx>],
+) {
+    let mut copies: FxHashMap<MonoItem<'tcx>, usize> = Default::default();
+    for cgu in codegen_units {
+        for &item in cgu.items().keys() {
+            *copies.entry(item).or_default() += 1;
+        }
+    }
+
+    let mut users: FxHashMap<MonoItem<'tcx>, FxHashSet<Symbol>> = Default::default();
+    inlining_map.iter_accesses(|user, used| {
+        let krate = tcx.crate_name(item_crate(user));
+        for &item in used {
+            users.entry(item).or_default().insert(krate);
+        }
+    });
+
+    let mut definitions: FxHashMap<DefId, Definition> = Default::default();
+    for &item in items {
+        let MonoItem::Fn(instance) = item else { continue };
+        if !item.is_generic_fn() {
+            continue;
+        }
+        let def_id = instance.def_id();
+        let definition = definitions.entry(def_id).or_insert_with(|| Definition {
+            name: with_no_trimmed_paths!(tcx.def_path_str(def_id)),
+            defined_in: tcx.crate_name(def_id.krate).to_string(),
+            total_size_estimate: 0,
+            requested_by: BTreeMap::new(),
+            instantiations: vec![],
+        });
+
+        // Items without users are roots, which the local crate requests.
+        let mut requested_by: Vec<String> = match users.get(&item) {
+            Some(crates) => crates.iter().map(|krate| krate.to_string()).collect(),
+            None => vec![tcx.crate_name(LOCAL_CRATE).to_string()],
+        };
+        requested_by.sort();
+        for krate in &requested_by {
+            *definition.requested_by.entry(krate.clone()).or_default() += 1;
+        }
+
+        let size_estimate = item.size_estimate(tcx);
+        let copies = copies.get(&item).copied().unwrap_or(0);
+        definition.total_size_estimate += size_estimate * copies;
+        definition.instantiations.push(Instantiation {
+            name: with_no_trimmed_paths!(item.to_string()),
+            size_estimate,
+            copies,
+            requested_by,
+        });
+    }
+
+    let mut definitions: Vec<Definition> = definitions.into_values().collect();
+    for definition in &mut definitions {
+        definition.instantiations.sort_by(|a, b| {
+            (b.size_estimate * b.copies)
+                .cmp(&(a.size_estimate * a.copies))
+                .then(a.name.cmp(&b.name))
+        });
+    }
+    definitions.sort_by(|a, b| {
+        b.total_size_estimate.cmp(&a.total_size_estimate).then(a.name.cmp(&b.name))
+    });
+
+    match format {
+        MonoItemsReportFormat::Json => println!("{}", as_pretty_json(&definitions)),
+        MonoItemsReportFormat::Table => print_table(&definitions),
+    }
+}
+
+fn print_table(definitions: &[Definition]) {
+    println!("{:>14}  {:>14}  {}", "Instantiations", "Estimated size", "Definition");
+    for definition in definitions {
+        let mut requested_by: Vec<_> = definition.requested_by.iter().collect();
+        requested_by.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        let requested_by = requested_by
+            .iter()
+            .map(|(krate, count)| format!("`{}` ({})", krate, count))
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!(
+            "{:>14}  {:>14}  {} (defined in `{}`, requested by {})",
+            definition.instantiations.len(),
+            definition.total_size_estimate,
+            definition.name,
+            definition.defined_in,
+            requested_by
+        );
+    }
+}
@@ -41,6 +41,18 @@ pub struct UnmatchedBrace {
         .into_token_trees()
 }
 
+/// Like `parse_token_trees`, but skips the closing delimiters without opening delimiter after
+/// reporting them, instead of failing, and returns them with the unmatched delimiters.
+crate fn parse_token_trees_resilient<'a>(
+    sess: &'a ParseSess,
+    src: &'a str,
+    start_pos: BytePos,
+) -> (TokenStream, Vec<UnmatchedBrace>, Vec<(Delimiter, Span)>) {
+    let override_span = None;
+    StringReader { sess, start_pos, pos: start_pos, end_src_index: src.len(), src, override_span }
+        .into_token_trees_resilient()
+}
+
 struct StringReader<'a> {
     sess: &'a ParseSess,
     /// Initial position, read-only.
@@ -13,19 +13,22 @@
 
 impl<'a> StringReader<'a> {
     pub(super) fn into_token_trees(self) -> (PResult<'a, TokenStream>, Vec<UnmatchedBrace>) {
-        let mut tt_reader = TokenTreesReader {
-            string_reader: self,
-            token: Token::dummy(),
-            open_braces: Vec::new(),
-            unmatched_braces: Vec::new(),
-            matching_delim_spans: Vec::new(),
-            last_unclosed_found_span: None,
-            last_delim_empty_block_spans: FxHashMap::default(),
-            matching_block_spans: Vec::new(),
-        };
+        let mut tt_reader = TokenTreesReader::new(self, None);
         let res = tt_reader.parse_all_token_trees();
         (res, tt_reader.unmatched_braces)
     }
+
+    /// Like `into_token_trees`, but skips the unexpected closing delimiters instead of failing,
+    /// and returns them.
+    pub(super) fn into_token_trees_resilient(
+        self,
+    ) -> (TokenStream, Vec<UnmatchedBrace>, Vec<(Delimiter, Span)>) {
+        let mut tt_reader = TokenTreesReader::new(self, Some(Vec::new()));
+        let Ok(stream) = tt_reader.parse_all_token_trees() else {
+            unreachable!("unexpected closing delimiters are skipped");
+        };
+        (stream, tt_reader.unmatched_braces, tt_reader.unexpected_close_delims.unwrap_or_default())
+    }
 }
 
 struct TokenTreesReader<'a> {
@@ -44,16 +47,47 @@ struct TokenTreesReader<'a> {
     /// Collect the spans of braces (Open, Close). Used only
     /// for detecting if blocks are empty and only braces.
     matching_block_spans: Vec<(Span, Span)>,
+    /// The closing delimiters without opening delimiter that were skipped, if they are skipped
+    /// rather than failing.
+    unexpected_close_delims: Option<Vec<(Delimiter, Span)>>,
 }
 
 impl<'a> TokenTreesReader<'a> {
+    fn new(
+        string_reader: StringReader<'a>,
+        unexpected_close_delims: Option<Vec<(Delimiter, Span)>>,
+    ) -> Self {
+        TokenTreesReader {
+            string_reader,
+            token: Token::dummy(),
+            open_braces: Vec::new(),
+            unmatched_braces: Vec::new(),
+            matching_delim_spans: Vec::new(),
+            last_unclosed_found_span: None,
+            last_delim_empty_block_spans: FxHashMap::default(),
+            matching_block_spans: Vec::new(),
+            unexpected_close_delims,
+        }
+    }
+
     // Parse a stream of tokens into a list of `TokenTree`s, up to an `Eof`.
     fn parse_all_token_trees(&mut self) -> PResult<'a, TokenStream> {
         let mut buf = TokenStreamBuilder::default();
 
         self.bump();
         while self.token != token::Eof {
-            buf.push(self.parse_token_tree()?);
+            match self.parse_token_tree() {
+                Ok(tree) => buf.push(tree),
+                // Only unexpected closing delimiters fail at the top level.
+                Err(mut err) if let Some(skipped) = &mut self.unexpected_close_delims => {
+                    err.emit();
+                    if let token::CloseDelim(delim) = self.token.kind {
+                        skipped.push((delim, self.token.span));
+                    }
+                    self.bump();
+                }
+                Err(err) => return Err(err),
+            }
         }
 
         Ok(buf.into_token_stream())
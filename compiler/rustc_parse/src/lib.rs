@@ -35,6 +35,7 @@
 pub mod parser;
 use parser::{emit_unclosed_delims, make_unclosed_delims_error, Parser};
 pub mod lexer;
+pub mod resilient;
 pub mod validate_attr;
 
 // A bunch of utility functions of the form `parse_<thing>_from_<source>`
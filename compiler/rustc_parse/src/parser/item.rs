@@ -13,7 +13,8 @@
 use rustc_ast::{FnHeader, ForeignItem, Path, PathSegment, Visibility, VisibilityKind};
 use rustc_ast::{MacArgs, MacCall, MacDelimiter};
 use rustc_ast_pretty::pprust;
-use rustc_errors::{struct_span_err, Applicability, PResult, StashKey};
+use rustc_errors::{struct_span_err, Applicability, DiagnosticBuilder, ErrorGuaranteed};
+use rustc_errors::{PResult, StashKey};
 use rustc_span::edition::Edition;
 use rustc_span::lev_distance::lev_distance;
 use rustc_span::source_map::{self, Span};
@@ -54,29 +55,119 @@ pub fn parse_mod(
         term: &TokenKind,
     ) -> PResult<'a, (Vec<Attribute>, Vec<P<Item>>, ModSpans)> {
         let lo = self.token.span;
-        let attrs = self.parse_inner_attributes()?;
+        let attrs = match self.parse_inner_attributes() {
+            Err(mut err) if self.resilient => {
+                err.emit();
+                vec![]
+            }
+            attrs => attrs?,
+        };
 
         let post_attr_lo = self.token.span;
         let mut items = vec![];
-        while let Some(item) = self.parse_item(ForceCollect::No)? {
-            items.push(item);
-            self.maybe_consume_incorrect_semicolon(&items);
+        loop {
+            let item_lo = self.token.span;
+            let depth = self.token_cursor.stack.len();
+            match self.parse_item(ForceCollect::No) {
+                Ok(Some(item)) => {
+                    items.push(item);
+                    self.maybe_consume_incorrect_semicolon(&items);
+                }
+                Ok(None) if self.resilient && self.token != *term && self.token != token::Eof => {
+                    if !self.maybe_consume_incorrect_semicolon(&items) {
+                        self.expected_item_err().emit();
+                        items.push(self.recover_err_item(item_lo, depth, term));
+                    }
+                }
+                Ok(None) => break,
+                Err(mut err) if self.resilient => {
+                    err.emit();
+                    items.push(self.recover_err_item(item_lo, depth, term));
+                }
+                Err(err) => return Err(err),
+            }
         }
 
-        if !self.eat(term) {
-            let token_str = super::token_descr(&self.token);
-            if !self.maybe_consume_incorrect_semicolon(&items) {
-                let msg = &format!("expected item, found {token_str}");
-                let mut err = self.struct_span_err(self.token.span, msg);
-                err.span_label(self.token.span, "expected item");
+        if !self.eat(term) && !self.maybe_consume_incorrect_semicolon(&items) {
+            let err = self.expected_item_err();
+            if !self.resilient {
                 return Err(err);
             }
+            err.emit();
         }
 
         let inject_use_span = post_attr_lo.data().with_hi(post_attr_lo.lo());
         let mod_spans = ModSpans { inner_span: lo.to(self.prev_token.span), inject_use_span };
         Ok((attrs, items, mod_spans))
     }
+
+    fn expected_item_err(&self) -> DiagnosticBuilder<'a, ErrorGuaranteed> {
+        let token_str = super::token_descr(&self.token);
+        let msg = &format!("expected item, found {token_str}");
+        let mut err = self.struct_span_err(self.token.span, msg);
+        err.span_label(self.token.span, "expected item");
+        err
+    }
+
+    /// Skips the tokens of an item that failed to parse in resilient mode, up to the start of the
+    /// next item or the end of the module, and returns an `ItemKind::Err` item spanning them.
+    ///
+    /// `depth` is the depth of the token cursor in the module, since the parser may have stopped
+    /// within a delimited group of the item.
+    fn recover_err_item(&mut self, lo: Span, depth: usize, term: &TokenKind) -> P<Item> {
+        let mut skipped = false;
+        loop {
+            let at_mod_level = self.token_cursor.stack.len() <= depth;
+            if self.token == token::Eof
+                || at_mod_level && (self.token == *term || skipped && self.is_item_start())
+            {
+                break;
+            }
+            if let token::OpenDelim(..) = self.token.kind {
+                self.parse_token_tree();
+            } else {
+                self.bump();
+            }
+            skipped = true;
+        }
+        let span = if skipped { lo.to(self.prev_token.span) } else { lo.shrink_to_lo() };
+        let vis =
+            Visibility { span: span.shrink_to_lo(), kind: VisibilityKind::Inherited, tokens: None };
+        P(Item {
+            attrs: vec![],
+            id: DUMMY_NODE_ID,
+            span,
+            vis,
+            ident: Ident::empty(),
+            kind: ItemKind::Err,
+            tokens: None,
+        })
+    }
+
+    /// Whether the current token may start an item, for the recovery of `recover_err_item`.
+    fn is_item_start(&self) -> bool {
+        const ITEM_KEYWORDS: &[Symbol] = &[
+            kw::Async,
+            kw::Const,
+            kw::Enum,
+            kw::Extern,
+            kw::Fn,
+            kw::Impl,
+            kw::Macro,
+            kw::Mod,
+            kw::Pub,
+            kw::Static,
+            kw::Struct,
+            kw::Trait,
+            kw::Type,
+            kw::Unsafe,
+            kw::Use,
+        ];
+        self.token == token::Pound
+            || ITEM_KEYWORDS.iter().any(|&kw| self.token.is_keyword(kw))
+            || self.token.is_ident_named(sym::macro_rules)
+            || self.token.is_ident_named(sym::union)
+    }
 }
 
 pub(super) type ItemInfo = (Ident, ItemKind);
@@ -126,6 +126,8 @@ pub struct Parser<'a> {
     // `token_cursor.num_next_calls` is updated properly.
     token_cursor: TokenCursor,
     desugar_doc_comments: bool,
+    /// Whether the parser recovers from every syntax error, see [`Parser::resilient`].
+    resilient: bool,
     /// This field is used to keep track of how many left angle brackets we have seen. This is
     /// required in order to detect extra leading left angle brackets (`<` characters) and error
     /// appropriately.
@@ -443,6 +445,7 @@ pub fn new(
                 break_last_token: false,
             },
             desugar_doc_comments,
+            resilient: false,
             unmatched_angle_bracket_count: 0,
             max_angle_bracket_count: 0,
             unclosed_delims: Vec::new(),
@@ -463,6 +466,16 @@ pub fn new(
         parser
     }
 
+    /// Makes the parser recover from every syntax error in the items of modules, so that parsing
+    /// a module always succeeds. The items that cannot be parsed are replaced with
+    /// `ItemKind::Err` items spanning their tokens, after emitting their errors.
+    ///
+    /// This is meant for tools working on incomplete code, see [`crate::resilient`].
+    pub fn resilient(mut self) -> Self {
+        self.resilient = true;
+        self
+    }
+
     pub fn unexpected<T>(&mut self) -> PResult<'a, T> {
         match self.expect_one_of(&[], &[]) {
             Err(e) => Err(e),
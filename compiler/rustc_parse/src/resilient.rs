@@ -0,0 +1,151 @@
+//! Resilient parsing, for the tools working on incomplete code.
+//!
+//! The parser gives up on the first syntax error it cannot recover from, and the AST drops the
+//! whitespace and comments of the source. The functions of this module instead always return a
+//! crate, parsed in the resilient mode of the parser (see [`crate::parser::Parser::resilient`]):
+//!
+//! - the items that fail to parse are replaced with `ItemKind::Err` items spanning their tokens,
+//! - the statements that fail to parse are replaced with `ExprKind::Err` expressions,
+//! - the unclosed delimiters are closed implicitly, and the closing delimiters without opening
+//!   delimiter are skipped; both are listed in [`ResilientParse::delim_errors`].
+//!
+//! The syntax errors are still reported to the `ParseSess`.
+//!
+//! Alongside the crate, [`ResilientParse::pieces`] is a lossless side table splitting the whole
+//! source file into tokens and trivia, so that the tools can map the nodes back to the exact
+//! source text, e.g. to keep the comments of a node they rewrite.
+
+use crate::lexer;
+use crate::{file_to_source_file, stream_to_parser};
+
+use rustc_ast as ast;
+use rustc_ast::token::{self, Delimiter};
+use rustc_data_structures::sync::Lrc;
+use rustc_session::parse::ParseSess;
+use rustc_span::{BytePos, FileName, Pos, SourceFile, Span};
+
+use std::path::Path;
+
+/// The kind of a [`Piece`] of source text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PieceKind {
+    /// A token, including doc comments and the text that doesn't form a valid token.
+    ///
+    /// The tokens are the ones of `rustc_lexer`, so multi-character operators like `::` are
+    /// split into several pieces.
+    Token,
+    Whitespace,
+    /// A line or block comment, other than a doc comment.
+    Comment,
+    /// The `#!` line starting a script.
+    Shebang,
+}
+
+/// A piece of the source text of a file, see [`ResilientParse::pieces`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Piece {
+    pub kind: PieceKind,
+    pub span: Span,
+}
+
+/// A delimiter error the parser recovered from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DelimError {
+    /// The delimiter opened at `open` was closed implicitly at `closed_at`, where the parser found
+    /// another closing delimiter or the end of the file.
+    Unclosed { delim: Delimiter, open: Option<Span>, closed_at: Span },
+    /// The closing delimiter at `span` has no opening delimiter, and was skipped.
+    Unopened { delim: Delimiter, span: Span },
+}
+
+/// The result of parsing a file in resilient mode.
+pub struct ResilientParse {
+    pub krate: ast::Crate,
+    pub source_file: Lrc<SourceFile>,
+    /// The pieces of the source file, in order. They cover the whole file: concatenating their
+    /// text gives back the source.
+    pub pieces: Vec<Piece>,
+    pub delim_errors: Vec<DelimError>,
+}
+
+impl ResilientParse {
+    /// Returns the source text of `span`, which must be within the parsed file.
+    pub fn text(&self, span: Span) -> &str {
+        let src = self.source_file.src.as_deref().expect("parsed files have a source");
+        let lo = (span.lo() - self.source_file.start_pos).to_usize();
+        let hi = (span.hi() - self.source_file.start_pos).to_usize();
+        &src[lo..hi]
+    }
+}
+
+pub fn parse_file_resilient(sess: &ParseSess, path: &Path) -> ResilientParse {
+    parse_source_file_resilient(sess, file_to_source_file(sess, path, None))
+}
+
+pub fn parse_source_str_resilient(
+    sess: &ParseSess,
+    name: FileName,
+    source: String,
+) -> ResilientParse {
+    parse_source_file_resilient(sess, sess.source_map().new_source_file(name, source))
+}
+
+pub fn parse_source_file_resilient(
+    sess: &ParseSess,
+    source_file: Lrc<SourceFile>,
+) -> ResilientParse {
+    let src = source_file.src.as_ref().unwrap_or_else(|| {
+        sess.span_diagnostic.bug(&format!(
+            "cannot lex `source_file` without source: {}",
+            sess.source_map().filename_for_diagnostics(&source_file.name)
+        ));
+    });
+
+    let (stream, unmatched_braces, unopened) =
+        lexer::parse_token_trees_resilient(sess, src.as_str(), source_file.start_pos);
+    let unclosed = unmatched_braces.iter().map(|unmatched| DelimError::Unclosed {
+        delim: unmatched.expected_delim,
+        open: unmatched.unclosed_span,
+        closed_at: unmatched.found_span,
+    });
+    let unopened = unopened.into_iter().map(|(delim, span)| DelimError::Unopened { delim, span });
+    let delim_errors = unclosed.chain(unopened).collect();
+
+    let mut parser = stream_to_parser(sess, stream, None).resilient();
+    parser.unclosed_delims = unmatched_braces;
+    if parser.token == token::Eof {
+        let end_pos = source_file.end_pos;
+        parser.token.span = Span::new(end_pos, end_pos, parser.token.span.ctxt(), None);
+    }
+    let Ok(krate) = parser.parse_crate_mod() else {
+        unreachable!("the resilient parser recovers from every error in modules");
+    };
+
+    let pieces = source_pieces(src, source_file.start_pos);
+    ResilientParse { krate, source_file, pieces, delim_errors }
+}
+
+fn source_pieces(src: &str, start_pos: BytePos) -> Vec<Piece> {
+    let shebang_len = rustc_lexer::strip_shebang(src);
+    let shebang = shebang_len.map(|len| (PieceKind::Shebang, len));
+    let tokens = rustc_lexer::tokenize(&src[shebang_len.unwrap_or(0)..]).map(|token| {
+        let kind = match token.kind {
+            rustc_lexer::TokenKind::Whitespace => PieceKind::Whitespace,
+            rustc_lexer::TokenKind::LineComment { doc_style: None }
+            | rustc_lexer::TokenKind::BlockComment { doc_style: None, .. } => PieceKind::Comment,
+            _ => PieceKind::Token,
+        };
+        (kind, token.len)
+    });
+
+    let mut pos = start_pos;
+    shebang
+        .into_iter()
+        .chain(tokens)
+        .map(|(kind, len)| {
+            let lo = pos;
+            pos = pos + BytePos::from_usize(len);
+            Piece { kind, span: Span::with_root_ctxt(lo, pos) }
+        })
+        .collect()
+}
@@ -41,6 +41,7 @@
 mod reachable;
 mod region;
 pub mod stability;
+pub mod unused_pub;
 mod upvars;
 mod weak_lang_items;
 
@@ -60,5 +61,6 @@ pub fn provide(providers: &mut Providers) {
     reachable::provide(providers);
     region::provide(providers);
     stability::provide(providers);
+    unused_pub::provide(providers);
     upvars::provide(providers);
 }
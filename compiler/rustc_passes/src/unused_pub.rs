@@ -0,0 +1,259 @@
+//! Detection of the public items that no crate of the workspace uses, for the `unused_pub_items`
+//! lint.
+//!
+//! Each crate records in its metadata whether it was compiled with the lint enabled, which is the
+//! case of the crates of the workspace since Cargo caps the lints of the other dependencies, and
+//! then its direct dependencies, including the `--extern` crates it never loads, and the items of
+//! other crates that it uses. When an executable is compiled with the lint enabled, the public
+//! items of those crates are compared with the items used by the whole crate graph, and each of
+//! those crates is checked to use at least one item of each of its dependencies.
+
+use rustc_data_structures::fx::{FxHashSet, FxIndexSet};
+use rustc_hir as hir;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::{CrateNum, DefId, LOCAL_CRATE};
+use rustc_hir::intravisit::{self, Visitor};
+use rustc_hir::CRATE_HIR_ID;
+use rustc_middle::hir::nested_filter;
+use rustc_middle::lint::LintDiagnosticBuilder;
+use rustc_middle::ty::query::Providers;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_session::config::CrateType;
+use rustc_session::cstore::ExternCrateSource;
+use rustc_session::lint;
+use rustc_span::Symbol;
+
+use std::iter;
+
+struct UsedForeignItemsVisitor<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    maybe_typeck_results: Option<&'tcx ty::TypeckResults<'tcx>>,
+    used: FxHashSet<DefId>,
+}
+
+impl<'tcx> UsedForeignItemsVisitor<'tcx> {
+    fn record(&mut self, def_id: DefId) {
+        if !def_id.is_local() {
+            self.used.insert(api_item(self.tcx, def_id));
+        }
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for UsedForeignItemsVisitor<'tcx> {
+    type NestedFilter = nested_filter::OnlyBodies;
+
+    fn nested_visit_map(&mut self) -> Self::Map {
+        self.tcx.hir()
+    }
+
+    fn visit_nested_body(&mut self, body: hir::BodyId) {
+        let old_maybe_typeck_results =
+            self.maybe_typeck_results.replace(self.tcx.typeck_body(body));
+        let body = self.tcx.hir().body(body);
+        self.visit_body(body);
+        self.maybe_typeck_results = old_maybe_typeck_results;
+    }
+
+    fn visit_path(&mut self, path: &'tcx hir::Path<'tcx>, _: hir::HirId) {
+        if let Res::Def(_, def_id) = path.res {
+            self.record(def_id);
+        }
+        intravisit::walk_path(self, path);
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        if let hir::ExprKind::MethodCall(..) | hir::ExprKind::Path(hir::QPath::TypeRelative(..)) =
+            expr.kind
+        {
+            let typeck_results = self.maybe_typeck_results.expect("expression outside of a body");
+            if let Some(def_id) = typeck_results.type_dependent_def_id(expr.hir_id) {
+                self.record(def_id);
+            }
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// The item of the public API of its crate through which `def_id` is used: the variants,
+/// constructors and fields are used through their type, and the associated items through their
+/// trait or self type.
+fn api_item(tcx: TyCtxt<'_>, mut def_id: DefId) -> DefId {
+    loop {
+        match tcx.def_kind(def_id) {
+            DefKind::Ctor(..) | DefKind::Variant | DefKind::Field => def_id = tcx.parent(def_id),
+            DefKind::AssocFn | DefKind::AssocConst | DefKind::AssocTy => {
+                let parent = tcx.parent(def_id);
+                if tcx.def_kind(parent) != DefKind::Impl {
+                    return parent;
+                }
+                return tcx.type_of(parent).ty_adt_def().map_or(parent, |adt| adt.did());
+            }
+            _ => return def_id,
+        }
+    }
+}
+
+fn crate_dependencies<'tcx>(tcx: TyCtxt<'tcx>, cnum: CrateNum) -> &'tcx [CrateNum] {
+    assert_eq!(cnum, LOCAL_CRATE);
+
+    // The standard library injected by an `extern crate` item from an expansion is left out, as
+    // are the crates only loaded for their macros, none of whose uses are counted.
+    tcx.arena.alloc_from_iter(tcx.crates(()).iter().copied().filter(|&dep| {
+        tcx.extern_crate(dep.as_def_id()).map_or(false, |extern_crate| {
+            extern_crate.is_direct() && !extern_crate.span.from_expansion()
+        }) && !tcx.dep_kind(dep).macros_only()
+    }))
+}
+
+fn used_foreign_items<'tcx>(tcx: TyCtxt<'tcx>, cnum: CrateNum) -> &'tcx [DefId] {
+    assert_eq!(cnum, LOCAL_CRATE);
+
+    let mut visitor =
+        UsedForeignItemsVisitor { tcx, maybe_typeck_results: None, used: FxHashSet::default() };
+    tcx.hir().visit_all_item_likes(&mut visitor.as_deep_visitor());
+    let mut used: Vec<_> = visitor.used.into_iter().collect();
+    // Bring everything into deterministic order.
+    used.sort_by_cached_key(|&def_id| tcx.def_path_hash(def_id));
+    tcx.arena.alloc_slice(&used)
+}
+
+fn checks_unused_pub_items(tcx: TyCtxt<'_>, cnum: CrateNum) -> bool {
+    assert_eq!(cnum, LOCAL_CRATE);
+    tcx.lint_level_at_node(lint::builtin::UNUSED_PUB_ITEMS, CRATE_HIR_ID).0 != lint::Allow
+}
+
+/// The public items of `cnum` checked by the lint: the items reachable through its public
+/// modules, other than the modules themselves and the macros.
+fn pub_items(tcx: TyCtxt<'_>, cnum: CrateNum) -> FxIndexSet<DefId> {
+    let mut items = FxIndexSet::default();
+    let mut modules = vec![cnum.as_def_id()];
+    let mut seen_modules = FxHashSet::default();
+    while let Some(module) = modules.pop() {
+        for child in tcx.module_children(module) {
+            let Res::Def(kind, def_id) = child.res else { continue };
+            if !child.vis.is_public() || def_id.krate != cnum {
+                continue;
+            }
+            match kind {
+                DefKind::Mod => {
+                    if seen_modules.insert(def_id) {
+                        modules.push(def_id);
+                    }
+                }
+                DefKind::Struct
+                | DefKind::Union
+                | DefKind::Enum
+                | DefKind::Trait
+                | DefKind::TraitAlias
+                | DefKind::TyAlias
+                | DefKind::Fn
+                | DefKind::Const
+                | DefKind::Static(_) => {
+                    items.insert(def_id);
+                }
+                _ => {}
+            }
+        }
+    }
+    items
+}
+
+/// Reports the public items of the crates of the workspace that no crate of the crate graph
+/// uses, and the crates of the workspace that depend on a crate without using any of its public
+/// items. This is only done for executables, whose crate graph includes all the users of their
+/// dependencies.
+pub fn check_unused_pub_items(tcx: TyCtxt<'_>) {
+    let is_final_artifact = tcx.sess.crate_types().iter().all(|ty| match ty {
+        CrateType::Executable | CrateType::Cdylib | CrateType::Staticlib => true,
+        CrateType::Dylib | CrateType::Rlib | CrateType::ProcMacro => false,
+    });
+    if !is_final_artifact || !tcx.checks_unused_pub_items(LOCAL_CRATE) {
+        return;
+    }
+
+    // The uses are attributed to each depending crate, so that a dependency it declares is
+    // reported even if other crates use it. The crates that are not checked record no uses.
+    let mut used = FxHashSet::default();
+    for cnum in iter::once(LOCAL_CRATE).chain(tcx.crates(()).iter().copied()) {
+        if !tcx.checks_unused_pub_items(cnum) {
+            continue;
+        }
+        let used_by_crate = tcx.used_foreign_items(cnum);
+        used.extend(used_by_crate.iter().copied());
+        for &dep in tcx.crate_dependencies(cnum) {
+            if used_by_crate.iter().any(|def_id| def_id.krate == dep) {
+                continue;
+            }
+            let extern_crate_item =
+                tcx.extern_crate(dep.as_def_id()).and_then(|extern_crate| match extern_crate.src {
+                    ExternCrateSource::Extern(def_id) if cnum == LOCAL_CRATE => Some(def_id),
+                    _ => None,
+                });
+            report_unused_dependency(tcx, cnum, tcx.crate_name(dep), extern_crate_item);
+        }
+        // The `--extern` dependencies that are never referenced are not even loaded.
+        for &dep_name in tcx.unloaded_crate_dependencies(cnum) {
+            report_unused_dependency(tcx, cnum, dep_name, None);
+        }
+    }
+
+    for &cnum in tcx.crates(()) {
+        if !tcx.checks_unused_pub_items(cnum) {
+            continue;
+        }
+        let items = pub_items(tcx, cnum);
+        let unused: Vec<_> = items.iter().filter(|def_id| !used.contains(def_id)).collect();
+        // When no public item is used at all, the crates depending on it were reported instead.
+        if unused.len() == items.len() {
+            continue;
+        }
+
+        let crate_name = tcx.crate_name(cnum);
+        for &def_id in unused {
+            let descr = tcx.def_kind(def_id).descr(def_id);
+            let span = tcx.def_span(def_id);
+            tcx.struct_span_lint_hir(lint::builtin::UNUSED_PUB_ITEMS, CRATE_HIR_ID, span, |lint| {
+                lint.build(&format!(
+                    "public {} `{}` is never used outside of the crate `{}`",
+                    descr,
+                    tcx.def_path_str(def_id),
+                    crate_name
+                ))
+                .emit();
+            });
+        }
+    }
+}
+
+/// Reports that `cnum` depends on the crate `dep_name` without using any of its public items, at
+/// the `extern crate` item declaring it if any.
+fn report_unused_dependency(
+    tcx: TyCtxt<'_>,
+    cnum: CrateNum,
+    dep_name: Symbol,
+    extern_crate_item: Option<DefId>,
+) {
+    let decorate = |lint: LintDiagnosticBuilder<'_, ()>| {
+        lint.build(&format!(
+            "the crate `{}` depends on `{}`, but uses none of its public items",
+            tcx.crate_name(cnum),
+            dep_name
+        ))
+        .help("remove the dependency")
+        .emit();
+    };
+    match extern_crate_item {
+        Some(def_id) => tcx.struct_span_lint_hir(
+            lint::builtin::UNUSED_PUB_ITEMS,
+            CRATE_HIR_ID,
+            tcx.def_span(def_id),
+            decorate,
+        ),
+        None => tcx.struct_lint_node(lint::builtin::UNUSED_PUB_ITEMS, CRATE_HIR_ID, decorate),
+    }
+}
+
+pub(crate) fn provide(providers: &mut Providers) {
+    *providers =
+        Providers { crate_dependencies, used_foreign_items, checks_unused_pub_items, ..*providers };
+}
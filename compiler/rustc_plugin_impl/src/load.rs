@@ -4,6 +4,8 @@
 use libloading::Library;
 use rustc_ast::Crate;
 use rustc_errors::struct_span_err;
+use rustc_lint::plugin::{LintPluginDeclaration, LintPluginRegistry};
+use rustc_lint::plugin::{LINT_PLUGIN_DECLARATION_SYMBOL, LINT_PLUGIN_VERSION, RUSTC_VERSION};
 use rustc_metadata::locator;
 use rustc_session::cstore::MetadataLoader;
 use rustc_session::Session;
@@ -13,7 +15,8 @@
 use std::borrow::ToOwned;
 use std::env;
 use std::mem;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::ptr;
 
 /// Pointer to a registrar function.
 type PluginRegistrarFn = fn(&mut Registry<'_>);
@@ -82,3 +85,57 @@ fn dylink_registrar(lib_path: PathBuf) -> Result<PluginRegistrarFn, libloading::
 
     Ok(*registrar_sym)
 }
+
+/// Pointer to the registration function of a lint plugin.
+pub type LintPluginRegisterFn = fn(&mut LintPluginRegistry<'_>);
+
+/// Dynamically loads the lint plugins of `-Z lint-plugin`, checking that they were built for this
+/// compiler, and returns them with their paths.
+pub fn load_lint_plugins(sess: &Session) -> Vec<(&Path, LintPluginRegisterFn)> {
+    sess.opts
+        .debugging_opts
+        .lint_plugin
+        .iter()
+        .map(|path| {
+            let register = dylink_lint_plugin(path).unwrap_or_else(|err| {
+                sess.fatal(&format!("failed to load lint plugin `{}`: {}", path.display(), err))
+            });
+            (path.as_path(), register)
+        })
+        .collect()
+}
+
+fn dylink_lint_plugin(lib_path: &Path) -> Result<LintPluginRegisterFn, String> {
+    // Make sure the path contains a / or the linker will search for it.
+    let lib_path = env::current_dir().unwrap().join(lib_path);
+
+    let lib = unsafe { Library::new(&lib_path) }.map_err(|err| err.to_string())?;
+    let declaration: *const LintPluginDeclaration = unsafe {
+        *lib.get(LINT_PLUGIN_DECLARATION_SYMBOL)
+            .map_err(|_| "it is not declared with `declare_lint_plugin!`".to_string())?
+    };
+
+    // The version is read first, as it is the only field whose position is the same in all the
+    // versions of the declaration.
+    let version = unsafe { ptr::addr_of!((*declaration).version).read() };
+    if version != LINT_PLUGIN_VERSION {
+        return Err(format!(
+            "it uses version {} of the lint plugin interface, but this compiler uses version {}",
+            version, LINT_PLUGIN_VERSION
+        ));
+    }
+    let declaration = unsafe { &*declaration };
+    if declaration.rustc_version != RUSTC_VERSION {
+        return Err(format!(
+            "it was built by rustc {}, but this compiler is rustc {}",
+            declaration.rustc_version, RUSTC_VERSION
+        ));
+    }
+    let register = declaration.register;
+
+    // Intentionally leak the dynamic library, like plugin registrars, as the lint passes it
+    // creates live as long as the lint store.
+    mem::forget(lib);
+
+    Ok(register)
+}
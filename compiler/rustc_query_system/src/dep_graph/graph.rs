@@ -94,6 +94,24 @@ struct DepGraphData<K: DepKind> {
     /// a particular query result was decoded from disk
     /// (not just marked green)
     debug_loaded_from_disk: Lock<FxHashSet<DepNode<K>>>,
+
+    /// For `-Z incremental-explain`: the dependency that was found red when trying to mark
+    /// each node of the previous session green.
+    dirtied_by: Lock<FxHashMap<SerializedDepNodeIndex, SerializedDepNodeIndex>>,
+
+    /// For `-Z incremental-explain`: the new result fingerprint of each node of the previous
+    /// session that was re-executed and turned out red.
+    red_fingerprints: Lock<FxHashMap<SerializedDepNodeIndex, Option<Fingerprint>>>,
+}
+
+/// A node of the previous session that turned out red, see [`DepGraph::explain_red_node`].
+#[derive(Debug)]
+pub struct RedNode<K: DepKind> {
+    pub node: DepNode<K>,
+    pub prev_fingerprint: Fingerprint,
+    /// The fingerprint of the new result, if the node was re-executed and its query hashes
+    /// its result.
+    pub new_fingerprint: Option<Fingerprint>,
 }
 
 pub fn hash_result<R>(hcx: &mut StableHashingContext<'_>, result: &R) -> Fingerprint
@@ -142,6 +160,8 @@ pub fn new(
                 previous: prev_graph,
                 colors: DepNodeColorMap::new(prev_graph_node_count),
                 debug_loaded_from_disk: Default::default(),
+                dirtied_by: Default::default(),
+                red_fingerprints: Default::default(),
             })),
             virtual_dep_node_index: Lrc::new(AtomicU32::new(0)),
         }
@@ -355,6 +375,10 @@ fn with_task_impl<Ctxt: HasDepContext<DepKind = K>, A: Debug, R>(
                 key
             );
 
+            if color == DepNodeColor::Red && dcx.sess().opts.debugging_opts.incremental_explain {
+                data.red_fingerprints.lock().insert(prev_index, current_fingerprint);
+            }
+
             data.colors.insert(prev_index, color);
         }
 
@@ -719,7 +743,12 @@ fn try_mark_previous_green<Ctxt: QueryContext<DepKind = K>>(
         let prev_deps = data.previous.edge_targets_from(prev_dep_node_index);
 
         for &dep_dep_node_index in prev_deps {
-            self.try_mark_parent_green(tcx, data, dep_dep_node_index, dep_node)?
+            if self.try_mark_parent_green(tcx, data, dep_dep_node_index, dep_node).is_none() {
+                if tcx.dep_context().sess().opts.debugging_opts.incremental_explain {
+                    data.dirtied_by.lock().insert(prev_dep_node_index, dep_dep_node_index);
+                }
+                return None;
+            }
         }
 
         // If we got here without hitting a `return` that means that all
@@ -790,6 +819,30 @@ fn emit_side_effects<Ctxt: QueryContext<DepKind = K>>(
         }
     }
 
+    /// Returns why `dep_node`, a node of the previous session, could not be marked green, for
+    /// `-Z incremental-explain`. Each node of the returned chain was dirtied by the next one,
+    /// the first one being the dependency of `dep_node` that was found red. The last one was
+    /// not dirtied by a dependency, e.g. because it is an `eval_always` input of the query
+    /// system whose value changed.
+    pub fn explain_red_node(&self, dep_node: &DepNode<K>) -> Vec<RedNode<K>> {
+        let Some(data) = &self.data else { return vec![] };
+        let Some(mut prev_index) = data.previous.node_to_index_opt(dep_node) else {
+            return vec![];
+        };
+        let dirtied_by = data.dirtied_by.lock();
+        let red_fingerprints = data.red_fingerprints.lock();
+        let mut chain = vec![];
+        while let Some(&dep_index) = dirtied_by.get(&prev_index) {
+            chain.push(RedNode {
+                node: data.previous.index_to_node(dep_index),
+                prev_fingerprint: data.previous.fingerprint_by_index(dep_index),
+                new_fingerprint: red_fingerprints.get(&dep_index).copied().flatten(),
+            });
+            prev_index = dep_index;
+        }
+        chain
+    }
+
     // Returns true if the given node has been marked as red during the
     // current compilation session. Used in various assertions
     pub fn is_red(&self, dep_node: &DepNode<K>) -> bool {
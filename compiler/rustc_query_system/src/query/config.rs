@@ -1,13 +1,14 @@
 //! Query configuration and description traits.
 
-use crate::dep_graph::DepNode;
-use crate::dep_graph::SerializedDepNodeIndex;
+use crate::dep_graph::{DepContext, DepNode, SerializedDepNodeIndex};
 use crate::ich::StableHashingContext;
 use crate::query::caches::QueryCache;
 use crate::query::{QueryContext, QueryState};
 
 use rustc_data_structures::fingerprint::Fingerprint;
 use rustc_errors::{DiagnosticBuilder, ErrorGuaranteed};
+use rustc_session::time_budget::TimeBudgetGuard;
+use rustc_span::def_id::DefId;
 use std::fmt::Debug;
 use std::hash::Hash;
 
@@ -20,6 +21,9 @@ pub trait QueryConfig {
 }
 
 pub struct QueryVtable<CTX: QueryContext, K, V> {
+    pub name: &'static str,
+    /// The item the key refers to, if any, which `-Z time-budget` charges the query to.
+    pub item: Option<DefId>,
     pub anon: bool,
     pub dep_kind: CTX::DepKind,
     pub eval_always: bool,
@@ -40,9 +44,24 @@ pub(crate) fn to_dep_node(&self, tcx: CTX::DepContext, key: &K) -> DepNode<CTX::
     }
 
     pub(crate) fn compute(&self, tcx: CTX::DepContext, key: K) -> V {
+        self.inject_fault(tcx);
         (self.compute)(tcx, key)
     }
 
+    /// Injects the fault requested with `-Z inject-fault` if this query is its target. Must be
+    /// called before each run of the provider.
+    pub(crate) fn inject_fault(&self, tcx: CTX::DepContext) {
+        tcx.sess().inject_fault(|| format!("{:?}", self.dep_kind));
+    }
+
+    /// Starts timing a run of the provider for `-Z time-budget`.
+    pub(crate) fn time_budget_activity<'a>(
+        &self,
+        tcx: &'a CTX::DepContext,
+    ) -> Option<TimeBudgetGuard<'a>> {
+        tcx.sess().time_budget_activity(self.name, self.item)
+    }
+
     pub(crate) fn try_load_from_disk(&self, tcx: CTX, index: SerializedDepNodeIndex) -> Option<V> {
         self.try_load_from_disk
             .expect("QueryDescription::load_from_disk() called for an unsupported query.")(
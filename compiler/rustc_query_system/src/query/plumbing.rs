@@ -381,7 +381,9 @@ fn execute_job<CTX, K, V>(
     // Fast path for when incr. comp. is off.
     if !dep_graph.is_fully_enabled() {
         let prof_timer = tcx.dep_context().profiler().query_provider();
+        let budget_guard = query.time_budget_activity(tcx.dep_context());
         let result = tcx.start_query(job_id, None, || query.compute(*tcx.dep_context(), key));
+        drop(budget_guard);
         let dep_node_index = dep_graph.next_virtual_depnode_index();
         prof_timer.finish_with_query_invocation_id(dep_node_index.into());
         return (result, dep_node_index);
@@ -402,6 +404,7 @@ fn execute_job<CTX, K, V>(
     }
 
     let prof_timer = tcx.dep_context().profiler().query_provider();
+    let budget_guard = query.time_budget_activity(tcx.dep_context());
     let diagnostics = Lock::new(ThinVec::new());
 
     let (result, dep_node_index) = tcx.start_query(job_id, Some(&diagnostics), || {
@@ -414,9 +417,12 @@ fn execute_job<CTX, K, V>(
         // `to_dep_node` is expensive for some `DepKind`s.
         let dep_node = dep_node_opt.unwrap_or_else(|| query.to_dep_node(*tcx.dep_context(), &key));
 
+        // `with_task` calls the provider directly rather than through `QueryVtable::compute`.
+        query.inject_fault(*tcx.dep_context());
         dep_graph.with_task(dep_node, *tcx.dep_context(), key, query.compute, query.hash_result)
     });
 
+    drop(budget_guard);
     prof_timer.finish_with_query_invocation_id(dep_node_index.into());
 
     let diagnostics = diagnostics.into_inner();
@@ -504,10 +510,12 @@ fn try_load_from_disk_and_cache_in_memory<CTX, K, V>(
     // We could not load a result from the on-disk cache, so
     // recompute.
     let prof_timer = tcx.dep_context().profiler().query_provider();
+    let budget_guard = query.time_budget_activity(tcx.dep_context());
 
     // The dep-graph for this computation is already in-place.
     let result = dep_graph.with_ignore(|| query.compute(*tcx.dep_context(), key.clone()));
 
+    drop(budget_guard);
     prof_timer.finish_with_query_invocation_id(dep_node_index.into());
 
     // Verify that re-running the query produced a result with the expected hash
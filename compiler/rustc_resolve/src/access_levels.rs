@@ -156,7 +156,8 @@ fn visit_item(&mut self, item: &'ast ast::Item) {
             | ast::ItemKind::Union(..)
             | ast::ItemKind::Trait(..)
             | ast::ItemKind::TraitAlias(..)
-            | ast::ItemKind::MacroDef(..) => {
+            | ast::ItemKind::MacroDef(..)
+            | ast::ItemKind::Err => {
                 if item.vis.kind.is_pub() {
                     self.prev_level
                 } else {
@@ -229,7 +230,8 @@ fn visit_item(&mut self, item: &'ast ast::Item) {
             | ast::ItemKind::TyAlias(..)
             | ast::ItemKind::TraitAlias(..)
             | ast::ItemKind::MacroDef(..)
-            | ast::ItemKind::Fn(..) => return,
+            | ast::ItemKind::Fn(..)
+            | ast::ItemKind::Err => return,
 
             // Unreachable kinds
             ast::ItemKind::Impl(..) | ast::ItemKind::MacCall(..) => unreachable!(),
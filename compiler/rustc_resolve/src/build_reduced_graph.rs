@@ -825,7 +825,10 @@ fn build_reduced_graph_for_item(&mut self, item: &'b Item) {
             ItemKind::Impl(box Impl { of_trait: Some(..), .. }) => {
                 self.r.trait_impl_items.insert(local_def_id);
             }
-            ItemKind::Impl { .. } | ItemKind::ForeignMod(..) | ItemKind::GlobalAsm(..) => {}
+            ItemKind::Impl { .. }
+            | ItemKind::ForeignMod(..)
+            | ItemKind::GlobalAsm(..)
+            | ItemKind::Err => {}
 
             ItemKind::MacroDef(..) | ItemKind::MacCall(_) => unreachable!(),
         }
@@ -113,6 +113,7 @@ fn visit_item(&mut self, i: &'a Item) {
             ItemKind::Use(..) => {
                 return visit::walk_item(self, i);
             }
+            ItemKind::Err => return,
         };
         let def = self.create_def(i.id, def_data, i.span);
 
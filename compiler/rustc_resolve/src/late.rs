@@ -1788,7 +1788,7 @@ fn resolve_item(&mut self, item: &'ast Item) {
                 self.future_proof_import(use_tree);
             }
 
-            ItemKind::ExternCrate(..) | ItemKind::MacroDef(..) => {
+            ItemKind::ExternCrate(..) | ItemKind::MacroDef(..) | ItemKind::Err => {
                 // do nothing, these are just around to be encoded
             }
 
@@ -3345,7 +3345,8 @@ fn visit_item(&mut self, item: &'ast Item) {
             | ItemKind::ExternCrate(..)
             | ItemKind::MacroDef(..)
             | ItemKind::GlobalAsm(..)
-            | ItemKind::MacCall(..) => {}
+            | ItemKind::MacCall(..)
+            | ItemKind::Err => {}
         }
         visit::walk_item(self, item)
     }
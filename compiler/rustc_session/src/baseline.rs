@@ -0,0 +1,200 @@
+//! Diagnostic baselines, for `--diagnostic-baseline`.
+//!
+//! A baseline is a file listing the lint warnings of a crate, one per line. When the file does not
+//! exist, the warnings of the compilation are recorded into it. When it exists, the warnings it
+//! lists are suppressed so that only the new ones are reported, and the entries that no longer
+//! match any warning are reported as stale.
+//!
+//! Warnings are identified by a fingerprint made of the name of their lint, the path of the item
+//! they are in and the snippet of their primary span with whitespace normalized, so that entries
+//! survive edits that move code around. The same fingerprint can appear several times, each entry
+//! suppressing one warning.
+//!
+//! The baseline is a [`DiagnosticFilter`] of the handler, so that it also sees the warnings
+//! replayed from the incremental cache, which are not linted again.
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::sync::{Lock, Lrc};
+use rustc_errors::{Diagnostic, DiagnosticFilter, DiagnosticId, Handler, Level};
+use rustc_span::source_map::SourceMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Snippets are truncated to this many characters, to keep the baseline readable when warnings
+/// point at whole expressions.
+const MAX_SNIPPET_LEN: usize = 120;
+
+const HEADER: &str =
+    "# rustc diagnostic baseline: one `lint<TAB>item<TAB>snippet` line per warning";
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Fingerprint {
+    pub lint: String,
+    /// The path of the item containing the warning, empty for the lints emitted before the
+    /// items have paths.
+    pub item: String,
+    pub snippet: String,
+}
+
+impl Fingerprint {
+    pub fn new(lint: String, item: String, snippet: &str) -> Fingerprint {
+        let mut normalized = String::new();
+        for word in snippet.split_whitespace() {
+            if !normalized.is_empty() {
+                normalized.push(' ');
+            }
+            normalized.push_str(word);
+        }
+        let snippet = normalized.chars().take(MAX_SNIPPET_LEN).collect();
+        Fingerprint { lint, item, snippet }
+    }
+
+    fn parse(line: &str) -> Option<Fingerprint> {
+        let mut fields = line.splitn(3, '\t');
+        let (lint, item, snippet) = (fields.next()?, fields.next()?, fields.next()?);
+        Some(Fingerprint {
+            lint: lint.to_owned(),
+            item: item.to_owned(),
+            snippet: snippet.to_owned(),
+        })
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\t{}\t{}", self.lint, self.item, self.snippet)
+    }
+}
+
+enum Mode {
+    /// The baseline does not exist yet, the warnings are recorded.
+    Record(Vec<Fingerprint>),
+    /// The number of warnings that remain to be suppressed for each fingerprint.
+    Check(FxHashMap<Fingerprint, usize>),
+}
+
+pub struct DiagnosticBaseline {
+    path: PathBuf,
+    mode: Lock<Mode>,
+    source_map: Lrc<SourceMap>,
+}
+
+impl DiagnosticBaseline {
+    pub fn load(path: PathBuf, source_map: Lrc<SourceMap>) -> io::Result<DiagnosticBaseline> {
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                let mode = Lock::new(Mode::Record(vec![]));
+                return Ok(DiagnosticBaseline { path, mode, source_map });
+            }
+            Err(e) => return Err(e),
+        };
+        let mut entries = FxHashMap::default();
+        for (i, line) in text.lines().enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fingerprint = Fingerprint::parse(line).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("malformed entry on line {}", i + 1),
+                )
+            })?;
+            *entries.entry(fingerprint).or_default() += 1;
+        }
+        Ok(DiagnosticBaseline { path, mode: Lock::new(Mode::Check(entries)), source_map })
+    }
+
+    /// Returns whether the warning identified by `fingerprint` is in the baseline, and records it
+    /// if the baseline is being recorded.
+    fn matches(&self, fingerprint: Fingerprint) -> bool {
+        match &mut *self.mode.lock() {
+            Mode::Record(recorded) => {
+                recorded.push(fingerprint);
+                false
+            }
+            Mode::Check(entries) => match entries.get_mut(&fingerprint) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    true
+                }
+                _ => false,
+            },
+        }
+    }
+
+    /// Writes the recorded baseline, or reports the stale entries of the checked one.
+    pub fn finish(&self, handler: &Handler) {
+        match &mut *self.mode.lock() {
+            Mode::Record(recorded) => {
+                recorded.sort();
+                let mut text = format!("{}\n", HEADER);
+                for fingerprint in recorded.iter() {
+                    text.push_str(&format!("{}\n", fingerprint));
+                }
+                if let Err(e) = fs::write(&self.path, text) {
+                    handler.err(&format!(
+                        "failed to write diagnostic baseline `{}`: {}",
+                        self.path.display(),
+                        e
+                    ));
+                }
+            }
+            Mode::Check(entries) => {
+                let mut stale = entries
+                    .iter()
+                    .filter(|(_, &count)| count > 0)
+                    .map(|(fingerprint, &count)| (fingerprint, count))
+                    .collect::<Vec<_>>();
+                if stale.is_empty() {
+                    return;
+                }
+                stale.sort();
+                let count = stale.iter().map(|(_, count)| count).sum::<usize>();
+                let mut diag = handler.struct_warn(&format!(
+                    "{} {} of the diagnostic baseline `{}` no longer {} any warning",
+                    count,
+                    if count == 1 { "entry" } else { "entries" },
+                    self.path.display(),
+                    if count == 1 { "matches" } else { "match" },
+                ));
+                for (fingerprint, count) in stale {
+                    let times =
+                        if count > 1 { format!(" ({} times)", count) } else { String::new() };
+                    let item = if fingerprint.item.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" in `{}`", fingerprint.item)
+                    };
+                    diag.note(&format!(
+                        "`{}`{}: `{}`{}",
+                        fingerprint.lint, item, fingerprint.snippet, times
+                    ));
+                }
+                diag.help("remove the stale entries, or delete the file to record a new baseline");
+                diag.emit();
+            }
+        }
+    }
+}
+
+impl DiagnosticFilter for DiagnosticBaseline {
+    /// Only the lint warnings are baselined, so that denied lints still fail the build.
+    fn suppresses(&self, diagnostic: &Diagnostic) -> bool {
+        let Some(DiagnosticId::Lint { name, is_force_warn: false, .. }) = &diagnostic.code else {
+            return false;
+        };
+        if diagnostic.level() != Level::Warning {
+            return false;
+        }
+        let snippet = diagnostic
+            .span
+            .primary_span()
+            .and_then(|span| self.source_map.span_to_snippet(span).ok())
+            .unwrap_or_default();
+        let item = diagnostic.item_path.clone().unwrap_or_default();
+        self.matches(Fingerprint::new(name.clone(), item, &snippet))
+    }
+}
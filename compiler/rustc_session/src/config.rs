@@ -140,6 +140,70 @@ pub enum MirSpanview {
     Block,
 }
 
+/// The different settings that the `-Z dump-mir-format` flag can have. `Text` writes the
+/// human-readable `.mir` files, while `Json` writes `.json` files with a versioned schema meant to
+/// be consumed by external tools.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum MirDumpFormat {
+    /// Default `-Z dump-mir-format=text`
+    Text,
+    /// `-Z dump-mir-format=json`
+    Json,
+}
+
+/// A fault to inject into the compiler with `-Z inject-fault`, to exercise the error recovery and
+/// ICE reporting paths of drivers. See `Session::inject_fault`.
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct InjectFault {
+    /// The name of the query or MIR pass to inject the fault into, e.g. `typeck` or `Inline`.
+    pub target: String,
+    /// The fault is injected when `target` runs for the `n`-th time, counting from 1.
+    pub n: u64,
+    pub kind: FaultKind,
+}
+
+/// The different kinds of faults that `-Z inject-fault` can inject.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum FaultKind {
+    /// Default `-Z inject-fault=<target>:<n>` or `-Z inject-fault=<target>:<n>:panic`
+    Panic,
+    /// `-Z inject-fault=<target>:<n>:error`
+    Error,
+    /// `-Z inject-fault=<target>:<n>:delay-bug`
+    DelayBug,
+}
+
+/// A position in a source file, as `-Z explain-inference` takes it.
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct SourcePosition {
+    /// A suffix of the path of the file, e.g. `main.rs` or `src/main.rs`.
+    pub file: String,
+    /// The line, counting from 1.
+    pub line: usize,
+    /// The column, in characters and counting from 1, as in the diagnostics.
+    pub col: usize,
+}
+
+/// The outputs of `-Z borrowck-explain`.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum BorrowckExplain {
+    /// Default `-Z borrowck-explain` or `-Z borrowck-explain=text`: a note rendering the
+    /// constraint path on the source.
+    Text,
+    /// `-Z borrowck-explain=dot`: the note, and a DOT rendering of the constraint graph of the
+    /// function.
+    Dot,
+}
+
+/// The formats in which `-Z mono-items-report` can print its report.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum MonoItemsReportFormat {
+    /// A table, sorted by estimated size.
+    Table,
+    /// A JSON object, for tools.
+    Json,
+}
+
 /// The different settings that the `-C instrument-coverage` flag can have.
 ///
 /// Coverage instrumentation now supports combining `-C instrument-coverage`
@@ -374,6 +438,8 @@ pub enum ErrorOutputType {
         /// human output.
         json_rendered: HumanReadableErrorType,
     },
+    /// Output in the SARIF format, consumed by code scanning tools.
+    Sarif,
 }
 
 impl Default for ErrorOutputType {
@@ -454,6 +520,27 @@ pub fn should_link(&self) -> bool {
     }
 }
 
+/// The mappings of `--remap-path-prefix`, from local path prefixes to the virtual ones replacing
+/// them in all output. Only the virtual prefixes are hashed for dependency tracking: remapped
+/// paths only reach the output through their virtual names, which are hashed along with the
+/// file names, so the incremental cache does not depend on where the sources are checked out.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RemapPathPrefix(Vec<(PathBuf, PathBuf)>);
+
+impl RemapPathPrefix {
+    pub fn new(mapping: Vec<(PathBuf, PathBuf)>) -> RemapPathPrefix {
+        RemapPathPrefix(mapping)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, (PathBuf, PathBuf)> {
+        self.0.iter()
+    }
+
+    pub fn file_path_mapping(&self) -> FilePathMapping {
+        FilePathMapping::new(self.0.clone())
+    }
+}
+
 /// Use tree-based collections to cheaply get a deterministic `Hash` implementation.
 /// *Do not* switch `BTreeMap` or `BTreeSet` out for an unsorted container type! That
 /// would break dependency tracking for command-line arguments.
@@ -732,6 +819,8 @@ fn default() -> Options {
             debuginfo: DebugInfo::None,
             lint_opts: Vec::new(),
             lint_cap: None,
+            diagnostic_baseline: None,
+            lint_config: None,
             describe_lints: false,
             output_types: OutputTypes(BTreeMap::new()),
             search_paths: vec![],
@@ -753,7 +842,7 @@ fn default() -> Options {
             trimmed_def_paths: TrimmedDefPaths::default(),
             cli_forced_codegen_units: None,
             cli_forced_thinlto_off: false,
-            remap_path_prefix: Vec::new(),
+            remap_path_prefix: RemapPathPrefix::default(),
             real_rust_source_base_dir: None,
             edition: DEFAULT_EDITION,
             json_artifact_notifications: false,
@@ -774,7 +863,7 @@ pub fn build_dep_graph(&self) -> bool {
     }
 
     pub fn file_path_mapping(&self) -> FilePathMapping {
-        FilePathMapping::new(self.remap_path_prefix.clone())
+        self.remap_path_prefix.file_path_mapping()
     }
 
     /// Returns `true` if there will be an output file generated.
@@ -1439,6 +1528,20 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
             "Remap source names in all output (compiler messages and output files)",
             "FROM=TO",
         ),
+        opt::opt(
+            "",
+            "diagnostic-baseline",
+            "Suppress the warnings listed in FILE, or record the warnings \
+             in FILE if it does not exist",
+            "FILE",
+        ),
+        opt::opt(
+            "",
+            "lint-config",
+            "Set the lint levels of the crate from FILE, instead of \
+             the `lints.toml` found from the crate root",
+            "FILE",
+        ),
     ]);
     opts
 }
@@ -1595,6 +1698,7 @@ pub fn parse_error_format(
             }
             Some("json") => ErrorOutputType::Json { pretty: false, json_rendered },
             Some("pretty-json") => ErrorOutputType::Json { pretty: true, json_rendered },
+            Some("sarif") => ErrorOutputType::Sarif,
             Some("short") => ErrorOutputType::HumanReadable(HumanReadableErrorType::Short(color)),
 
             Some(arg) => early_error(
@@ -1678,6 +1782,12 @@ fn check_debug_option_stability(
                 "`--error-format=human-annotate-rs` is unstable",
             );
         }
+        if let ErrorOutputType::Sarif = error_format {
+            early_error(
+                ErrorOutputType::Json { pretty: false, json_rendered },
+                "`--error-format=sarif` is unstable",
+            );
+        }
     }
 }
 
@@ -2212,7 +2322,7 @@ fn parse_remap_path_prefix(
     matches: &getopts::Matches,
     debugging_opts: &DebuggingOptions,
     error_format: ErrorOutputType,
-) -> Vec<(PathBuf, PathBuf)> {
+) -> RemapPathPrefix {
     let mut mapping: Vec<(PathBuf, PathBuf)> = matches
         .opt_strs("remap-path-prefix")
         .into_iter()
@@ -2231,7 +2341,7 @@ fn parse_remap_path_prefix(
         },
         None => (),
     };
-    mapping
+    RemapPathPrefix::new(mapping)
 }
 
 pub fn build_session_options(matches: &getopts::Matches) -> Options {
@@ -2254,6 +2364,8 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
 
     let mut debugging_opts = DebuggingOptions::build(matches, error_format);
     let (lint_opts, describe_lints, lint_cap) = get_cmd_lint_options(matches, error_format);
+    let diagnostic_baseline = matches.opt_str("diagnostic-baseline").map(PathBuf::from);
+    let lint_config = matches.opt_str("lint-config").map(PathBuf::from);
 
     check_debug_option_stability(&debugging_opts, error_format, json_rendered);
 
@@ -2496,8 +2608,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         early_error(error_format, &format!("Current directory is invalid: {e}"));
     });
 
-    let (path, remapped) =
-        FilePathMapping::new(remap_path_prefix.clone()).map_prefix(working_dir.clone());
+    let (path, remapped) = remap_path_prefix.file_path_mapping().map_prefix(working_dir.clone());
     let working_dir = if remapped {
         RealFileName::Remapped { local_path: Some(working_dir), virtual_name: path }
     } else {
@@ -2511,6 +2622,8 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         debuginfo,
         lint_opts,
         lint_cap,
+        diagnostic_baseline,
+        lint_config,
         describe_lints,
         output_types,
         search_paths,
@@ -2771,10 +2884,11 @@ pub fn needs_analysis(&self) -> bool {
 /// how the hash should be calculated when adding a new command-line argument.
 crate mod dep_tracking {
     use super::{
-        BranchProtection, CFGuard, CFProtection, CrateType, DebugInfo, ErrorOutputType,
-        InstrumentCoverage, LdImpl, LinkerPluginLto, LocationDetail, LtoCli, OomStrategy, OptLevel,
-        OutputType, OutputTypes, Passes, SourceFileHashAlgorithm, SwitchWithOptPath,
-        SymbolManglingVersion, TrimmedDefPaths,
+        BorrowckExplain, BranchProtection, CFGuard, CFProtection, CrateType, DebugInfo,
+        ErrorOutputType, InjectFault, InstrumentCoverage, LdImpl, LinkerPluginLto, LocationDetail,
+        LtoCli, OomStrategy, OptLevel, OutputType, OutputTypes, Passes, RemapPathPrefix,
+        SourceFileHashAlgorithm, SourcePosition, SwitchWithOptPath, SymbolManglingVersion,
+        TrimmedDefPaths,
     };
     use crate::lint;
     use crate::options::WasiExecModel;
@@ -2870,6 +2984,9 @@ fn hash(
         OutputType,
         RealFileName,
         LocationDetail,
+        InjectFault,
+        BorrowckExplain,
+        SourcePosition,
         BranchProtection,
         OomStrategy,
         LanguageIdentifier,
@@ -2946,6 +3063,21 @@ fn hash(
         }
     }
 
+    impl DepTrackingHash for RemapPathPrefix {
+        fn hash(
+            &self,
+            hasher: &mut DefaultHasher,
+            error_format: ErrorOutputType,
+            for_crate_hash: bool,
+        ) {
+            Hash::hash(&self.0.len(), hasher);
+            for (index, (_from, to)) in self.0.iter().enumerate() {
+                Hash::hash(&index, hasher);
+                DepTrackingHash::hash(to, hasher, error_format, for_crate_hash);
+            }
+        }
+    }
+
     // This is a stable hash because BTreeMap is a sorted container
     crate fn stable_hash(
         sub_hashes: BTreeMap<&'static str, &dyn DepTrackingHash>,
@@ -22,10 +22,13 @@
 mod code_stats;
 #[macro_use]
 pub mod config;
+pub mod baseline;
 pub mod cstore;
 pub mod filesearch;
+pub mod lint_config;
 mod options;
 pub mod search_paths;
+pub mod time_budget;
 
 mod session;
 pub use session::*;
@@ -0,0 +1,248 @@
+//! Lint configuration files, for `--lint-config`.
+//!
+//! A lint configuration file sets the lint levels of every crate it applies to, as if the crates
+//! started with the corresponding `#![level(lint)]` attributes, so that the attributes of the
+//! crates, and of their items, override it. It is the file given with `--lint-config`, or else,
+//! with `-Z lint-config-discovery`, the first `lints.toml` found in the directory of the crate root
+//! or in one of its ancestors.
+//!
+//! The file is written in a subset of TOML: a `[lints]` table sets the levels of the whole crate,
+//! and `[paths."<path>"]` tables set the levels of the modules whose source file is `<path>`, or is
+//! in the directory `<path>`, relative to the directory of the configuration file. A level is
+//! either a string or an inline table with a `reason`, which is shown with the diagnostics:
+//!
+//! ```toml
+//! [lints]
+//! unsafe_code = "forbid"
+//! missing_docs = { level = "deny", reason = "the public API must be documented" }
+//! "clippy::pedantic" = "warn"
+//!
+//! [paths."src/generated"]
+//! missing_docs = "allow"
+//! ```
+
+use crate::lint::Level;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The name of the lint configuration files looked up from the crate root.
+pub const LINT_CONFIG_FILE_NAME: &str = "lints.toml";
+
+/// The level of a lint in a lint configuration file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LintConfigEntry {
+    /// The name of the lint or lint group, with dashes replaced by underscores.
+    pub name: String,
+    pub level: Level,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct LintConfig {
+    pub path: PathBuf,
+    /// The levels of the `[lints]` table.
+    pub lints: Vec<LintConfigEntry>,
+    /// The `[paths]` tables, with their paths made absolute.
+    pub paths: Vec<(PathBuf, Vec<LintConfigEntry>)>,
+}
+
+impl LintConfig {
+    /// Returns the lint configuration file applying to the crate whose root is `crate_root`.
+    pub fn discover(crate_root: &Path) -> Option<PathBuf> {
+        let crate_root = absolute(crate_root);
+        crate_root
+            .ancestors()
+            .skip(1)
+            .map(|dir| dir.join(LINT_CONFIG_FILE_NAME))
+            .find(|path| path.is_file())
+    }
+
+    pub fn load(path: PathBuf) -> Result<LintConfig, String> {
+        let src = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+        let mut config = parse_lint_config(&src)?;
+        let dir = absolute(&path);
+        let dir = dir.parent().unwrap_or(Path::new(""));
+        for (path, _) in &mut config.paths {
+            *path = dir.join(&path);
+        }
+        config.path = path;
+        Ok(config)
+    }
+
+    /// Returns the levels of the `[paths]` tables applying to the source file `file`.
+    pub fn path_lints<'a>(&'a self, file: &Path) -> impl Iterator<Item = &'a LintConfigEntry> {
+        let file = absolute(file);
+        self.paths
+            .iter()
+            .filter(move |(path, _)| file.starts_with(path))
+            .flat_map(|(_, lints)| lints)
+    }
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        env::current_dir().map_or_else(|_| path.to_path_buf(), |dir| dir.join(path))
+    }
+}
+
+enum Table {
+    None,
+    Lints,
+    Path(usize),
+}
+
+/// Parses a lint configuration file, leaving the paths of its `[paths]` tables relative.
+pub fn parse_lint_config(src: &str) -> Result<LintConfig, String> {
+    let mut config = LintConfig { path: PathBuf::new(), lints: vec![], paths: vec![] };
+    let mut table = Table::None;
+    for (i, line) in src.lines().enumerate() {
+        let error = |msg: String| format!("line {}: {}", i + 1, msg);
+        let mut line = Cursor(line);
+        line.skip_whitespace();
+        if line.at_end() {
+            continue;
+        }
+
+        if line.eat('[') {
+            let name = line.key().map_err(error)?;
+            table = match &name[..] {
+                "lints" => Table::Lints,
+                "paths" if line.eat('.') => {
+                    let path = line.key().map_err(error)?;
+                    config.paths.push((PathBuf::from(path), vec![]));
+                    Table::Path(config.paths.len() - 1)
+                }
+                _ => return Err(error(format!("unknown table `{}`", name))),
+            };
+            if !line.eat(']') {
+                return Err(error("expected `]`".into()));
+            }
+            line.expect_end().map_err(error)?;
+            continue;
+        }
+
+        let name = line.key().map_err(error)?.replace('-', "_");
+        if !line.eat('=') {
+            return Err(error("expected `=`".into()));
+        }
+        let (level, reason) = if line.eat('{') {
+            let (mut level, mut reason) = (None, None);
+            loop {
+                let key = line.key().map_err(error)?;
+                if !line.eat('=') {
+                    return Err(error("expected `=`".into()));
+                }
+                let value = line.string().map_err(error)?;
+                match &key[..] {
+                    "level" => level = Some(value),
+                    "reason" => reason = Some(value),
+                    _ => return Err(error(format!("unknown key `{}`", key))),
+                }
+                if line.eat('}') {
+                    break;
+                }
+                if !line.eat(',') {
+                    return Err(error("expected `,` or `}`".into()));
+                }
+            }
+            (level.ok_or_else(|| error(format!("missing `level` for `{}`", name)))?, reason)
+        } else {
+            (line.string().map_err(error)?, None)
+        };
+        line.expect_end().map_err(error)?;
+
+        let level = match Level::from_str(&level) {
+            Some(level) => level,
+            None if level == "expect" => {
+                return Err(error("the `expect` level cannot be set in lint configuration".into()));
+            }
+            None => return Err(error(format!("unknown lint level `{}`", level))),
+        };
+        let entry = LintConfigEntry { name, level, reason };
+        match table {
+            Table::None => return Err(error("expected a `[lints]` or `[paths]` table".into())),
+            Table::Lints => config.lints.push(entry),
+            Table::Path(index) => config.paths[index].1.push(entry),
+        }
+    }
+    Ok(config)
+}
+
+/// The unparsed part of a line.
+struct Cursor<'a>(&'a str);
+
+impl Cursor<'_> {
+    fn skip_whitespace(&mut self) {
+        self.0 = self.0.trim_start();
+        if self.0.starts_with('#') {
+            self.0 = "";
+        }
+    }
+
+    fn at_end(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn expect_end(&mut self) -> Result<(), String> {
+        self.skip_whitespace();
+        if self.at_end() { Ok(()) } else { Err(format!("unexpected `{}`", self.0)) }
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        match self.0.strip_prefix(c) {
+            Some(rest) => {
+                self.0 = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Parses a bare or quoted key.
+    fn key(&mut self) -> Result<String, String> {
+        self.skip_whitespace();
+        if self.0.starts_with('"') {
+            return self.string();
+        }
+        let len = self
+            .0
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(self.0.len());
+        if len == 0 {
+            return Err("expected a key".into());
+        }
+        let (key, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(key.to_string())
+    }
+
+    /// Parses a basic string.
+    fn string(&mut self) -> Result<String, String> {
+        if !self.eat('"') {
+            return Err("expected a string".into());
+        }
+        let mut string = String::new();
+        let mut chars = self.0.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.0 = &self.0[i + 1..];
+                    return Ok(string);
+                }
+                '\\' => match chars.next() {
+                    Some((_, '"')) => string.push('"'),
+                    Some((_, '\\')) => string.push('\\'),
+                    Some((_, 'n')) => string.push('\n'),
+                    Some((_, 't')) => string.push('\t'),
+                    _ => return Err("invalid escape in string".into()),
+                },
+                c => string.push(c),
+            }
+        }
+        Err("unterminated string".into())
+    }
+}
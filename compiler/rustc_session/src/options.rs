@@ -4,6 +4,7 @@
 use crate::lint;
 use crate::search_paths::SearchPath;
 use crate::utils::NativeLib;
+use rustc_data_structures::profiling::ProfileFormat;
 use rustc_errors::LanguageIdentifier;
 use rustc_target::spec::{CodeModel, LinkerFlavor, MergeFunctions, PanicStrategy, SanitizerSet};
 use rustc_target::spec::{
@@ -22,6 +23,7 @@
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::str;
+use std::time::Duration;
 
 macro_rules! insert {
     ($opt_name:ident, $opt_expr:expr, $sub_hashes:expr) => {
@@ -160,6 +162,11 @@ pub struct Options {
         debuginfo: DebugInfo [TRACKED],
         lint_opts: Vec<(String, lint::Level)> [TRACKED_NO_CRATE_HASH],
         lint_cap: Option<lint::Level> [TRACKED_NO_CRATE_HASH],
+        /// The file of `--diagnostic-baseline`, listing the warnings to suppress. Untracked as the
+        /// baseline only filters the emitted diagnostics, including the replayed ones.
+        diagnostic_baseline: Option<PathBuf> [UNTRACKED],
+        /// The file of `--lint-config`, setting the lint levels of the crate.
+        lint_config: Option<PathBuf> [UNTRACKED],
         describe_lints: bool [UNTRACKED],
         output_types: OutputTypes [TRACKED],
         search_paths: Vec<SearchPath> [UNTRACKED],
@@ -204,7 +211,7 @@ pub struct Options {
         cli_forced_thinlto_off: bool [UNTRACKED],
 
         /// Remap source path prefixes in all output (messages, object files, debug, etc.).
-        remap_path_prefix: Vec<(PathBuf, PathBuf)> [TRACKED_NO_CRATE_HASH],
+        remap_path_prefix: RemapPathPrefix [TRACKED_NO_CRATE_HASH],
         /// Base directory containing the `src/` for the Rust standard library, and
         /// potentially `rustc` as well, if we can can find it. Right now it's always
         /// `$sysroot/lib/rustlib/src/rust` (i.e. the `rustup` `rust-src` component).
@@ -367,6 +374,7 @@ mod desc {
     pub const parse_string_push: &str = parse_string;
     pub const parse_opt_langid: &str = "a language identifier";
     pub const parse_opt_pathbuf: &str = "a path";
+    pub const parse_pathbuf_push: &str = parse_opt_pathbuf;
     pub const parse_list: &str = "a space-separated list of strings";
     pub const parse_list_with_polarity: &str =
         "a comma-separated list of strings, with elements beginning with + or -";
@@ -374,6 +382,9 @@ mod desc {
     pub const parse_number: &str = "a number";
     pub const parse_opt_number: &str = parse_number;
     pub const parse_threads: &str = parse_number;
+    pub const parse_opt_byte_size: &str =
+        "a number of bytes, optionally followed by a `K`, `M`, or `G` multiplier";
+    pub const parse_opt_days: &str = "a number of days";
     pub const parse_passes: &str = "a space-separated list of passes, or `all`";
     pub const parse_panic_strategy: &str = "either `unwind` or `abort`";
     pub const parse_opt_panic_strategy: &str = parse_panic_strategy;
@@ -388,6 +399,13 @@ mod desc {
     pub const parse_linker_flavor: &str = ::rustc_target::spec::LinkerFlavor::one_of();
     pub const parse_optimization_fuel: &str = "crate=integer";
     pub const parse_mir_spanview: &str = "`statement` (default), `terminator`, or `block`";
+    pub const parse_mir_dump_format: &str = "either `text` (default) or `json`";
+    pub const parse_self_profile_format: &str = "one of: `chrome`, `folded`, or `summary`";
+    pub const parse_inject_fault: &str =
+        "`name:n` or `name:n:kind`, where `kind` is `panic` (default), `error`, or `delay-bug`";
+    pub const parse_mono_items_report: &str = "either `table` or `json`";
+    pub const parse_borrowck_explain: &str = "either `text` (default) or `dot`";
+    pub const parse_source_position: &str = "`file:line:col`";
     pub const parse_instrument_coverage: &str =
         "`all` (default), `except-unused-generics`, `except-unused-functions`, or `off`";
     pub const parse_unpretty: &str = "`string` or `string=string`";
@@ -511,6 +529,16 @@ mod parse {
         }
     }
 
+    crate fn parse_pathbuf_push(slot: &mut Vec<PathBuf>, v: Option<&str>) -> bool {
+        match v {
+            Some(s) => {
+                slot.push(PathBuf::from(s));
+                true
+            }
+            None => false,
+        }
+    }
+
     crate fn parse_string_push(slot: &mut Vec<String>, v: Option<&str>) -> bool {
         match v {
             Some(s) => {
@@ -611,6 +639,33 @@ mod parse {
         }
     }
 
+    crate fn parse_opt_byte_size(slot: &mut Option<u64>, v: Option<&str>) -> bool {
+        let Some(v) = v else { return false };
+        let (number, shift) = match v.as_bytes().last() {
+            Some(b'K') => (&v[..v.len() - 1], 10),
+            Some(b'M') => (&v[..v.len() - 1], 20),
+            Some(b'G') => (&v[..v.len() - 1], 30),
+            _ => (v, 0),
+        };
+        match number.parse::<u64>().ok().and_then(|n| n.checked_mul(1 << shift)) {
+            Some(size) => {
+                *slot = Some(size);
+                true
+            }
+            None => false,
+        }
+    }
+
+    crate fn parse_opt_days(slot: &mut Option<Duration>, v: Option<&str>) -> bool {
+        match v.and_then(|v| v.parse::<u64>().ok()).and_then(|n| n.checked_mul(24 * 60 * 60)) {
+            Some(secs) => {
+                *slot = Some(Duration::from_secs(secs));
+                true
+            }
+            None => false,
+        }
+    }
+
     crate fn parse_passes(slot: &mut Passes, v: Option<&str>) -> bool {
         match v {
             Some("all") => {
@@ -813,6 +868,80 @@ mod parse {
         true
     }
 
+    crate fn parse_mir_dump_format(slot: &mut MirDumpFormat, v: Option<&str>) -> bool {
+        *slot = match v {
+            Some("text") => MirDumpFormat::Text,
+            Some("json") => MirDumpFormat::Json,
+            _ => return false,
+        };
+        true
+    }
+
+    crate fn parse_self_profile_format(slot: &mut Option<ProfileFormat>, v: Option<&str>) -> bool {
+        *slot = match v {
+            Some("chrome") => Some(ProfileFormat::Chrome),
+            Some("folded") => Some(ProfileFormat::Folded),
+            Some("summary") => Some(ProfileFormat::Summary),
+            _ => return false,
+        };
+        true
+    }
+
+    crate fn parse_inject_fault(slot: &mut Option<InjectFault>, v: Option<&str>) -> bool {
+        let Some(s) = v else { return false };
+        let mut parts = s.split(':');
+        let (Some(target), Some(n)) = (parts.next(), parts.next()) else { return false };
+        let kind = match parts.next() {
+            None | Some("panic") => FaultKind::Panic,
+            Some("error") => FaultKind::Error,
+            Some("delay-bug") => FaultKind::DelayBug,
+            Some(_) => return false,
+        };
+        match n.parse::<u64>() {
+            Ok(n) if n > 0 && !target.is_empty() && parts.next().is_none() => {
+                *slot = Some(InjectFault { target: target.to_string(), n, kind });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    crate fn parse_mono_items_report(
+        slot: &mut Option<MonoItemsReportFormat>,
+        v: Option<&str>,
+    ) -> bool {
+        *slot = match v {
+            Some("table") => Some(MonoItemsReportFormat::Table),
+            Some("json") => Some(MonoItemsReportFormat::Json),
+            _ => return false,
+        };
+        true
+    }
+
+    crate fn parse_borrowck_explain(slot: &mut Option<BorrowckExplain>, v: Option<&str>) -> bool {
+        *slot = match v {
+            None | Some("text") => Some(BorrowckExplain::Text),
+            Some("dot") => Some(BorrowckExplain::Dot),
+            _ => return false,
+        };
+        true
+    }
+
+    crate fn parse_source_position(slot: &mut Option<SourcePosition>, v: Option<&str>) -> bool {
+        let Some(s) = v else { return false };
+        let mut parts = s.rsplitn(3, ':');
+        let (Some(col), Some(line), Some(file)) = (parts.next(), parts.next(), parts.next()) else {
+            return false;
+        };
+        match (line.parse::<usize>(), col.parse::<usize>()) {
+            (Ok(line), Ok(col)) if line > 0 && col > 0 && !file.is_empty() => {
+                *slot = Some(SourcePosition { file: file.to_string(), line, col });
+                true
+            }
+            _ => false,
+        }
+    }
+
     crate fn parse_instrument_coverage(
         slot: &mut Option<InstrumentCoverage>,
         v: Option<&str>,
@@ -1176,6 +1305,9 @@ mod parse {
         "only allow the listed language features to be enabled in code (space separated)"),
     always_encode_mir: bool = (false, parse_bool, [TRACKED],
         "encode MIR of all functions into the crate metadata (default: no)"),
+    apply_suggestions: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "apply the machine-applicable suggestions of the emitted diagnostics, writing the \
+        fixed source files to the given directory"),
     assume_incomplete_release: bool = (false, parse_bool, [TRACKED],
         "make cfg(version) treat the current version as incomplete (default: no)"),
     asm_comments: bool = (false, parse_bool, [TRACKED],
@@ -1192,6 +1324,10 @@ mod parse {
         (default: no)"),
     borrowck: String = ("migrate".to_string(), parse_string, [UNTRACKED],
         "select which borrowck is used (`mir` or `migrate`) (default: `migrate`)"),
+    borrowck_explain: Option<BorrowckExplain> = (None, parse_borrowck_explain, [TRACKED],
+        "explain the region errors of the borrow checker by rendering the path of outlives \
+        constraints that forced the conflicting region on the source (`text`), and also by \
+        writing the region constraint graph of the function as DOT into `-Z dump-mir-dir` (`dot`)"),
     branch_protection: Option<BranchProtection> = (None, parse_branch_protection, [TRACKED],
         "set options for branch target identification and pointer authentication on AArch64"),
     cf_protection: CFProtection = (CFProtection::None, parse_cfprotection, [TRACKED],
@@ -1244,6 +1380,9 @@ mod parse {
         "the directory the MIR is dumped into (default: `mir_dump`)"),
     dump_mir_exclude_pass_number: bool = (false, parse_bool, [UNTRACKED],
         "exclude the pass number when dumping MIR (used in tests) (default: no)"),
+    dump_mir_format: MirDumpFormat = (MirDumpFormat::Text, parse_mir_dump_format, [UNTRACKED],
+        "the format of the MIR dump files: human-readable `.mir` files (`text`) or \
+        machine-readable `.json` files with a versioned schema (`json`) (default: `text`)"),
     dump_mir_graphviz: bool = (false, parse_bool, [UNTRACKED],
         "in addition to `.mir` files, create graphviz `.dot` files (and with \
         `-Z instrument-coverage`, also create a `.dot` file for the MIR-derived \
@@ -1254,8 +1393,14 @@ mod parse {
         computed `block` spans (one span encompassing a block's terminator and \
         all statements). If `-Z instrument-coverage` is also enabled, create \
         an additional `.html` file showing the computed coverage spans."),
+    emit_size_map: bool = (false, parse_bool, [UNTRACKED],
+        "write a JSON map from each symbol of the output to its size and to the crate, path and \
+        generic arguments of its item, as `{output}.size-map.json` (default: no)"),
     emit_stack_sizes: bool = (false, parse_bool, [UNTRACKED],
         "emit a section containing stack size metadata (default: no)"),
+    explain_inference: Option<SourcePosition> = (None, parse_source_position, [TRACKED],
+        "explain the inference of the type variables created at `file:line:col`, with the \
+        unifications, obligations and defaulting steps that touched them"),
     fewer_names: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "reduce memory use by retaining fewer names within compilation artifacts (LLVM-IR) \
         (default: no)"),
@@ -1279,15 +1424,32 @@ mod parse {
         "generate human-readable, predictable names for codegen units (default: no)"),
     identify_regions: bool = (false, parse_bool, [UNTRACKED],
         "display unnamed regions as `'<id>`, using a non-ident unique id (default: no)"),
+    incremental_explain: bool = (false, parse_bool, [UNTRACKED],
+        "explain why each codegen unit is recompiled, with the chain of changed queries leading \
+        to the changed inputs (default: no)"),
     incremental_ignore_spans: bool = (false, parse_bool, [UNTRACKED],
         "ignore spans during ICH computation -- used for testing (default: no)"),
     incremental_info: bool = (false, parse_bool, [UNTRACKED],
         "print high-level information about incremental reuse (or the lack thereof) \
         (default: no)"),
+    incremental_max_age: Option<Duration> = (None, parse_opt_days, [UNTRACKED],
+        "evict the incremental session directories that were not used for this many days"),
+    incremental_max_size: Option<u64> = (None, parse_opt_byte_size, [UNTRACKED],
+        "evict the least recently used incremental session directories until the incremental \
+        directory fits in this size"),
     incremental_relative_spans: bool = (false, parse_bool, [TRACKED],
         "hash spans relative to their parent item for incr. comp. (default: no)"),
+    incremental_shared_cache: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "start from the session last published to this shared incremental cache when the \
+        incremental directory has none"),
+    incremental_shared_cache_publish: bool = (false, parse_bool, [UNTRACKED],
+        "publish the finalized session to the `-Z incremental-shared-cache` (default: no)"),
     incremental_verify_ich: bool = (false, parse_bool, [UNTRACKED],
         "verify incr. comp. hashes of green query instances (default: no)"),
+    inject_fault: Option<InjectFault> = (None, parse_inject_fault, [TRACKED],
+        "inject a fault into the `n`-th run of the query or MIR pass `name`, to test the error \
+        recovery of drivers (`-Z inject-fault=name:n[:kind]`, `kind` being `panic` (default), \
+        `error`, or `delay-bug`)"),
     inline_mir: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "enable MIR inlining (default: no)"),
     inline_mir_threshold: Option<usize> = (None, parse_opt_number, [TRACKED],
@@ -1314,6 +1476,15 @@ mod parse {
         "link native libraries in the linker invocation (default: yes)"),
     link_only: bool = (false, parse_bool, [TRACKED],
         "link the `.rlink` file generated by `-Z no-link` (default: no)"),
+    lint_config_discovery: bool = (false, parse_bool, [UNTRACKED],
+        "without `--lint-config`, set the lint levels of the crate from the first `lints.toml` \
+        found in the directory of the crate root or in one of its ancestors (default: no)"),
+    lint_plugin: Vec<PathBuf> = (Vec::new(), parse_pathbuf_push, [TRACKED],
+        "load a lint plugin, a dynamic library declared with `rustc_lint::declare_lint_plugin!` \
+        (may be specified multiple times)"),
+    lint_rules: Vec<PathBuf> = (Vec::new(), parse_pathbuf_push, [TRACKED],
+        "check the uses of items against the rules of a lint rules file \
+        (may be specified multiple times)"),
     llvm_plugins: Vec<String> = (Vec::new(), parse_list, [TRACKED],
         "a list LLVM plugins to enable (space separated)"),
     llvm_time_trace: bool = (false, parse_bool, [UNTRACKED],
@@ -1339,6 +1510,9 @@ mod parse {
         disabled by other flags as usual."),
     mir_opt_level: Option<usize> = (None, parse_opt_number, [TRACKED],
         "MIR optimization level (0-4; default: 1 in non optimized builds and 2 in optimized builds)"),
+    mono_items_report: Option<MonoItemsReportFormat> = (None, parse_mono_items_report, [UNTRACKED],
+        "print the instantiations of each generic definition, with their estimated size and the \
+        crates that requested them, as a table or as JSON (`table` or `json`)"),
     move_size_limit: Option<usize> = (None, parse_opt_number, [TRACKED],
         "the size at which the `large_assignments` lint starts to be emitted"),
     mutable_noalias: Option<bool> = (None, parse_opt_bool, [TRACKED],
@@ -1458,6 +1632,9 @@ mod parse {
         for example: `-Z self-profile-events=default,query-keys`
         all options: none, all, default, generic-activity, query-provider, query-cache-hit
                      query-blocked, incr-cache-load, incr-result-hashing, query-keys, function-args, args, llvm, artifact-sizes"),
+    self_profile_format: Option<ProfileFormat> = (None, parse_self_profile_format, [UNTRACKED],
+        "also write the profile of `-Z self-profile` as a Chrome trace, as folded stacks for \
+        flame graphs, or as a summary table (`chrome`, `folded`, or `summary`)"),
     share_generics: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "make the current crate share its generic instantiations"),
     show_span: Option<String> = (None, parse_opt_string, [TRACKED],
@@ -1516,6 +1693,9 @@ mod parse {
         "use a thread pool with N threads"),
     time: bool = (false, parse_bool, [UNTRACKED],
         "measure time of rustc processes (default: no)"),
+    time_budget: Option<u64> = (None, parse_opt_number, [UNTRACKED],
+        "warn about the items whose type-checking, trait selection, or monomorphization, and \
+        the codegen units whose LLVM optimization, takes more than this many milliseconds"),
     time_llvm_passes: bool = (false, parse_bool, [UNTRACKED],
         "measure time of each LLVM pass (default: no)"),
     time_passes: bool = (false, parse_bool, [UNTRACKED],
@@ -1524,6 +1704,9 @@ mod parse {
         "choose the TLS model to use (`rustc --print tls-models` for details)"),
     trace_macros: bool = (false, parse_bool, [UNTRACKED],
         "for every macro invocation, print its name and arguments (default: no)"),
+    trait_solver_trace: bool = (false, parse_bool, [TRACKED],
+        "print the selection tree of the trait and projection goals that fail to hold, with the \
+        candidates considered for each goal and their nested obligations (default: no)"),
     trap_unreachable: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "generate trap instructions for unreachable intrinsics (default: use target setting, usually yes)"),
     treat_err_as_bug: Option<NonZeroUsize> = (None, parse_treat_err_as_bug, [TRACKED],
@@ -1,9 +1,12 @@
+use crate::baseline::DiagnosticBaseline;
 use crate::cgu_reuse_tracker::CguReuseTracker;
 use crate::code_stats::CodeStats;
 pub use crate::code_stats::{DataTypeKind, FieldInfo, SizeKind, VariantInfo};
 use crate::config::{self, CrateType, OutputType, SwitchWithOptPath};
+use crate::lint_config::LintConfig;
 use crate::parse::ParseSess;
 use crate::search_paths::{PathKind, SearchPath};
+use crate::time_budget::{TimeBudget, TimeBudgetGuard};
 use crate::{filesearch, lint};
 
 pub use rustc_ast::attr::MarkedAttrs;
@@ -17,13 +20,16 @@
 };
 use rustc_errors::annotate_snippet_emitter_writer::AnnotateSnippetEmitterWriter;
 use rustc_errors::emitter::{Emitter, EmitterWriter, HumanReadableErrorType};
+use rustc_errors::fix::FixEmitter;
 use rustc_errors::json::JsonEmitter;
 use rustc_errors::registry::Registry;
+use rustc_errors::sarif::SarifEmitter;
 use rustc_errors::{
     fallback_fluent_bundle, DiagnosticBuilder, DiagnosticId, DiagnosticMessage, EmissionGuarantee,
     ErrorGuaranteed, FluentBundle, LazyFallbackBundle, MultiSpan,
 };
 use rustc_macros::HashStable_Generic;
+use rustc_span::def_id::DefId;
 pub use rustc_span::def_id::StableCrateId;
 use rustc_span::edition::Edition;
 use rustc_span::source_map::{FileLoader, RealFileLoader, SourceMap, Span};
@@ -168,6 +174,18 @@ pub struct Session {
     /// Always set to zero and incremented so that we can print fuel expended by a crate.
     pub print_fuel: AtomicU64,
 
+    /// Counts the runs of the target of `-Z inject-fault` so far.
+    fault_injection_count: AtomicU64,
+
+    /// The warnings to suppress or to record, if `--diagnostic-baseline` is specified.
+    pub diagnostic_baseline: Option<Lrc<DiagnosticBaseline>>,
+
+    /// The lint levels set by `--lint-config`, or by the `lints.toml` applying to the crate.
+    pub lint_config: Option<LintConfig>,
+
+    /// The time spent on each item, if `-Z time-budget` is specified.
+    pub time_budget: Option<TimeBudget>,
+
     /// Loaded up early on in the initialization of this `Session` to avoid
     /// false positives about a job server in our environment.
     pub jobserver: Client,
@@ -251,10 +269,21 @@ fn check_miri_unleashed_features(&self) {
     /// Invoked all the way at the end to finish off diagnostics printing.
     pub fn finish_diagnostics(&self, registry: &Registry) {
         self.check_miri_unleashed_features();
+        self.finish_diagnostic_baseline();
         self.diagnostic().print_error_count(registry);
         self.emit_future_breakage();
     }
 
+    fn finish_diagnostic_baseline(&self) {
+        // The lints may not have run if there were errors, don't record an incomplete baseline
+        // or report entries as stale.
+        if let Some(baseline) = &self.diagnostic_baseline {
+            if self.has_errors().is_none() {
+                baseline.finish(self.diagnostic());
+            }
+        }
+    }
+
     fn emit_future_breakage(&self) {
         if !self.opts.json_future_incompat {
             return;
@@ -909,6 +938,46 @@ pub fn consider_optimizing<T: Fn() -> String>(&self, crate_name: &str, msg: T) -
         ret
     }
 
+    /// Called before each run of the query provider or MIR pass `name`. If `name` is the target
+    /// of `-Z inject-fault` and this is the requested run, injects the requested fault: either
+    /// panics, emits an error or emits a delayed bug. `name` is only called if `-Z inject-fault`
+    /// is passed.
+    ///
+    /// Runs are counted in the order they happen, so the fault is only injected
+    /// deterministically without a parallel compiler.
+    pub fn inject_fault(&self, name: impl FnOnce() -> String) {
+        let Some(fault) = &self.opts.debugging_opts.inject_fault else { return };
+        if name() != fault.target {
+            return;
+        }
+        let run = self.fault_injection_count.fetch_add(1, SeqCst) + 1;
+        if run != fault.n {
+            return;
+        }
+        let msg =
+            format!("`-Z inject-fault`: injected fault into run {} of `{}`", run, fault.target);
+        match fault.kind {
+            config::FaultKind::Panic => panic!("{}", msg),
+            config::FaultKind::Error => {
+                self.err(&msg);
+            }
+            config::FaultKind::DelayBug => {
+                self.delay_span_bug(rustc_span::DUMMY_SP, &msg);
+            }
+        }
+    }
+
+    /// Starts timing `activity` for `-Z time-budget`, charging it to `item`, or to the item of the
+    /// enclosing activity if `item` is `None`. The activity ends when the guard is dropped.
+    #[inline]
+    pub fn time_budget_activity(
+        &self,
+        activity: &'static str,
+        item: Option<DefId>,
+    ) -> Option<TimeBudgetGuard<'_>> {
+        self.time_budget.as_ref().map(|budget| budget.start(activity, item))
+    }
+
     /// Returns the number of query threads that should be used for this
     /// compilation
     pub fn threads(&self) -> usize {
@@ -1168,6 +1237,12 @@ fn default_emitter(
             )
             .ui_testing(sopts.debugging_opts.ui_testing),
         ),
+        (config::ErrorOutputType::Sarif, None) => {
+            Box::new(SarifEmitter::stderr(Some(registry), source_map, bundle, fallback_bundle))
+        }
+        (config::ErrorOutputType::Sarif, Some(dst)) => {
+            Box::new(SarifEmitter::new(dst, Some(registry), source_map, bundle, fallback_bundle))
+        }
     }
 }
 
@@ -1236,14 +1311,31 @@ pub fn build_session(
         rustc_errors::DEFAULT_LOCALE_RESOURCES,
         sopts.debugging_opts.translate_directionality_markers,
     );
-    let emitter =
+    let mut emitter =
         default_emitter(&sopts, registry, source_map.clone(), bundle, fallback_bundle, write_dest);
+    if let Some(ref out_dir) = sopts.debugging_opts.apply_suggestions {
+        emitter = Box::new(FixEmitter::new(emitter, source_map.clone(), out_dir.clone()));
+    }
 
     let span_diagnostic = rustc_errors::Handler::with_emitter_and_flags(
         emitter,
         sopts.debugging_opts.diagnostic_handler_flags(can_emit_warnings),
     );
 
+    let diagnostic_baseline = sopts.diagnostic_baseline.as_ref().map(|path| {
+        let baseline =
+            DiagnosticBaseline::load(path.clone(), source_map.clone()).unwrap_or_else(|e| {
+                early_error(
+                    sopts.error_format,
+                    &format!("failed to load diagnostic baseline `{}`: {e}", path.display()),
+                )
+            });
+        Lrc::new(baseline)
+    });
+    if let Some(baseline) = &diagnostic_baseline {
+        span_diagnostic.set_diagnostic_filter(baseline.clone());
+    }
+
     let self_profiler = if let SwitchWithOptPath::Enabled(ref d) = sopts.debugging_opts.self_profile
     {
         let directory =
@@ -1253,6 +1345,7 @@ pub fn build_session(
             directory,
             sopts.crate_name.as_deref(),
             &sopts.debugging_opts.self_profile_events,
+            sopts.debugging_opts.self_profile_format,
         );
         match profiler {
             Ok(profiler) => Some(Arc::new(profiler)),
@@ -1279,6 +1372,23 @@ pub fn build_session(
         Lrc::new(SearchPath::from_sysroot_and_triple(&sysroot, target_triple))
     };
 
+    // The `lints.toml` files are unstable, so they are only looked up with
+    // `-Z lint-config-discovery`.
+    let lint_config_path = sopts.lint_config.clone().or_else(|| {
+        if !sopts.debugging_opts.lint_config_discovery {
+            return None;
+        }
+        local_crate_source_file.as_deref().and_then(LintConfig::discover)
+    });
+    let lint_config = lint_config_path.map(|path| {
+        LintConfig::load(path.clone()).unwrap_or_else(|e| {
+            early_error(
+                sopts.error_format,
+                &format!("failed to load lint configuration `{}`: {e}", path.display()),
+            )
+        })
+    });
+
     let file_path_mapping = sopts.file_path_mapping();
 
     let local_crate_source_file =
@@ -1289,6 +1399,10 @@ pub fn build_session(
         out_of_fuel: false,
     });
     let print_fuel = AtomicU64::new(0);
+    let fault_injection_count = AtomicU64::new(0);
+
+    let time_budget =
+        sopts.debugging_opts.time_budget.map(|ms| TimeBudget::new(Duration::from_millis(ms)));
 
     let cgu_reuse_tracker = if sopts.debugging_opts.query_dep_graph {
         CguReuseTracker::new()
@@ -1335,6 +1449,10 @@ pub fn build_session(
         code_stats: Default::default(),
         optimization_fuel,
         print_fuel,
+        fault_injection_count,
+        diagnostic_baseline,
+        lint_config,
+        time_budget,
         jobserver: jobserver::client(),
         driver_lint_caps,
         ctfe_backtrace,
@@ -1488,6 +1606,7 @@ fn early_error_handler(output: config::ErrorOutputType) -> rustc_errors::Handler
         config::ErrorOutputType::Json { pretty, json_rendered } => {
             Box::new(JsonEmitter::basic(pretty, json_rendered, None, fallback_bundle, None, false))
         }
+        config::ErrorOutputType::Sarif => Box::new(SarifEmitter::basic(None, fallback_bundle)),
     };
     rustc_errors::Handler::with_emitter(true, None, emitter)
 }
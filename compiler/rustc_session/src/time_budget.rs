@@ -0,0 +1,106 @@
+//! Compile-time budgets, for `-Z time-budget`.
+//!
+//! Like the self-profiler, the query system and the monomorphization collector time their
+//! activities, but here the time is attributed to items: an activity whose key is an item, like
+//! `typeck` of a function, is charged to that item, and the other activities, like the trait
+//! selection queries, are charged to the item of the activity they run in. Only self time is
+//! charged, so that an item is not charged for the items it causes to be checked. At the end of
+//! the compilation, the items that cost more than the budget are reported with the breakdown of
+//! their cost by activity.
+//!
+//! LLVM optimizes the items of a codegen unit together, so its time is not charged to items: the
+//! codegen backend reports the codegen units whose optimization is over budget instead.
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::sync::Lock;
+use rustc_span::def_id::DefId;
+
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+/// The time spent on an item.
+#[derive(Default, Debug)]
+pub struct ItemCost {
+    pub total: Duration,
+    pub by_activity: FxHashMap<&'static str, Duration>,
+}
+
+struct Frame {
+    activity: &'static str,
+    item: Option<DefId>,
+    start: Instant,
+    /// The time spent in the activities started while this one was running.
+    nested: Duration,
+}
+
+thread_local! {
+    /// The activities running on this thread, innermost last.
+    static STACK: RefCell<Vec<Frame>> = RefCell::new(vec![]);
+}
+
+pub struct TimeBudget {
+    budget: Duration,
+    items: Lock<FxHashMap<DefId, ItemCost>>,
+}
+
+impl TimeBudget {
+    pub fn new(budget: Duration) -> TimeBudget {
+        TimeBudget { budget, items: Default::default() }
+    }
+
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    /// Starts timing `activity`, which is charged to `item` if it is `Some`, or to the item of
+    /// the innermost running activity that has one. The activity ends when the guard is dropped.
+    pub fn start(&self, activity: &'static str, item: Option<DefId>) -> TimeBudgetGuard<'_> {
+        STACK.with(|stack| {
+            stack.borrow_mut().push(Frame {
+                activity,
+                item,
+                start: Instant::now(),
+                nested: Duration::ZERO,
+            })
+        });
+        TimeBudgetGuard { budget: self }
+    }
+
+    fn finish(&self) {
+        let charged = STACK.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            let frame = stack.pop()?;
+            let elapsed = frame.start.elapsed();
+            if let Some(outer) = stack.last_mut() {
+                outer.nested += elapsed;
+            }
+            let item = frame.item.or_else(|| stack.iter().rev().find_map(|frame| frame.item))?;
+            Some((item, frame.activity, elapsed.saturating_sub(frame.nested)))
+        });
+        if let Some((item, activity, self_time)) = charged {
+            let mut items = self.items.lock();
+            let cost = items.entry(item).or_default();
+            cost.total += self_time;
+            *cost.by_activity.entry(activity).or_default() += self_time;
+        }
+    }
+
+    /// Returns the items that cost more than the budget, most expensive first.
+    pub fn over_budget(&self) -> Vec<(DefId, ItemCost)> {
+        let mut items = self.items.lock();
+        let mut over_budget: Vec<_> =
+            items.drain().filter(|(_, cost)| cost.total > self.budget).collect();
+        over_budget.sort_by(|a, b| b.1.total.cmp(&a.1.total));
+        over_budget
+    }
+}
+
+pub struct TimeBudgetGuard<'a> {
+    budget: &'a TimeBudget,
+}
+
+impl Drop for TimeBudgetGuard<'_> {
+    fn drop(&mut self) {
+        self.budget.finish();
+    }
+}
@@ -0,0 +1,14 @@
+//! The WIP stable interface to rustc internals.
+//!
+//! Analysis tools consume the [`stable_mir`] module, which exposes function bodies, types and
+//! trait resolution through types that do not depend on `rustc_middle`. Its API is versioned
+//! independently of the compiler, see [`stable_mir::VERSION`]. The only entry point that still
+//! requires a `TyCtxt` is [`rustc_internal::run`].
+
+#![doc(html_root_url = "https://doc.rust-lang.org/nightly/nightly-rustc/")]
+#![feature(nll)]
+#![recursion_limit = "256"]
+
+pub mod rustc_internal;
+mod rustc_smir;
+pub mod stable_mir;
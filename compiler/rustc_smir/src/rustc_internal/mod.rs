@@ -0,0 +1,18 @@
+//! Module that implements the bridge between Stable MIR and internal compiler MIR.
+//!
+//! For that, we define APIs that will temporarily be public to 3P that exposes rustc internal APIs
+//! until stable MIR is complete.
+
+use crate::rustc_smir::{Tables, TablesWrapper};
+use crate::stable_mir;
+use rustc_middle::ty::TyCtxt;
+use std::cell::RefCell;
+
+/// Runs `f` with the Stable MIR of the crate being compiled by `tcx`.
+///
+/// This is meant to be called from the `after_analysis` callback of a `rustc_driver`
+/// wrapper. Inside `f`, the functions of [`stable_mir`] can be used to inspect the crate.
+pub fn run<'tcx>(tcx: TyCtxt<'tcx>, f: impl FnOnce()) {
+    let tables = TablesWrapper(RefCell::new(Tables::new(tcx)));
+    stable_mir::run(&tables, f);
+}
@@ -0,0 +1,654 @@
+//! Module that implements what will become the rustc side of Stable MIR.
+//!
+//! This module is responsible for building Stable MIR components from internal components.
+//!
+//! This module is not intended to be invoked directly by users. It will eventually
+//! become the public API of rustc that will be invoked by the `stable_mir` crate.
+//!
+//! For now, we are developing everything inside `rustc`, thus, we keep this module private.
+
+use crate::stable_mir::mir as smir;
+use crate::stable_mir::ty as sty;
+use crate::stable_mir::{self, Context, Crate, CrateItem, CrateItems, ImplDef, TraitDef};
+use rustc_ast::{InlineAsmTemplatePiece, Mutability};
+use rustc_data_structures::fx::FxIndexSet;
+use rustc_hir::def::DefKind;
+use rustc_infer::infer::TyCtxtInferExt;
+use rustc_middle::mir;
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_span::def_id::{CrateNum, DefId, LOCAL_CRATE};
+use rustc_span::Span;
+use rustc_trait_selection::infer::InferCtxtExt;
+use std::cell::RefCell;
+
+/// The internal values that the opaque identifiers of Stable MIR stand for. Each identifier is
+/// the index of the value in the corresponding set.
+pub struct Tables<'tcx> {
+    pub tcx: TyCtxt<'tcx>,
+    pub def_ids: FxIndexSet<DefId>,
+    pub types: FxIndexSet<ty::Ty<'tcx>>,
+    pub consts: FxIndexSet<ty::Const<'tcx>>,
+}
+
+impl<'tcx> Tables<'tcx> {
+    pub fn new(tcx: TyCtxt<'tcx>) -> Self {
+        Tables {
+            tcx,
+            def_ids: Default::default(),
+            types: Default::default(),
+            consts: Default::default(),
+        }
+    }
+
+    fn create_def_id(&mut self, did: DefId) -> stable_mir::DefId {
+        stable_mir::DefId(self.def_ids.insert_full(did).0)
+    }
+
+    fn crate_item(&mut self, did: DefId) -> CrateItem {
+        CrateItem(self.create_def_id(did))
+    }
+
+    fn def_id(&self, def_id: stable_mir::DefId) -> DefId {
+        self.def_ids[def_id.0]
+    }
+
+    fn intern_ty(&mut self, ty: ty::Ty<'tcx>) -> sty::Ty {
+        sty::Ty(self.types.insert_full(ty).0)
+    }
+
+    fn intern_const(&mut self, constant: ty::Const<'tcx>) -> sty::Const {
+        sty::Const(self.consts.insert_full(constant).0)
+    }
+
+    fn internal_args(&self, args: &sty::GenericArgs) -> ty::SubstsRef<'tcx> {
+        self.tcx.mk_substs(args.0.iter().map(|arg| match *arg {
+            sty::GenericArgKind::Lifetime(_) => self.tcx.lifetimes.re_erased.into(),
+            sty::GenericArgKind::Type(ty) => self.types[ty.0].into(),
+            sty::GenericArgKind::Const(constant) => self.consts[constant.0].into(),
+        }))
+    }
+
+    fn span(&self, span: Span) -> Option<stable_mir::Span> {
+        if span.is_dummy() {
+            return None;
+        }
+        let source_map = self.tcx.sess.source_map();
+        let start = source_map.lookup_char_pos(span.lo());
+        let end = source_map.lookup_char_pos(span.hi());
+        Some(stable_mir::Span {
+            file_name: start.file.name.prefer_remapped().to_string(),
+            line_start: start.line,
+            column_start: start.col.0 + 1,
+            line_end: end.line,
+            column_end: end.col.0 + 1,
+        })
+    }
+}
+
+/// The implementation of [`Context`] used by [`crate::rustc_internal::run`].
+pub struct TablesWrapper<'tcx>(pub RefCell<Tables<'tcx>>);
+
+impl<'tcx> Context for TablesWrapper<'tcx> {
+    fn local_crate(&self) -> Crate {
+        smir_crate(self.0.borrow().tcx, LOCAL_CRATE)
+    }
+
+    fn external_crates(&self) -> Vec<Crate> {
+        let tcx = self.0.borrow().tcx;
+        tcx.crates(()).iter().map(|&krate| smir_crate(tcx, krate)).collect()
+    }
+
+    fn all_local_items(&self) -> CrateItems {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        tcx.mir_keys(()).iter().map(|def_id| tables.crate_item(def_id.to_def_id())).collect()
+    }
+
+    fn all_local_traits(&self) -> Vec<TraitDef> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        tcx.hir()
+            .items()
+            .filter(|item| matches!(tcx.def_kind(item.def_id), DefKind::Trait))
+            .map(|item| TraitDef(tables.create_def_id(item.def_id.to_def_id())))
+            .collect()
+    }
+
+    fn entry_fn(&self) -> Option<CrateItem> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        tcx.entry_fn(()).map(|(did, _)| tables.crate_item(did))
+    }
+
+    fn item_name(&self, def_id: stable_mir::DefId) -> stable_mir::Symbol {
+        let tables = self.0.borrow();
+        with_no_trimmed_paths!(tables.tcx.def_path_str(tables.def_id(def_id)))
+    }
+
+    fn item_span(&self, def_id: stable_mir::DefId) -> Option<stable_mir::Span> {
+        let tables = self.0.borrow();
+        tables.span(tables.tcx.def_span(tables.def_id(def_id)))
+    }
+
+    fn item_ty(&self, def_id: stable_mir::DefId) -> sty::Ty {
+        let mut tables = self.0.borrow_mut();
+        let ty = tables.tcx.type_of(tables.def_id(def_id));
+        tables.intern_ty(ty)
+    }
+
+    fn mir_body(&self, item: CrateItem) -> Option<smir::Body> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let did = tables.def_id(item.0);
+        let body = if did.is_local() || tcx.is_mir_available(did) {
+            tcx.instance_mir(ty::InstanceDef::Item(ty::WithOptConstParam::unknown(did)))
+        } else if tcx.is_ctfe_mir_available(did) {
+            // Another crate only encodes the MIR of its `const fn`s used in const evaluation
+            // unless they can be inlined, and `optimized_mir` would not find it.
+            tcx.mir_for_ctfe(did)
+        } else {
+            return None;
+        };
+        Some(body.stable(&mut tables))
+    }
+
+    fn ty_kind(&self, ty: sty::Ty) -> sty::TyKind {
+        let mut tables = self.0.borrow_mut();
+        let ty = tables.types[ty.0];
+        ty.stable(&mut tables)
+    }
+
+    fn ty_to_string(&self, ty: sty::Ty) -> String {
+        with_no_trimmed_paths!(self.0.borrow().types[ty.0].to_string())
+    }
+
+    fn const_to_string(&self, constant: sty::Const) -> String {
+        with_no_trimmed_paths!(self.0.borrow().consts[constant.0].to_string())
+    }
+
+    fn trait_impls(&self, trait_def: TraitDef) -> Vec<ImplDef> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let did = tables.def_id(trait_def.0);
+        tcx.all_impls(did).map(|impl_did| ImplDef(tables.create_def_id(impl_did))).collect()
+    }
+
+    fn impl_trait_ref(&self, impl_def: ImplDef) -> stable_mir::TraitRef {
+        let mut tables = self.0.borrow_mut();
+        let did = tables.def_id(impl_def.0);
+        let trait_ref = tables.tcx.impl_trait_ref(did).expect("`ImplDef` of an inherent impl");
+        stable_mir::TraitRef {
+            def_id: TraitDef(tables.create_def_id(trait_ref.def_id)),
+            args: trait_ref.substs.stable(&mut tables),
+        }
+    }
+
+    fn implements_trait(&self, ty: sty::Ty, trait_def: TraitDef, args: &sty::GenericArgs) -> bool {
+        let tables = self.0.borrow();
+        let ty = tables.types[ty.0];
+        let trait_did = tables.def_id(trait_def.0);
+        let substs = tables.internal_args(args);
+        tables.tcx.infer_ctxt().enter(|infcx| {
+            infcx.type_implements_trait(trait_did, ty, substs, ty::ParamEnv::empty()).may_apply()
+        })
+    }
+
+    fn resolve_instance(
+        &self,
+        def: CrateItem,
+        args: &sty::GenericArgs,
+    ) -> Option<stable_mir::Instance> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let did = tables.def_id(def.0);
+        let substs = tables.internal_args(args);
+        let instance =
+            ty::Instance::resolve(tcx, ty::ParamEnv::reveal_all(), did, substs).ok().flatten()?;
+        let kind = match instance.def {
+            ty::InstanceDef::Item(_) => stable_mir::InstanceKind::Item,
+            ty::InstanceDef::Intrinsic(_) => stable_mir::InstanceKind::Intrinsic,
+            ty::InstanceDef::Virtual(_, index) => stable_mir::InstanceKind::Virtual(index),
+            _ => stable_mir::InstanceKind::Shim,
+        };
+        Some(stable_mir::Instance {
+            def: tables.crate_item(instance.def_id()),
+            args: instance.substs.stable(&mut tables),
+            kind,
+        })
+    }
+}
+
+fn smir_crate(tcx: TyCtxt<'_>, crate_num: CrateNum) -> Crate {
+    Crate {
+        id: crate_num.as_usize(),
+        name: tcx.crate_name(crate_num).to_string(),
+        is_local: crate_num == LOCAL_CRATE,
+    }
+}
+
+/// Trait used to convert between an internal MIR type to a Stable MIR type.
+pub(crate) trait Stable<'tcx> {
+    /// The stable representation of the type implementing Stable.
+    type T;
+    /// Converts an object to the equivalent Stable MIR representation.
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T;
+}
+
+impl<'tcx> Stable<'tcx> for mir::Body<'tcx> {
+    type T = smir::Body;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        let mut names = vec![None; self.local_decls.len()];
+        for var_debug_info in &self.var_debug_info {
+            if let mir::VarDebugInfoContents::Place(place) = var_debug_info.value {
+                if let Some(local) = place.as_local() {
+                    names[local.as_usize()].get_or_insert_with(|| var_debug_info.name.to_string());
+                }
+            }
+        }
+        smir::Body {
+            blocks: self
+                .basic_blocks()
+                .iter()
+                .map(|block| smir::BasicBlock {
+                    statements: block.statements.iter().map(|s| s.stable(tables)).collect(),
+                    terminator: block.terminator().stable(tables),
+                    is_cleanup: block.is_cleanup,
+                })
+                .collect(),
+            locals: self
+                .local_decls
+                .iter()
+                .zip(names)
+                .map(|(decl, name)| smir::LocalDecl {
+                    ty: tables.intern_ty(decl.ty),
+                    mutability: decl.mutability.stable(tables),
+                    name,
+                    span: tables.span(decl.source_info.span),
+                })
+                .collect(),
+            arg_count: self.arg_count,
+            span: tables.span(self.span),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::Statement<'tcx> {
+    type T = smir::Statement;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        use mir::StatementKind::*;
+        let kind = match &self.kind {
+            Assign(assign) => {
+                smir::StatementKind::Assign(assign.0.stable(tables), assign.1.stable(tables))
+            }
+            FakeRead(fake_read) => smir::StatementKind::FakeRead(fake_read.1.stable(tables)),
+            SetDiscriminant { place, variant_index } => smir::StatementKind::SetDiscriminant {
+                place: place.stable(tables),
+                variant_index: variant_index.as_usize(),
+            },
+            Deinit(place) => smir::StatementKind::Deinit(place.stable(tables)),
+            StorageLive(local) => smir::StatementKind::StorageLive(local.as_usize()),
+            StorageDead(local) => smir::StatementKind::StorageDead(local.as_usize()),
+            Retag(_, place) => smir::StatementKind::Retag(place.stable(tables)),
+            AscribeUserType(ascription, _) => {
+                smir::StatementKind::AscribeUserType(ascription.0.stable(tables))
+            }
+            Coverage(_) => smir::StatementKind::Coverage,
+            CopyNonOverlapping(copy) => smir::StatementKind::CopyNonOverlapping {
+                src: copy.src.stable(tables),
+                dst: copy.dst.stable(tables),
+                count: copy.count.stable(tables),
+            },
+            Nop => smir::StatementKind::Nop,
+        };
+        smir::Statement { kind, span: tables.span(self.source_info.span) }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::Rvalue<'tcx> {
+    type T = smir::Rvalue;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        use mir::Rvalue::*;
+        match self {
+            Use(op) => smir::Rvalue::Use(op.stable(tables)),
+            Repeat(op, len) => smir::Rvalue::Repeat(op.stable(tables), tables.intern_const(*len)),
+            Ref(region, kind, place) => smir::Rvalue::Ref(
+                sty::Region(region.to_string()),
+                kind.stable(tables),
+                place.stable(tables),
+            ),
+            ThreadLocalRef(did) => smir::Rvalue::ThreadLocalRef(tables.crate_item(*did)),
+            AddressOf(mutability, place) => {
+                smir::Rvalue::AddressOf(mutability.stable(tables), place.stable(tables))
+            }
+            Len(place) => smir::Rvalue::Len(place.stable(tables)),
+            Cast(kind, op, ty) => {
+                let kind = match kind {
+                    mir::CastKind::Misc => smir::CastKind::Misc,
+                    mir::CastKind::Pointer(_) => smir::CastKind::Pointer,
+                };
+                smir::Rvalue::Cast(kind, op.stable(tables), tables.intern_ty(*ty))
+            }
+            BinaryOp(bin_op, ops) => smir::Rvalue::BinaryOp(
+                bin_op.stable(tables),
+                ops.0.stable(tables),
+                ops.1.stable(tables),
+            ),
+            CheckedBinaryOp(bin_op, ops) => smir::Rvalue::CheckedBinaryOp(
+                bin_op.stable(tables),
+                ops.0.stable(tables),
+                ops.1.stable(tables),
+            ),
+            NullaryOp(null_op, ty) => {
+                let null_op = match null_op {
+                    mir::NullOp::SizeOf => smir::NullOp::SizeOf,
+                    mir::NullOp::AlignOf => smir::NullOp::AlignOf,
+                };
+                smir::Rvalue::NullaryOp(null_op, tables.intern_ty(*ty))
+            }
+            UnaryOp(un_op, op) => {
+                let un_op = match un_op {
+                    mir::UnOp::Not => smir::UnOp::Not,
+                    mir::UnOp::Neg => smir::UnOp::Neg,
+                };
+                smir::Rvalue::UnaryOp(un_op, op.stable(tables))
+            }
+            Discriminant(place) => smir::Rvalue::Discriminant(place.stable(tables)),
+            Aggregate(kind, ops) => smir::Rvalue::Aggregate(
+                kind.stable(tables),
+                ops.iter().map(|op| op.stable(tables)).collect(),
+            ),
+            ShallowInitBox(op, ty) => {
+                smir::Rvalue::ShallowInitBox(op.stable(tables), tables.intern_ty(*ty))
+            }
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::AggregateKind<'tcx> {
+    type T = smir::AggregateKind;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        match self {
+            mir::AggregateKind::Array(ty) => smir::AggregateKind::Array(tables.intern_ty(*ty)),
+            mir::AggregateKind::Tuple => smir::AggregateKind::Tuple,
+            mir::AggregateKind::Adt(did, variant, substs, _, active_field) => {
+                smir::AggregateKind::Adt(
+                    sty::AdtDef(tables.create_def_id(*did)),
+                    variant.as_usize(),
+                    substs.stable(tables),
+                    *active_field,
+                )
+            }
+            mir::AggregateKind::Closure(did, substs) => smir::AggregateKind::Closure(
+                sty::ClosureDef(tables.create_def_id(*did)),
+                substs.stable(tables),
+            ),
+            mir::AggregateKind::Generator(did, substs, _) => smir::AggregateKind::Generator(
+                sty::GeneratorDef(tables.create_def_id(*did)),
+                substs.stable(tables),
+            ),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::BinOp {
+    type T = smir::BinOp;
+    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+        match self {
+            mir::BinOp::Add => smir::BinOp::Add,
+            mir::BinOp::Sub => smir::BinOp::Sub,
+            mir::BinOp::Mul => smir::BinOp::Mul,
+            mir::BinOp::Div => smir::BinOp::Div,
+            mir::BinOp::Rem => smir::BinOp::Rem,
+            mir::BinOp::BitXor => smir::BinOp::BitXor,
+            mir::BinOp::BitAnd => smir::BinOp::BitAnd,
+            mir::BinOp::BitOr => smir::BinOp::BitOr,
+            mir::BinOp::Shl => smir::BinOp::Shl,
+            mir::BinOp::Shr => smir::BinOp::Shr,
+            mir::BinOp::Eq => smir::BinOp::Eq,
+            mir::BinOp::Lt => smir::BinOp::Lt,
+            mir::BinOp::Le => smir::BinOp::Le,
+            mir::BinOp::Ne => smir::BinOp::Ne,
+            mir::BinOp::Ge => smir::BinOp::Ge,
+            mir::BinOp::Gt => smir::BinOp::Gt,
+            mir::BinOp::Offset => smir::BinOp::Offset,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::BorrowKind {
+    type T = smir::BorrowKind;
+    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+        match *self {
+            mir::BorrowKind::Shared => smir::BorrowKind::Shared,
+            mir::BorrowKind::Shallow => smir::BorrowKind::Shallow,
+            mir::BorrowKind::Unique => smir::BorrowKind::Unique,
+            mir::BorrowKind::Mut { allow_two_phase_borrow } => {
+                smir::BorrowKind::Mut { allow_two_phase_borrow }
+            }
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::Operand<'tcx> {
+    type T = smir::Operand;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        match self {
+            mir::Operand::Copy(place) => smir::Operand::Copy(place.stable(tables)),
+            mir::Operand::Move(place) => smir::Operand::Move(place.stable(tables)),
+            mir::Operand::Constant(constant) => smir::Operand::Constant(smir::Constant {
+                ty: tables.intern_ty(constant.ty()),
+                literal: with_no_trimmed_paths!(constant.literal.to_string()),
+            }),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::Place<'tcx> {
+    type T = smir::Place;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        smir::Place {
+            local: self.local.as_usize(),
+            projection: self
+                .projection
+                .iter()
+                .map(|elem| match elem {
+                    mir::ProjectionElem::Deref => smir::ProjectionElem::Deref,
+                    mir::ProjectionElem::Field(field, ty) => {
+                        smir::ProjectionElem::Field(field.as_usize(), tables.intern_ty(ty))
+                    }
+                    mir::ProjectionElem::Index(local) => {
+                        smir::ProjectionElem::Index(local.as_usize())
+                    }
+                    mir::ProjectionElem::ConstantIndex { offset, min_length, from_end } => {
+                        smir::ProjectionElem::ConstantIndex { offset, min_length, from_end }
+                    }
+                    mir::ProjectionElem::Subslice { from, to, from_end } => {
+                        smir::ProjectionElem::Subslice { from, to, from_end }
+                    }
+                    mir::ProjectionElem::Downcast(_, variant) => {
+                        smir::ProjectionElem::Downcast(variant.as_usize())
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::Terminator<'tcx> {
+    type T = smir::Terminator;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        use mir::TerminatorKind::*;
+        let kind = match &self.kind {
+            Goto { target } => smir::TerminatorKind::Goto { target: target.as_usize() },
+            SwitchInt { discr, targets, .. } => smir::TerminatorKind::SwitchInt {
+                discr: discr.stable(tables),
+                targets: targets
+                    .iter()
+                    .map(|(value, target)| smir::SwitchTarget { value, target: target.as_usize() })
+                    .collect(),
+                otherwise: targets.otherwise().as_usize(),
+            },
+            Resume => smir::TerminatorKind::Resume,
+            Abort => smir::TerminatorKind::Abort,
+            Return => smir::TerminatorKind::Return,
+            Unreachable => smir::TerminatorKind::Unreachable,
+            Drop { place, target, unwind } => smir::TerminatorKind::Drop {
+                place: place.stable(tables),
+                target: target.as_usize(),
+                unwind: unwind.map(|unwind| unwind.as_usize()),
+            },
+            DropAndReplace { place, value, target, unwind } => {
+                smir::TerminatorKind::DropAndReplace {
+                    place: place.stable(tables),
+                    value: value.stable(tables),
+                    target: target.as_usize(),
+                    unwind: unwind.map(|unwind| unwind.as_usize()),
+                }
+            }
+            Call { func, args, destination, cleanup, .. } => smir::TerminatorKind::Call {
+                func: func.stable(tables),
+                args: args.iter().map(|arg| arg.stable(tables)).collect(),
+                destination: destination
+                    .as_ref()
+                    .map(|(place, target)| (place.stable(tables), target.as_usize())),
+                cleanup: cleanup.map(|cleanup| cleanup.as_usize()),
+            },
+            Assert { cond, expected, msg, target, cleanup } => smir::TerminatorKind::Assert {
+                cond: cond.stable(tables),
+                expected: *expected,
+                msg: format!("{:?}", msg),
+                target: target.as_usize(),
+                cleanup: cleanup.map(|cleanup| cleanup.as_usize()),
+            },
+            Yield { value, resume, resume_arg, drop } => smir::TerminatorKind::Yield {
+                value: value.stable(tables),
+                resume: resume.as_usize(),
+                resume_arg: resume_arg.stable(tables),
+                drop: drop.map(|drop| drop.as_usize()),
+            },
+            GeneratorDrop => smir::TerminatorKind::GeneratorDrop,
+            FalseEdge { real_target, imaginary_target } => smir::TerminatorKind::FalseEdge {
+                real_target: real_target.as_usize(),
+                imaginary_target: imaginary_target.as_usize(),
+            },
+            FalseUnwind { real_target, unwind } => smir::TerminatorKind::FalseUnwind {
+                real_target: real_target.as_usize(),
+                unwind: unwind.map(|unwind| unwind.as_usize()),
+            },
+            InlineAsm { template, destination, cleanup, .. } => smir::TerminatorKind::InlineAsm {
+                template: InlineAsmTemplatePiece::to_string(template),
+                destination: destination.map(|destination| destination.as_usize()),
+                cleanup: cleanup.map(|cleanup| cleanup.as_usize()),
+            },
+        };
+        smir::Terminator { kind, span: tables.span(self.source_info.span) }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for Mutability {
+    type T = sty::Mutability;
+    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+        match *self {
+            Mutability::Not => sty::Mutability::Not,
+            Mutability::Mut => sty::Mutability::Mut,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for ty::SubstsRef<'tcx> {
+    type T = sty::GenericArgs;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        sty::GenericArgs(
+            self.iter()
+                .map(|arg| match arg.unpack() {
+                    ty::subst::GenericArgKind::Lifetime(region) => {
+                        sty::GenericArgKind::Lifetime(sty::Region(region.to_string()))
+                    }
+                    ty::subst::GenericArgKind::Type(ty) => {
+                        sty::GenericArgKind::Type(tables.intern_ty(ty))
+                    }
+                    ty::subst::GenericArgKind::Const(constant) => {
+                        sty::GenericArgKind::Const(tables.intern_const(constant))
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<'tcx> Stable<'tcx> for ty::Ty<'tcx> {
+    type T = sty::TyKind;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        match *self.kind() {
+            ty::Bool => sty::TyKind::Bool,
+            ty::Char => sty::TyKind::Char,
+            ty::Int(int_ty) => sty::TyKind::Int(match int_ty {
+                ty::IntTy::Isize => sty::IntTy::Isize,
+                ty::IntTy::I8 => sty::IntTy::I8,
+                ty::IntTy::I16 => sty::IntTy::I16,
+                ty::IntTy::I32 => sty::IntTy::I32,
+                ty::IntTy::I64 => sty::IntTy::I64,
+                ty::IntTy::I128 => sty::IntTy::I128,
+            }),
+            ty::Uint(uint_ty) => sty::TyKind::Uint(match uint_ty {
+                ty::UintTy::Usize => sty::UintTy::Usize,
+                ty::UintTy::U8 => sty::UintTy::U8,
+                ty::UintTy::U16 => sty::UintTy::U16,
+                ty::UintTy::U32 => sty::UintTy::U32,
+                ty::UintTy::U64 => sty::UintTy::U64,
+                ty::UintTy::U128 => sty::UintTy::U128,
+            }),
+            ty::Float(float_ty) => sty::TyKind::Float(match float_ty {
+                ty::FloatTy::F32 => sty::FloatTy::F32,
+                ty::FloatTy::F64 => sty::FloatTy::F64,
+            }),
+            ty::Adt(adt_def, substs) => sty::TyKind::Adt(
+                sty::AdtDef(tables.create_def_id(adt_def.did())),
+                substs.stable(tables),
+            ),
+            ty::Foreign(did) => sty::TyKind::Foreign(sty::ForeignDef(tables.create_def_id(did))),
+            ty::Str => sty::TyKind::Str,
+            ty::Array(ty, len) => {
+                sty::TyKind::Array(tables.intern_ty(ty), tables.intern_const(len))
+            }
+            ty::Slice(ty) => sty::TyKind::Slice(tables.intern_ty(ty)),
+            ty::RawPtr(ty::TypeAndMut { ty, mutbl }) => {
+                sty::TyKind::RawPtr(tables.intern_ty(ty), mutbl.stable(tables))
+            }
+            ty::Ref(region, ty, mutbl) => sty::TyKind::Ref(
+                sty::Region(region.to_string()),
+                tables.intern_ty(ty),
+                mutbl.stable(tables),
+            ),
+            ty::FnDef(did, substs) => {
+                sty::TyKind::FnDef(tables.crate_item(did), substs.stable(tables))
+            }
+            ty::Closure(did, substs) => sty::TyKind::Closure(
+                sty::ClosureDef(tables.create_def_id(did)),
+                substs.stable(tables),
+            ),
+            ty::Generator(did, substs, _) => sty::TyKind::Generator(
+                sty::GeneratorDef(tables.create_def_id(did)),
+                substs.stable(tables),
+            ),
+            ty::Never => sty::TyKind::Never,
+            ty::Tuple(fields) => {
+                sty::TyKind::Tuple(fields.iter().map(|ty| tables.intern_ty(ty)).collect())
+            }
+            ty::Param(param) => sty::TyKind::Param(sty::ParamTy {
+                index: param.index,
+                name: param.name.to_string(),
+            }),
+            ty::FnPtr(_)
+            | ty::Dynamic(..)
+            | ty::GeneratorWitness(_)
+            | ty::Projection(_)
+            | ty::Opaque(..)
+            | ty::Bound(..)
+            | ty::Placeholder(_)
+            | ty::Infer(_)
+            | ty::Error(_) => sty::TyKind::Other(with_no_trimmed_paths!(self.to_string())),
+        }
+    }
+}
@@ -0,0 +1,237 @@
+//! Module that implements the public interface to the Stable MIR.
+//!
+//! This module shall contain all type definitions and APIs that we expect 3P tools to invoke to
+//! interact with the compiler.
+//!
+//! The goal is to eventually move this module to its own crate which shall be published on
+//! [crates.io](https://crates.io).
+//!
+//! Nothing in this module may refer to a type of `rustc_middle` or any other compiler crate, so
+//! that its API can follow [`VERSION`] instead of the compiler's own churn. The translation from
+//! the compiler's internal representation lives in `rustc_smir`.
+
+use std::cell::Cell;
+
+pub mod mir;
+pub mod ty;
+
+use self::ty::{GenericArgs, Ty, TyKind};
+
+/// The version of the Stable MIR interface. Breaking changes to any type or function of
+/// this module bump the major version, additions bump the minor version.
+pub const VERSION: (u32, u32) = (0, 1);
+
+/// Use String for now but we should replace it.
+pub type Symbol = String;
+
+/// The number that identifies a crate.
+pub type CrateNum = usize;
+
+/// A unique identification number for each item accessible for the current compilation unit.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct DefId(pub(crate) usize);
+
+/// A list of crate items.
+pub type CrateItems = Vec<CrateItem>;
+
+/// Holds information about a crate.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Crate {
+    pub id: CrateNum,
+    pub name: Symbol,
+    pub is_local: bool,
+}
+
+/// A source location, with 1-based lines and columns.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Span {
+    pub file_name: String,
+    pub line_start: usize,
+    pub column_start: usize,
+    pub line_end: usize,
+    pub column_end: usize,
+}
+
+/// Holds information about an item in the crate that has a MIR body, such as a function or
+/// a constant.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct CrateItem(pub(crate) DefId);
+
+impl CrateItem {
+    /// Returns the MIR body of this item after optimizations, or `None` if the item comes from
+    /// another crate that does not provide its MIR. For a `const fn` of another crate that only
+    /// provides the MIR used in const evaluation, returns that MIR.
+    pub fn body(&self) -> Option<mir::Body> {
+        with(|cx| cx.mir_body(*self))
+    }
+
+    /// Returns the path of this item, e.g. `std::vec::Vec::new`.
+    pub fn name(&self) -> Symbol {
+        with(|cx| cx.item_name(self.0))
+    }
+
+    /// Returns the span of the definition of this item, unless it comes from another crate and
+    /// its source is not available.
+    pub fn span(&self) -> Option<Span> {
+        with(|cx| cx.item_span(self.0))
+    }
+
+    /// Returns the type of this item, e.g. a `TyKind::FnDef` for functions.
+    pub fn ty(&self) -> Ty {
+        with(|cx| cx.item_ty(self.0))
+    }
+}
+
+/// A trait definition.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct TraitDef(pub(crate) DefId);
+
+impl TraitDef {
+    pub fn name(&self) -> Symbol {
+        with(|cx| cx.item_name(self.0))
+    }
+
+    /// Returns all impls of this trait known to the current compilation, including the
+    /// impls in other crates.
+    pub fn impls(&self) -> Vec<ImplDef> {
+        with(|cx| cx.trait_impls(*self))
+    }
+}
+
+/// A trait impl definition.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ImplDef(pub(crate) DefId);
+
+impl ImplDef {
+    pub fn span(&self) -> Option<Span> {
+        with(|cx| cx.item_span(self.0))
+    }
+
+    /// Returns the trait implemented by this impl, with the `Self` type as first argument.
+    pub fn trait_ref(&self) -> TraitRef {
+        with(|cx| cx.impl_trait_ref(*self))
+    }
+}
+
+/// A reference to a trait together with its generic arguments, the first of which is the
+/// `Self` type.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TraitRef {
+    pub def_id: TraitDef,
+    pub args: GenericArgs,
+}
+
+/// A function item, together with the generic arguments it is called with, resolved to the
+/// definition that will be executed.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Instance {
+    pub def: CrateItem,
+    pub args: GenericArgs,
+    pub kind: InstanceKind,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InstanceKind {
+    /// A user-defined function or constant with a MIR body.
+    Item,
+    /// A compiler intrinsic, which has no MIR body.
+    Intrinsic,
+    /// A call through a trait object, `usize` being the index in the vtable.
+    Virtual(usize),
+    /// A compiler-generated body, such as drop glue or a closure shim.
+    Shim,
+}
+
+impl Instance {
+    /// Resolves a call to the function `def` with the generic arguments `args`, selecting the
+    /// impl that provides the function in the case of trait methods. Returns `None` if the
+    /// call cannot be resolved yet, e.g. because `args` still contain type parameters.
+    pub fn resolve(def: CrateItem, args: &GenericArgs) -> Option<Instance> {
+        with(|cx| cx.resolve_instance(def, args))
+    }
+}
+
+/// Returns the crate being compiled.
+pub fn local_crate() -> Crate {
+    with(|cx| cx.local_crate())
+}
+
+/// Returns all external crates, i.e. all the dependencies of the local crate.
+pub fn external_crates() -> Vec<Crate> {
+    with(|cx| cx.external_crates())
+}
+
+/// Returns all items of the local crate that have a MIR body.
+pub fn all_local_items() -> CrateItems {
+    with(|cx| cx.all_local_items())
+}
+
+/// Returns the entry function of the crate, i.e. its `main` function, if there is one.
+pub fn entry_fn() -> Option<CrateItem> {
+    with(|cx| cx.entry_fn())
+}
+
+/// Returns all trait definitions of the local crate.
+pub fn all_local_traits() -> Vec<TraitDef> {
+    with(|cx| cx.all_local_traits())
+}
+
+/// Returns whether `ty` implements the trait `trait_def` with the remaining trait generic
+/// arguments `args` (not including `Self`), in an empty environment. Lifetimes are ignored.
+pub fn implements_trait(ty: Ty, trait_def: TraitDef, args: &GenericArgs) -> bool {
+    with(|cx| cx.implements_trait(ty, trait_def, args))
+}
+
+/// The interface the compiler provides to the functions of this module.
+///
+/// The methods take `&self` so that calls can be nested, implementors use interior
+/// mutability to record the translated items.
+pub trait Context {
+    fn local_crate(&self) -> Crate;
+    fn external_crates(&self) -> Vec<Crate>;
+    fn all_local_items(&self) -> CrateItems;
+    fn all_local_traits(&self) -> Vec<TraitDef>;
+    fn entry_fn(&self) -> Option<CrateItem>;
+    fn item_name(&self, def_id: DefId) -> Symbol;
+    fn item_span(&self, def_id: DefId) -> Option<Span>;
+    fn item_ty(&self, def_id: DefId) -> Ty;
+    fn mir_body(&self, item: CrateItem) -> Option<mir::Body>;
+    fn ty_kind(&self, ty: Ty) -> TyKind;
+    fn ty_to_string(&self, ty: Ty) -> String;
+    fn const_to_string(&self, constant: ty::Const) -> String;
+    fn trait_impls(&self, trait_def: TraitDef) -> Vec<ImplDef>;
+    fn impl_trait_ref(&self, impl_def: ImplDef) -> TraitRef;
+    fn implements_trait(&self, ty: Ty, trait_def: TraitDef, args: &GenericArgs) -> bool;
+    fn resolve_instance(&self, def: CrateItem, args: &GenericArgs) -> Option<Instance>;
+}
+
+thread_local! {
+    /// A thread local variable that stores a pointer to the context used by the functions of
+    /// this module, see [`run`].
+    static TLV: Cell<*const ()> = const { Cell::new(std::ptr::null()) };
+}
+
+/// Makes `context` available to the functions of this module while `f` runs.
+pub fn run(context: &dyn Context, f: impl FnOnce()) {
+    struct Reset(*const ());
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            TLV.with(|tlv| tlv.set(self.0));
+        }
+    }
+
+    let ptr: *const &dyn Context = &context;
+    let _reset = Reset(TLV.with(|tlv| tlv.replace(ptr as *const ())));
+    f();
+}
+
+/// Loads the current context and calls a function with it.
+pub(crate) fn with<R>(f: impl FnOnce(&dyn Context) -> R) -> R {
+    TLV.with(|tlv| {
+        let ptr = tlv.get();
+        assert!(!ptr.is_null(), "Stable MIR used outside of `stable_mir::run`");
+        // SAFETY: `run` only stores pointers to a `&dyn Context` that outlives the call to `f`
+        // and resets the pointer before returning.
+        f(unsafe { *(ptr as *const &dyn Context) })
+    })
+}
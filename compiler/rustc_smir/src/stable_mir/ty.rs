@@ -0,0 +1,126 @@
+use super::{with, CrateItem, DefId, Symbol};
+use std::fmt;
+
+/// A type. Types are interned by the compiler, use [`Ty::kind`] to inspect them.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ty(pub(crate) usize);
+
+impl Ty {
+    pub fn kind(&self) -> TyKind {
+        with(|cx| cx.ty_kind(*self))
+    }
+}
+
+impl fmt::Debug for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&with(|cx| cx.ty_to_string(*self)))
+    }
+}
+
+/// A constant, such as an array length or a const generic argument.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Const(pub(crate) usize);
+
+impl fmt::Debug for Const {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&with(|cx| cx.const_to_string(*self)))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum TyKind {
+    Bool,
+    Char,
+    Int(IntTy),
+    Uint(UintTy),
+    Float(FloatTy),
+    Adt(AdtDef, GenericArgs),
+    Foreign(ForeignDef),
+    Str,
+    Array(Ty, Const),
+    Slice(Ty),
+    RawPtr(Ty, Mutability),
+    Ref(Region, Ty, Mutability),
+    FnDef(CrateItem, GenericArgs),
+    Closure(ClosureDef, GenericArgs),
+    Generator(GeneratorDef, GenericArgs),
+    Never,
+    Tuple(Vec<Ty>),
+    Param(ParamTy),
+    /// Types that are not (yet) exposed by Stable MIR, such as function pointers, trait
+    /// objects and projections, in their pretty-printed form.
+    Other(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IntTy {
+    Isize,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UintTy {
+    Usize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FloatTy {
+    F32,
+    F64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mutability {
+    Not,
+    Mut,
+}
+
+/// A region, in its pretty-printed form. Regions are erased after borrow checking, so the
+/// MIR of optimized bodies only contains `'_`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region(pub String);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AdtDef(pub(crate) DefId);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ForeignDef(pub(crate) DefId);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ClosureDef(pub(crate) DefId);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GeneratorDef(pub(crate) DefId);
+
+impl AdtDef {
+    pub fn name(&self) -> Symbol {
+        with(|cx| cx.item_name(self.0))
+    }
+}
+
+/// A type parameter, such as `T` in `fn foo<T>()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParamTy {
+    pub index: u32,
+    pub name: Symbol,
+}
+
+/// The generic arguments of an item, such as the `u8` in `Vec<u8>`.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct GenericArgs(pub Vec<GenericArgKind>);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GenericArgKind {
+    Lifetime(Region),
+    Type(Ty),
+    Const(Const),
+}
@@ -12,7 +12,9 @@
 use crate::infer::error_reporting::{TyCategory, TypeAnnotationNeeded as ErrorCode};
 use crate::infer::type_variable::{TypeVariableOrigin, TypeVariableOriginKind};
 use crate::infer::{self, InferCtxt, TyCtxtInferExt};
+use rustc_data_structures::captures::Captures;
 use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::OnDrop;
 use rustc_errors::{
     pluralize, struct_span_err, Applicability, Diagnostic, DiagnosticBuilder, ErrorGuaranteed,
     MultiSpan, Style,
@@ -274,6 +276,7 @@ fn report_selection_error(
         error: &SelectionError<'tcx>,
         fallback_has_occurred: bool,
     ) {
+        let _trace = solver_trace_guard(self, root_obligation);
         let tcx = self.tcx;
         let mut span = obligation.cause.span;
 
@@ -1549,6 +1552,7 @@ fn report_projection_error(
         obligation: &PredicateObligation<'tcx>,
         error: &MismatchedProjectionTypes<'tcx>,
     ) {
+        let _trace = solver_trace_guard(self, obligation);
         let predicate = self.resolve_vars_if_possible(obligation.predicate);
 
         if predicate.references_error() {
@@ -1976,6 +1980,7 @@ fn maybe_report_ambiguity(
         obligation: &PredicateObligation<'tcx>,
         body_id: Option<hir::BodyId>,
     ) {
+        let _trace = solver_trace_guard(self, obligation);
         // Unable to successfully determine, probably means
         // insufficient type information, but could mean
         // ambiguous impls. The latter *ought* to be a
@@ -2527,6 +2532,27 @@ fn is_recursive_obligation(
 
 /// Look for type `param` in an ADT being used only through a reference to confirm that suggesting
 /// `param: ?Sized` would be a valid constraint.
+/// Under `-Z trait-solver-trace`, follows the errors reported while the returned guard is alive
+/// with the selection tree of `obligation`. The reporting functions hold it for their whole body,
+/// so the tree is noted whichever path emits the error, and not when they bail out silently.
+fn solver_trace_guard<'a, 'tcx>(
+    infcx: &'a InferCtxt<'a, 'tcx>,
+    obligation: &'a PredicateObligation<'tcx>,
+) -> Option<OnDrop<impl Fn() + Captures<'tcx> + 'a>> {
+    let sess = infcx.tcx.sess;
+    if !sess.opts.debugging_opts.trait_solver_trace {
+        return None;
+    }
+    let err_count = sess.err_count();
+    Some(OnDrop(move || {
+        if sess.err_count() > err_count && !std::thread::panicking() {
+            let predicate = infcx.resolve_vars_if_possible(obligation.predicate);
+            let trace = SelectionContext::new(infcx).trace_obligation(obligation);
+            sess.note_without_error(&format!("selection tree of `{}`:\n{}", predicate, trace));
+        }
+    }))
+}
+
 struct FindTypeParam {
     param: rustc_span::Symbol,
     invalid_spans: Vec<Span>,
@@ -1,3 +1,4 @@
+use crate::infer::inference_trace::InferenceStepKind;
 use crate::infer::{InferCtxt, TyOrConstInferVar};
 use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::obligation_forest::ProcessResult;
@@ -216,6 +217,10 @@ fn register_predicate_obligation(
         assert!(!infcx.is_in_snapshot() || self.usable_in_snapshot);
 
         super::relationships::update(self, infcx, &obligation);
+        infcx.record_inference_step(
+            obligation.cause.span,
+            InferenceStepKind::Obligation(obligation.predicate),
+        );
 
         self.predicates
             .register_obligation(PendingPredicateObligation { obligation, stalled_on: vec![] });
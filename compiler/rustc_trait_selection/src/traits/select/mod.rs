@@ -51,6 +51,7 @@
 
 mod candidate_assembly;
 mod confirmation;
+mod trace;
 
 #[derive(Clone, Debug)]
 pub enum IntercrateAmbiguityCause {
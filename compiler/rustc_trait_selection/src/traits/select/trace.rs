@@ -0,0 +1,179 @@
+//! Selection trees for `-Z trait-solver-trace`.
+//!
+//! When a goal fails to hold, error reporting replays its selection in a probe: the candidates of
+//! each trait goal are assembled, then confirmed one by one, and their nested obligations are
+//! traced in turn. Only the goals that do not hold are expanded, so the tree shows the path to the
+//! failure and why each candidate on it was rejected. Like in evaluation, a trait goal that is
+//! already on the stack is reported as a cycle instead of being expanded again. A projection goal
+//! is expanded into what its associated type normalizes to and the trait goal it relies on.
+
+use super::EvaluationResult::*;
+use super::SelectionCandidate::*;
+use super::{
+    EvaluationResult, OverflowError, ProvisionalEvaluationCache, SelectionCandidate,
+    SelectionContext, TraitObligationStack, TraitObligationStackList,
+};
+use crate::traits::{normalize_projection_type, PredicateObligation};
+use rustc_middle::ty::{self, ToPredicate};
+
+/// The depth, in goals, after which the goals of a selection tree are not expanded anymore.
+const MAX_TRACE_DEPTH: usize = 16;
+
+impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
+    /// Renders the selection tree of `obligation`, one goal or candidate per line.
+    pub fn trace_obligation(&mut self, obligation: &PredicateObligation<'tcx>) -> String {
+        let mut lines = vec![];
+        self.infcx.probe(|_| {
+            let cache = ProvisionalEvaluationCache::default();
+            self.trace_goal(TraitObligationStackList::empty(&cache), obligation, 0, &mut lines)
+        });
+        lines.join("\n")
+    }
+
+    fn trace_goal<'o>(
+        &mut self,
+        previous_stack: TraitObligationStackList<'o, 'tcx>,
+        obligation: &PredicateObligation<'tcx>,
+        depth: usize,
+        lines: &mut Vec<String>,
+    ) {
+        let indent = "    ".repeat(depth);
+        let predicate = self.infcx.resolve_vars_if_possible(obligation.predicate);
+        let result = self.evaluate_root_obligation(obligation);
+        let Some(trait_predicate) = predicate.to_opt_poly_trait_pred() else {
+            lines.push(format!("{}`{}` {}", indent, predicate, describe_evaluation(result)));
+            if matches!(result, Ok(result) if result.must_apply_modulo_regions()) {
+                return;
+            }
+            if let ty::PredicateKind::Projection(projection) = predicate.kind().skip_binder() {
+                let projection = predicate.kind().rebind(projection);
+                self.trace_projection(previous_stack, obligation, projection, depth + 1, lines);
+            }
+            return;
+        };
+
+        let trait_obligation = obligation.with(trait_predicate);
+        let stack = self.push_stack(previous_stack, &trait_obligation);
+        if stack.iter().skip(1).any(|prev| prev.fresh_trait_pred == stack.fresh_trait_pred) {
+            lines.push(format!("{}`{}` cycles back to a goal above", indent, predicate));
+            return;
+        }
+        lines.push(format!("{}`{}` {}", indent, predicate, describe_evaluation(result)));
+        if matches!(result, Ok(result) if result.must_apply_modulo_regions()) {
+            return;
+        }
+        if depth / 2 >= MAX_TRACE_DEPTH {
+            lines.push(format!("{}    ...", indent));
+            return;
+        }
+
+        match self.assemble_candidates(&stack) {
+            Ok(candidates) => {
+                if candidates.ambiguous {
+                    lines.push(format!("{}    the candidates are ambiguous", indent));
+                } else if candidates.vec.is_empty() {
+                    lines.push(format!("{}    no candidates", indent));
+                }
+                for candidate in candidates.vec {
+                    self.trace_candidate(&stack, candidate, depth + 1, lines);
+                }
+            }
+            Err(error) => {
+                lines.push(format!("{}    error assembling the candidates: {:?}", indent, error));
+            }
+        }
+    }
+
+    /// Traces a projection goal that does not hold: what its associated type normalizes to, and
+    /// the trait goal the projection relies on.
+    fn trace_projection<'o>(
+        &mut self,
+        previous_stack: TraitObligationStackList<'o, 'tcx>,
+        obligation: &PredicateObligation<'tcx>,
+        projection: ty::PolyProjectionPredicate<'tcx>,
+        depth: usize,
+        lines: &mut Vec<String>,
+    ) {
+        let indent = "    ".repeat(depth);
+        if let Some(projection_ty) = projection.map_bound(|p| p.projection_ty).no_bound_vars() {
+            let infcx = self.infcx;
+            infcx.probe(|_| {
+                let normalized = normalize_projection_type(
+                    self,
+                    obligation.param_env,
+                    projection_ty,
+                    obligation.cause.clone(),
+                    0,
+                    &mut vec![],
+                );
+                let normalized = infcx.resolve_vars_if_possible(normalized);
+                // An associated type that cannot be normalized comes back as a fresh variable.
+                if !normalized.ty().map_or(false, |ty| ty.is_ty_var()) {
+                    lines.push(format!(
+                        "{}`{}` normalizes to `{}`",
+                        indent, projection_ty, normalized
+                    ));
+                }
+            });
+        }
+
+        let tcx = self.tcx();
+        let trait_predicate = projection.required_poly_trait_ref(tcx).without_const();
+        let trait_obligation = obligation.with(trait_predicate.to_predicate(tcx));
+        self.trace_goal(previous_stack, &trait_obligation, depth, lines);
+    }
+
+    fn trace_candidate<'o>(
+        &mut self,
+        stack: &TraitObligationStack<'o, 'tcx>,
+        candidate: SelectionCandidate<'tcx>,
+        depth: usize,
+        lines: &mut Vec<String>,
+    ) {
+        let indent = "    ".repeat(depth);
+        let result = self.evaluate_candidate(stack, &candidate);
+        lines.push(format!(
+            "{}candidate {}: {}",
+            indent,
+            self.describe_candidate(&candidate),
+            describe_evaluation(result)
+        ));
+        if matches!(result, Ok(result) if result.must_apply_modulo_regions()) {
+            return;
+        }
+
+        self.infcx.probe(|_| match self.confirm_candidate(stack.obligation, candidate) {
+            Ok(selection) => {
+                for nested in selection.nested_obligations() {
+                    self.trace_goal(stack.list(), &nested, depth + 1, lines);
+                }
+            }
+            Err(error) => lines.push(format!("{}    rejected: {:?}", indent, error)),
+        });
+    }
+
+    fn describe_candidate(&self, candidate: &SelectionCandidate<'tcx>) -> String {
+        let tcx = self.tcx();
+        match *candidate {
+            ImplCandidate(def_id) => format!(
+                "`{}` at {}",
+                tcx.def_path_str(def_id),
+                tcx.sess.source_map().span_to_embeddable_string(tcx.def_span(def_id))
+            ),
+            ParamCandidate(bound) => format!("the where clause `{}`", bound),
+            ProjectionCandidate(_) => "a bound of the associated type".to_string(),
+            ObjectCandidate(_) => "a supertrait of the trait object".to_string(),
+            AutoImplCandidate(def_id) => format!("the auto trait `{}`", tcx.def_path_str(def_id)),
+            _ => format!("the builtin `{:?}`", candidate),
+        }
+    }
+}
+
+fn describe_evaluation(result: Result<EvaluationResult, OverflowError>) -> &'static str {
+    match result {
+        Ok(result) if result.must_apply_modulo_regions() => "holds",
+        Ok(EvaluatedToErr) => "fails",
+        Ok(_) => "may hold",
+        Err(_) => "overflows",
+    }
+}
@@ -5,6 +5,7 @@
     graph::{iterate::DepthFirstSearch, vec_graph::VecGraph},
     stable_set::FxHashSet,
 };
+use rustc_infer::infer::inference_trace::InferenceStepKind;
 use rustc_middle::ty::{self, Ty};
 
 impl<'tcx> FnCtxt<'_, 'tcx> {
@@ -122,6 +123,7 @@ fn fallback_if_possible(
             .type_var_origin(ty)
             .map(|origin| origin.span)
             .unwrap_or(rustc_span::DUMMY_SP);
+        self.record_inference_step(span, InferenceStepKind::Fallback(ty, fallback));
         self.demand_eqtype(span, ty, fallback);
         true
     }
@@ -481,6 +481,7 @@ fn typeck_with_fallback<'tcx>(
         }
 
         fcx.select_all_obligations_or_error();
+        fcx.explain_inference();
 
         if fn_sig.is_some() {
             fcx.regionck_fn(id, body, span, wf_tys);
@@ -103,7 +103,7 @@
 use rustc_hir as hir;
 use rustc_hir::def_id::DefId;
 use rustc_hir::{Node, CRATE_HIR_ID};
-use rustc_infer::infer::{InferOk, TyCtxtInferExt};
+use rustc_infer::infer::{inference_trace, InferOk, TyCtxtInferExt};
 use rustc_infer::traits::TraitEngineExt as _;
 use rustc_middle::middle;
 use rustc_middle::ty::query::Providers;
@@ -491,6 +491,15 @@ pub fn provide(providers: &mut Providers) {
 pub fn check_crate(tcx: TyCtxt<'_>) -> Result<(), ErrorGuaranteed> {
     let _prof_timer = tcx.sess.timer("type_check_crate");
 
+    if let Some(position) = &tcx.sess.opts.debugging_opts.explain_inference
+        && inference_trace::source_position_to_pos(tcx.sess, position).is_none()
+    {
+        tcx.sess.warn(&format!(
+            "`-Z explain-inference={}:{}:{}` is not a position of the crate",
+            position.file, position.line, position.col
+        ));
+    }
+
     // this ensures that later parts of type checking can assume that items
     // have valid types and not error
     // FIXME(matthewjasper) We shouldn't need to use `track_errors`.
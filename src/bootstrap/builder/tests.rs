@@ -514,6 +514,8 @@ fn test_with_no_doc_stage0() {
             doc_tests: DocTests::No,
             bless: false,
             force_rerun: false,
+            rerun_failed: false,
+            shard: None,
             compare_mode: None,
             rustfix_coverage: false,
             pass: None,
@@ -585,6 +587,8 @@ fn test_docs() {
             doc_tests: DocTests::Yes,
             bless: false,
             force_rerun: false,
+            rerun_failed: false,
+            shard: None,
             compare_mode: None,
             rustfix_coverage: false,
             pass: None,
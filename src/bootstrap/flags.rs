@@ -108,6 +108,8 @@ pub enum Subcommand {
         /// Whether to automatically update stderr/stdout files
         bless: bool,
         force_rerun: bool,
+        rerun_failed: bool,
+        shard: Option<String>,
         compare_mode: Option<String>,
         pass: Option<String>,
         run: Option<String>,
@@ -280,6 +282,13 @@ pub fn parse(args: &[String]) -> Flags {
                 opts.optflag("", "doc", "only run doc tests");
                 opts.optflag("", "bless", "update all stderr/stdout files of failing ui tests");
                 opts.optflag("", "force-rerun", "rerun tests even if the inputs are unchanged");
+                opts.optflag("", "rerun-failed", "only run tests that failed in the previous run");
+                opts.optopt(
+                    "",
+                    "shard",
+                    "only run the N-th of M stable partitions of the test suite",
+                    "N/M",
+                );
                 opts.optopt(
                     "",
                     "compare-mode",
@@ -544,6 +553,8 @@ pub fn parse(args: &[String]) -> Flags {
                 paths,
                 bless: matches.opt_present("bless"),
                 force_rerun: matches.opt_present("force-rerun"),
+                rerun_failed: matches.opt_present("rerun-failed"),
+                shard: matches.opt_str("shard"),
                 compare_mode: matches.opt_str("compare-mode"),
                 pass: matches.opt_str("pass"),
                 run: matches.opt_str("run"),
@@ -751,6 +762,20 @@ pub fn force_rerun(&self) -> bool {
         }
     }
 
+    pub fn rerun_failed(&self) -> bool {
+        match *self {
+            Subcommand::Test { rerun_failed, .. } => rerun_failed,
+            _ => false,
+        }
+    }
+
+    pub fn shard(&self) -> Option<&str> {
+        match *self {
+            Subcommand::Test { ref shard, .. } => shard.as_ref().map(|s| &s[..]),
+            _ => None,
+        }
+    }
+
     pub fn rustfix_coverage(&self) -> bool {
         match *self {
             Subcommand::Test { rustfix_coverage, .. } => rustfix_coverage,
@@ -1353,6 +1353,14 @@ fn run(self, builder: &Builder<'_>) {
             cmd.arg("--force-rerun");
         }
 
+        if builder.config.cmd.rerun_failed() {
+            cmd.arg("--rerun-failed");
+        }
+
+        if let Some(shard) = builder.config.cmd.shard() {
+            cmd.arg("--shard").arg(shard);
+        }
+
         let compare_mode =
             builder.config.cmd.compare_mode().or_else(|| {
                 if builder.config.test_compare_mode { self.compare_mode } else { None }
@@ -374,6 +374,7 @@ fn run(self, builder: &Builder<'_>) -> PathBuf {
     JsonDocCk, "src/tools/jsondocck", "jsondocck";
     HtmlChecker, "src/tools/html-checker", "html-checker";
     BumpStage0, "src/tools/bump-stage0", "bump-stage0";
+    MirBisect, "src/tools/mir-bisect", "mir-bisect";
 );
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, Ord, PartialOrd)]
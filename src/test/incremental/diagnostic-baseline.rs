@@ -0,0 +1,20 @@
+// ignore-tidy-linelength
+
+// Checks that `--diagnostic-baseline` sees the warnings replayed from the incremental cache: the
+// baseline is recorded by a warm build in cfail2, and suppresses the replayed warning in cfail3.
+// The baseline is kept in the incremental directory, which is cleared before cfail1.
+
+// revisions: cfail1 cfail2 cfail3
+//[cfail2] compile-flags: -Z unstable-options
+//[cfail2] compile-flags: --diagnostic-baseline={{build-base}}/diagnostic-baseline/diagnostic-baseline.inc/baseline.txt
+//[cfail3] compile-flags: -Z unstable-options
+//[cfail3] compile-flags: --diagnostic-baseline={{build-base}}/diagnostic-baseline/diagnostic-baseline.inc/baseline.txt
+//[cfail3] forbid-output: unused variable
+//[cfail3] forbid-output: no longer match
+// build-pass
+
+#![crate_type = "rlib"]
+
+pub fn old() {
+    let x = 0; //[cfail1,cfail2]~ WARNING unused variable
+}
@@ -0,0 +1,8 @@
+pub fn unused_parens() -> i32 {
+    let x = 1;
+    x
+}
+
+pub fn mismatch() -> u32 {
+    "not a number"
+}
@@ -0,0 +1,8 @@
+pub fn unused_variable() {
+    let _x = 1;
+}
+
+pub fn unused_mut() -> i32 {
+    let y = 2;
+    y
+}
@@ -0,0 +1,8 @@
+pub fn unused_variable() {
+    let x = 1;
+}
+
+pub fn unused_mut() -> i32 {
+    let mut y = 2;
+    y
+}
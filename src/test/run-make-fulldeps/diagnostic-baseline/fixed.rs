@@ -0,0 +1,3 @@
+pub fn old() {
+    let _x = 1;
+}
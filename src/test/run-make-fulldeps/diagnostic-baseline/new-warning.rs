@@ -0,0 +1,9 @@
+// The warning in `old` moved, but is still in the baseline.
+
+pub fn new() {
+    let y = 2;
+}
+
+pub fn old() {
+    let x = 1;
+}
@@ -0,0 +1,3 @@
+pub fn old() {
+    let x = 1;
+}
@@ -0,0 +1,9 @@
+fn main() {
+    let mut x = 0;
+    for i in 0..10 {
+        if i % 2 == 0 {
+            x += i;
+        }
+    }
+    println!("{}", x);
+}
@@ -0,0 +1,34 @@
+#!/usr/bin/env python
+
+import os
+import sys
+import json
+
+dump_dir = sys.argv[1]
+files = os.listdir(dump_dir)
+assert files, "no MIR was dumped"
+
+main_bodies = 0
+for name in files:
+    assert name.endswith(".json"), "unexpected dump file: {}".format(name)
+    with open(os.path.join(dump_dir, name)) as f:
+        body = json.load(f)
+    assert body["version"] == 1
+    assert body["def_path"] == "main"
+    assert body["disambiguator"] in ("before", "after")
+    assert body["arg_count"] == 0
+    assert body["locals"][0]["kind"] == "return"
+    if body["promoted"] is None:
+        assert body["locals"][0]["ty"] == "()"
+        main_bodies += 1
+    else:
+        # The promoted constants of `main`, such as the pieces of the `println!` format string,
+        # are dumped with it, and evaluate to a reference to the promoted value.
+        assert body["locals"][0]["ty"].startswith("&")
+    for block in body["basic_blocks"]:
+        for statement in block["statements"]:
+            assert statement["kind"] and statement["text"]
+        for successor in block["terminator"]["successors"]:
+            assert 0 <= successor["target"] < len(body["basic_blocks"])
+
+assert main_bodies, "the body of `main` itself was not dumped"
@@ -0,0 +1,8 @@
+#[inline(never)]
+fn compute<T: std::ops::Add<Output = T> + Copy>(value: T) -> T {
+    value + value
+}
+
+fn main() {
+    println!("{}", compute(21u64));
+}
@@ -0,0 +1,14 @@
+pub mod changed {
+    pub fn value() -> u32 {
+        #[cfg(before)]
+        return 1;
+        #[cfg(not(before))]
+        return 2;
+    }
+}
+
+pub mod unchanged {
+    pub fn value() -> u32 {
+        3
+    }
+}
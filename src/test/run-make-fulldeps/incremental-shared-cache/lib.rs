@@ -0,0 +1,9 @@
+pub fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+pub mod nested {
+    pub fn double(x: u32) -> u32 {
+        super::add(x, x)
+    }
+}
@@ -0,0 +1,37 @@
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+#[macro_use]
+extern crate rustc_lint;
+#[macro_use]
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_hir as hir;
+use rustc_lint::plugin::LintPluginRegistry;
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+
+declare_lint!(NO_TODO_FNS, Warn, "function names must not contain `todo`");
+
+declare_lint_pass!(NoTodoFns => [NO_TODO_FNS]);
+
+impl<'tcx> LateLintPass<'tcx> for NoTodoFns {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::Item<'tcx>) {
+        if let hir::ItemKind::Fn(..) = item.kind {
+            if item.ident.as_str().contains("todo") {
+                cx.lint(NO_TODO_FNS, |lint| {
+                    lint.build("function names must not contain `todo`").set_span(item.span).emit();
+                });
+            }
+        }
+    }
+}
+
+fn register(registry: &mut LintPluginRegistry<'_>) {
+    registry.register_lints(&[&NO_TODO_FNS]);
+    registry.register_group("house_rules", &[&NO_TODO_FNS]);
+    registry.register_late_pass(|| Box::new(NoTodoFns));
+}
+
+declare_lint_plugin!(register);
@@ -0,0 +1,5 @@
+#![crate_type = "lib"]
+
+pub fn todo_later() {}
+
+pub fn done() {}
@@ -0,0 +1,8 @@
+#[inline(never)]
+pub fn wrap<T>(value: T) -> Option<T> {
+    Some(value)
+}
+
+pub fn wrap_twice<T>(value: T) -> Option<Option<T>> {
+    wrap(wrap(value))
+}
@@ -0,0 +1,6 @@
+extern crate dep;
+
+fn main() {
+    dep::wrap(1u32);
+    dep::wrap_twice("a");
+}
@@ -18,7 +18,9 @@
 extern crate rustc_middle;
 extern crate rustc_session;
 
-use rustc_borrowck::consumers::BodyWithBorrowckFacts;
+use rustc_borrowck::consumers::{
+    BodyWithBorrowckFacts, InitLocation, TwoPhaseActivation, BORROWCK_FACTS_VERSION,
+};
 use rustc_driver::Compilation;
 use rustc_hir::def_id::LocalDefId;
 use rustc_hir::itemlikevisit::ItemLikeVisitor;
@@ -32,6 +34,9 @@
 use std::collections::HashMap;
 use std::thread_local;
 
+// The driver is written against this version of the borrowck facts.
+const _: () = assert!(BORROWCK_FACTS_VERSION == 1);
+
 fn main() {
     let exit_code = rustc_driver::catch_with_exit_code(move || {
         let mut rustc_args: Vec<_> = std::env::args().collect();
@@ -80,6 +85,22 @@ fn after_analysis<'tcx>(
             for (def_id, body) in bodies {
                 println!("{}", def_id);
                 assert!(body.input_facts.cfg_edge.len() > 0);
+                for (index, borrow) in body.borrow_set.iter_enumerated() {
+                    assert_eq!(body.loan_created_at(borrow.reserve_location), Some(index));
+                    // A two-phase loan is used, and so live, where it is activated.
+                    if let TwoPhaseActivation::ActivatedAt(location) = borrow.activation_location {
+                        assert!(body.loans_activated_at(location).contains(&index));
+                        assert!(body.loans_live_at(location).any(|live| live == index));
+                    }
+                }
+                for (index, move_out) in body.move_data.moves.iter_enumerated() {
+                    assert!(body.moves_at(move_out.source).contains(&index));
+                }
+                for (index, init) in body.move_data.inits.iter_enumerated() {
+                    if let InitLocation::Statement(location) = init.location {
+                        assert!(body.inits_at(location).contains(&index));
+                    }
+                }
             }
         });
 
@@ -0,0 +1,31 @@
+#!/usr/bin/env python
+
+import sys
+import json
+
+with open(sys.argv[1]) as f:
+    log = json.load(f)
+
+assert log["version"] == "2.1.0"
+assert len(log["runs"]) == 1
+run = log["runs"][0]
+assert run["tool"]["driver"]["name"] == "rustc"
+rules = run["tool"]["driver"]["rules"]
+
+results = run["results"]
+assert len(results) == 1, results
+result = results[0]
+assert result["level"] == "warning"
+assert result["ruleId"] == "unused_variables"
+assert rules[result["ruleIndex"]]["id"] == "unused_variables"
+assert result["message"]["text"].startswith("unused variable: `x`")
+
+region = result["locations"][0]["physicalLocation"]["region"]
+assert (region["startLine"], region["startColumn"]) == (2, 9)
+assert (region["endLine"], region["endColumn"]) == (2, 10)
+
+fix = result["fixes"][0]
+assert fix["properties"]["applicability"] == "MachineApplicable"
+change = fix["artifactChanges"][0]
+assert change["artifactLocation"]["uri"] == "lib.rs"
+assert change["replacements"][0]["insertedContent"]["text"] == "_x"
@@ -0,0 +1,9 @@
+#![crate_type = "lib"]
+
+pub fn generic_heavy<T: Clone + std::fmt::Debug>(values: Vec<T>) -> String {
+    values.iter().cloned().map(|value| format!("{:?}", value)).collect()
+}
+
+pub fn instantiate() -> String {
+    generic_heavy(vec![1u8, 2, 3]) + &generic_heavy(vec!["a", "b"])
+}
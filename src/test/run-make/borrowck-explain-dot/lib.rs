@@ -0,0 +1,6 @@
+#![crate_type = "lib"]
+
+pub fn foo<'a, 'b>(x: &'a u32) -> &'b u32 {
+    let y = x;
+    y
+}
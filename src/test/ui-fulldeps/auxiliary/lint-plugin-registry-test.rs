@@ -0,0 +1,35 @@
+// force-host
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+#[macro_use]
+extern crate rustc_lint;
+#[macro_use]
+extern crate rustc_session;
+
+use rustc_lint::plugin::LintPluginRegistry;
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+
+declare_lint!(TEST_LINT, Warn, "Warn about items named 'lintme'");
+
+declare_lint_pass!(Pass => [TEST_LINT]);
+
+impl<'tcx> LateLintPass<'tcx> for Pass {
+    fn check_item(&mut self, cx: &LateContext, it: &rustc_hir::Item) {
+        if it.ident.name.as_str() == "lintme" {
+            cx.lint(TEST_LINT, |lint| {
+                lint.build("item is named 'lintme'").set_span(it.span).emit();
+            });
+        }
+    }
+}
+
+fn register(registry: &mut LintPluginRegistry<'_>) {
+    registry.register_lints(&[&TEST_LINT]);
+    registry.register_group("lint_me", &[&TEST_LINT]);
+    registry.register_late_pass(|| Box::new(Pass));
+}
+
+declare_lint_plugin!(register);
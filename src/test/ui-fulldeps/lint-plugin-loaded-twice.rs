@@ -0,0 +1,15 @@
+// ignore-tidy-linelength
+
+// Test that loading a lint plugin twice reports the lint and the lint group it registers twice,
+// instead of ICEing.
+
+// aux-build:lint-plugin-registry-test.rs
+// ignore-stage1
+// only-linux
+// compile-flags: -Z lint-plugin={{build-base}}/lint-plugin-loaded-twice/auxiliary/liblint_plugin_registry_test.so
+// compile-flags: -Z lint-plugin={{build-base}}/lint-plugin-loaded-twice/auxiliary/liblint_plugin_registry_test.so
+// error-pattern: but this name is already registered
+
+fn lintme() {}
+
+fn main() {}
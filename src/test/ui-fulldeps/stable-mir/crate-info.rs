@@ -0,0 +1,194 @@
+// run-pass
+// Test that users are able to use stable mir APIs to retrieve information of the current crate
+
+// ignore-cross-compile
+// ignore-stage1
+// ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate rustc_smir;
+
+use rustc_driver::Compilation;
+use rustc_interface::{interface, Queries};
+use rustc_smir::{rustc_internal, stable_mir};
+use stable_mir::mir::{Operand, StatementKind, TerminatorKind};
+use stable_mir::ty::{GenericArgKind, GenericArgs, IntTy, TyKind};
+use stable_mir::CrateItem;
+use std::io::Write;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() {
+    assert_eq!(stable_mir::local_crate().name, CRATE_NAME);
+    assert!(stable_mir::local_crate().is_local);
+    assert!(stable_mir::external_crates().iter().any(|krate| krate.name == "std"));
+
+    let items = stable_mir::all_local_items();
+    let get_item = |name: &str| items.iter().find(|item| item.name() == name).unwrap();
+
+    let main = stable_mir::entry_fn().unwrap();
+    assert_eq!(main.name(), "main");
+    assert!(main.body().unwrap().blocks.len() > 1);
+
+    let bar = get_item("bar").body().unwrap();
+    assert_eq!(bar.arg_count, 1);
+    assert_eq!(bar.locals[1].name.as_deref(), Some("i"));
+    assert!(matches!(bar.locals[0].ty.kind(), TyKind::Int(IntTy::I32)));
+    assert_eq!(bar.blocks.len(), 1);
+    assert!(matches!(bar.blocks[0].statements[0].kind, StatementKind::Assign(..)));
+    assert!(matches!(bar.blocks[0].terminator.kind, TerminatorKind::Return));
+
+    let foo_bar = get_item("foo_bar").body().unwrap();
+    assert!(foo_bar.blocks.len() > 1);
+
+    let double = stable_mir::all_local_traits().into_iter().find(|t| t.name() == "Double").unwrap();
+    let impls = double.impls();
+    assert_eq!(impls.len(), 1);
+    let self_ty = match impls[0].trait_ref().args.0[0] {
+        GenericArgKind::Type(ty) => ty,
+        _ => unreachable!(),
+    };
+    assert!(matches!(self_ty.kind(), TyKind::Int(IntTy::I32)));
+    assert!(stable_mir::implements_trait(self_ty, double, &GenericArgs::default()));
+
+    // Resolve the call to `Double::twice` in `main` to the impl for `i32`.
+    let (twice, args) = find_call(main, "Double::twice");
+    let instance = stable_mir::Instance::resolve(twice, &args).unwrap();
+    assert_eq!(instance.kind, stable_mir::InstanceKind::Item);
+    assert_eq!(instance.def.name(), "<i32 as Double>::twice");
+
+    // The dependency only provides the MIR of its `const fn` used in const evaluation.
+    let (answer, _) = find_call(main, "dep::answer");
+    let answer = answer.body().unwrap();
+    assert_eq!(answer.arg_count, 0);
+    assert!(matches!(answer.locals[0].ty.kind(), TyKind::Int(IntTy::I32)));
+}
+
+/// Returns the function called by name in `caller`, with its generic arguments.
+fn find_call(caller: CrateItem, name: &str) -> (CrateItem, GenericArgs) {
+    caller
+        .body()
+        .unwrap()
+        .blocks
+        .into_iter()
+        .find_map(|block| match block.terminator.kind {
+            TerminatorKind::Call { func: Operand::Constant(constant), .. } => {
+                match constant.ty.kind() {
+                    TyKind::FnDef(def, args) if def.name() == name => Some((def, args)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .unwrap()
+}
+
+// This test will generate and analyze a dummy crate using the stable mir.
+// For that, it will first write the dummy crate into a file.
+// It will invoke the compiler using a custom Callback implementation, which will
+// invoke Stable MIR APIs after the compiler has finished its analysis.
+fn main() {
+    let dep_path = "dep.rs";
+    generate_dep(&dep_path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        "dep".to_string(),
+        dep_path.to_string(),
+    ];
+    rustc_driver::catch_fatal_errors(|| {
+        rustc_driver::RunCompiler::new(&args, &mut DepCalls {}).run().unwrap();
+    })
+    .unwrap();
+
+    let path = "input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        "--edition=2021".to_string(),
+        "--extern".to_string(),
+        "dep=libdep.rlib".to_string(),
+        path.to_string(),
+    ];
+    rustc_driver::catch_fatal_errors(|| {
+        rustc_driver::RunCompiler::new(&args, &mut SMirCalls {}).run().unwrap();
+    })
+    .unwrap();
+}
+
+struct DepCalls {}
+
+impl rustc_driver::Callbacks for DepCalls {}
+
+struct SMirCalls {}
+
+impl rustc_driver::Callbacks for SMirCalls {
+    /// Called after analysis. Return value instructs the compiler whether to
+    /// continue the compilation afterwards (defaults to `Compilation::Continue`)
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().peek_mut().enter(|tcx| {
+            rustc_internal::run(tcx, test_stable_mir);
+        });
+        // No need to keep going.
+        Compilation::Stop
+    }
+}
+
+fn generate_dep(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub const fn answer() -> i32 {{
+        42
+    }}"#
+    )?;
+    Ok(())
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub trait Double {{
+        fn twice(&self) -> Self;
+    }}
+
+    impl Double for i32 {{
+        fn twice(&self) -> i32 {{
+            self * 2
+        }}
+    }}
+
+    pub fn main() {{
+        let _ = bar(1) + foo_bar("hello") + 2.twice() + dep::answer();
+    }}
+
+    pub fn bar(i: i32) -> i32 {{
+        i
+    }}
+
+    pub fn foo_bar(x: &str) -> i32 {{
+        let mut sum = 0;
+        for c in x.chars() {{
+            if c.is_ascii() {{
+                sum += 1;
+            }}
+        }}
+        sum
+    }}"#
+    )?;
+    Ok(())
+}
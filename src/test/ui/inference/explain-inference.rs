@@ -0,0 +1,18 @@
+// Check that `-Z explain-inference` reports the inference trace of the variables created at a
+// position: in `int`, the one of the type of `x`, including the unification of its integer
+// variable; in `collect`, the one of the type of `reversed`, which an iterator chain leaves to
+// infer from an ambiguous coercion.
+
+// revisions: int collect
+//[int] compile-flags: -Z explain-inference=explain-inference.rs:13:5
+//[collect] compile-flags: -Z explain-inference=explain-inference.rs:16:5
+// normalize-stderr-test: "_#\d+([tif])" -> "_#N$1"
+//[int] check-pass
+
+fn main() {
+    let x = 1;
+    let _: u8 = x;
+    #[cfg(collect)]
+    let reversed = "hello".chars().rev().collect();
+    //[collect]~^ ERROR type annotations needed
+}
@@ -0,0 +1,9 @@
+// Check that `-Z inject-fault` can make the second run of a MIR pass emit an error.
+
+// compile-flags: -Zinject-fault=CheckPackedRef:2:error
+
+fn foo() {}
+
+fn main() {
+    foo();
+}
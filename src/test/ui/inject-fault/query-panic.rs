@@ -0,0 +1,8 @@
+// compile-flags: -Zinject-fault=typeck:1
+// failure-status: 101
+// error-pattern: injected fault into run 1 of `typeck`
+// normalize-stderr-test "note: .*\n\n" -> ""
+// normalize-stderr-test "thread 'rustc' panicked.*\n" -> ""
+// rustc-env:RUST_BACKTRACE=0
+
+fn main() {}
@@ -0,0 +1,6 @@
+// Check that a number of days whose seconds do not fit in a `u64` is rejected.
+
+// compile-flags: -Z incremental-max-age=213503982334602
+// error-pattern: a number of days was expected
+
+fn main() {}
@@ -0,0 +1,27 @@
+// Checks that the `lints.toml` found from the crate root sets the lint levels of the crate.
+
+// compile-flags: -Z lint-config-discovery
+
+//! The crate documentation.
+
+#![crate_type = "lib"]
+
+/// The modules of `auxiliary` are exempted from `missing_docs`.
+#[path = "auxiliary/generated.rs"]
+pub mod generated;
+
+pub fn undocumented() {} //~ ERROR missing documentation for a function
+
+/// Not in snake case, which is allowed.
+pub fn camelCase() {}
+
+/// Attributes override the configuration.
+#[warn(missing_docs)]
+pub mod documented {
+    pub fn warned() {} //~ WARN missing documentation for a function
+}
+
+/// Uses unsafe code.
+pub fn unsafe_block() -> i32 {
+    unsafe { *(&0 as *const i32) } //~ ERROR usage of an `unsafe` block
+}
@@ -0,0 +1,41 @@
+// Checks the rules of `-Z lint-rules` against resolved paths.
+
+// compile-flags: -Z lint-rules={{src-base}}/lint/lint-rules/rules.txt
+
+#![allow(unused)]
+
+use std::process::exit as quit;
+
+mod net {
+    pub fn connect(address: Result<u32, ()>) -> u32 {
+        let parse = |s: Result<u32, ()>| s.unwrap(); //~ ERROR forbidden by a lint rule
+        address.unwrap() + parse(Ok(1)) //~ ERROR forbidden by a lint rule
+    }
+
+    pub fn connect_ufcs(address: Result<u32, ()>) -> u32 {
+        Result::unwrap(address) //~ ERROR forbidden by a lint rule
+    }
+
+    mod tests {
+        fn connect() {
+            Ok::<u32, ()>(1).unwrap();
+        }
+    }
+}
+
+fn parse(s: &str) -> u32 {
+    s.parse::<u32>().unwrap()
+}
+
+fn fail() {
+    quit(1); //~ ERROR forbidden by a lint rule
+}
+
+#[allow(lint_rules)]
+fn fail_allowed() {
+    std::process::exit(1);
+}
+
+fn main() {
+    std::process::exit(0);
+}
@@ -0,0 +1,7 @@
+// aux-build:unused-pub-items-dep.rs
+
+#![warn(unused_pub_items)]
+
+extern crate unused_pub_items_dep;
+
+pub fn unused() {}
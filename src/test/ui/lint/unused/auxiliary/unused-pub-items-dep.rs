@@ -0,0 +1,3 @@
+#![warn(unused_pub_items)]
+
+pub fn used() {}
@@ -0,0 +1,34 @@
+#![warn(unused_pub_items)]
+
+pub struct Wrapper(pub u32);
+
+impl Wrapper {
+    pub fn get(&self) -> u32 {
+        self.0
+    }
+}
+
+pub enum Kind {
+    A,
+    B,
+}
+
+pub trait Describe {
+    fn describe(&self) -> String;
+}
+
+impl Describe for u32 {
+    fn describe(&self) -> String {
+        self.to_string()
+    }
+}
+
+pub fn used() {}
+
+pub fn unused() {}
+
+pub const UNUSED: u32 = 0;
+
+pub mod nested {
+    pub struct Unused;
+}
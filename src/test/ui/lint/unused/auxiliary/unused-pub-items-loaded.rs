@@ -0,0 +1,6 @@
+// aux-crate:unused_pub_items_dep=unused-pub-items-dep.rs
+// edition:2018
+
+#![warn(unused_pub_items)]
+
+pub fn used() {}
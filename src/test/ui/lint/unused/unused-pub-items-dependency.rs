@@ -0,0 +1,15 @@
+// Check that a crate depending on a crate without using any of its public items is reported, at
+// its `extern crate` item for the local crate, even when other crates use them.
+
+// check-pass
+// aux-build:unused-pub-items-dep.rs
+// aux-build:unused-pub-items-declares.rs
+
+#![warn(unused_pub_items)]
+
+extern crate unused_pub_items_declares;
+extern crate unused_pub_items_dep;
+
+fn main() {
+    unused_pub_items_dep::used();
+}
@@ -0,0 +1,13 @@
+// Check that a dependency only passed with `--extern`, which is never loaded when it is never
+// referenced, is reported for the local crate and for the checked dependencies.
+
+// check-pass
+// aux-crate:unused_pub_items_dep=unused-pub-items-dep.rs
+// aux-crate:unused_pub_items_loaded=unused-pub-items-loaded.rs
+// edition:2018
+
+#![warn(unused_pub_items)]
+
+fn main() {
+    unused_pub_items_loaded::used();
+}
@@ -0,0 +1,20 @@
+// Check that the public items of the checked dependencies that the crate graph doesn't use are
+// reported, and that the items used through their constructors, variants, methods or associated
+// items count as used. A dependency that is not checked is still reported when it is unused.
+
+// check-pass
+// aux-build:unused-pub-items-lib.rs
+// aux-build:unused-pub-items-unchecked.rs
+
+#![warn(unused_pub_items)]
+
+extern crate unused_pub_items_lib;
+extern crate unused_pub_items_unchecked;
+
+fn main() {
+    unused_pub_items_lib::used();
+    let wrapper = unused_pub_items_lib::Wrapper(0);
+    let _ = wrapper.get();
+    let _ = unused_pub_items_lib::Kind::A;
+    let _ = unused_pub_items_lib::Describe::describe(&0u32);
+}
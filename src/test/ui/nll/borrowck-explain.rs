@@ -0,0 +1,16 @@
+// Checks that `-Z borrowck-explain` adds the constraint path to region errors, and that `=dot`
+// also writes the constraint graph, whose contents are checked by `run-make/borrowck-explain-dot`.
+// The numbers of the region variables depend on the MIR building details, so they are normalized.
+
+// revisions: text dot
+//[text] compile-flags: -Z borrowck-explain
+//[dot] compile-flags: -Z borrowck-explain=dot -Z dump-mir-dir={{build-base}}/nll/borrowck-explain
+// normalize-stderr-test: "'_#\d+r" -> "'_#Nr"
+
+fn foo<'a, 'b>(x: &'a u32) -> &'b u32 {
+    let y = x;
+    y
+    //~^ ERROR lifetime may not live long enough
+}
+
+fn main() {}
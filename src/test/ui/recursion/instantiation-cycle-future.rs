@@ -0,0 +1,18 @@
+// Check that a cycle of instantiations growing a future suggests boxing it with `Box::pin`.
+
+// build-fail
+// edition:2018
+
+#![allow(unconditional_recursion)]
+
+use std::future::Future;
+
+fn grow<F: Future>(future: F) {
+    grow(async move { future.await });
+    //~^ ERROR reached the recursion limit while instantiating
+    //~| HELP consider boxing the future with `Box::pin` to break the cycle
+}
+
+fn main() {
+    grow(async {});
+}
@@ -0,0 +1,37 @@
+// Check that `-Z trait-solver-trace` notes the selection tree of a goal failing through a stack
+// of generic impls, reports the goals that cycle back to a goal above, and expands a failing
+// projection goal into its normalized type and its trait goal.
+
+// compile-flags: -Z trait-solver-trace
+
+trait Service {}
+
+struct Leaf;
+struct Wrap<S>(S);
+struct Pair<A, B>(A, B);
+struct Loop<S>(S);
+
+impl<S: Service> Service for Wrap<S> {}
+impl<A: Service, B: Service> Service for Pair<A, B> {}
+impl<S> Service for Loop<S> where Loop<S>: Service, S: Service {}
+impl Service for u8 {}
+
+trait Source {
+    type Item;
+}
+
+impl Source for u8 {
+    type Item = i32;
+}
+
+fn serve<S: Service>(_: S) {}
+fn take<S: Source<Item = u32>>(_: S) {}
+
+fn main() {
+    serve(Wrap(Pair(0u8, Wrap(Leaf))));
+    //~^ ERROR the trait bound `Leaf: Service` is not satisfied
+    serve(Loop(Leaf));
+    //~^ ERROR the trait bound `Leaf: Service` is not satisfied
+    take(0u8);
+    //~^ ERROR type mismatch resolving `<u8 as Source>::Item == u32`
+}
@@ -370,6 +370,12 @@ pub struct Config {
 
     /// Whether to rerun tests even if the inputs are unchanged.
     pub force_rerun: bool,
+
+    /// Only run the tests recorded as failed in the results file of the previous run.
+    pub rerun_failed: bool,
+
+    /// Only run the tests of the given `(index, count)` shard, where `index` is 1-based.
+    pub shard: Option<(u64, u64)>,
 }
 
 impl Config {
@@ -379,6 +385,25 @@ pub fn run_enabled(&self) -> bool {
             !self.target.ends_with("-fuchsia")
         })
     }
+
+    /// Path to the file recording the outcome of every test of the last run,
+    /// which is read back by `--rerun-failed`. This is the `--logfile` if one
+    /// was given, and otherwise a file in `build_base` specific to the compare
+    /// mode and shard.
+    pub fn results_path(&self) -> PathBuf {
+        if let Some(ref logfile) = self.logfile {
+            return logfile.clone();
+        }
+        let mut name = String::from("results");
+        if let Some(ref mode) = self.compare_mode {
+            name.push_str(&format!(".{}", mode.to_str()));
+        }
+        if let Some((index, count)) = self.shard {
+            name.push_str(&format!(".shard-{}-of-{}", index, count));
+        }
+        name.push_str(".log");
+        self.build_base.join(name)
+    }
 }
 
 #[derive(Debug, Clone)]
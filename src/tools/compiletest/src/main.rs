@@ -11,6 +11,7 @@
 use crate::common::{CompareMode, Config, Debugger, Mode, PassMode, TestPaths};
 use crate::util::logv;
 use getopts::Options;
+use std::collections::HashSet;
 use std::env;
 use std::ffi::OsString;
 use std::fs;
@@ -147,6 +148,8 @@ pub fn parse_config(args: Vec<String>) -> Config {
                 `./<build_base>/rustfix_missing_coverage.txt`",
         )
         .optflag("", "force-rerun", "rerun tests even if the inputs are unchanged")
+        .optflag("", "rerun-failed", "only run tests that failed in the previous run")
+        .optopt("", "shard", "only run the N-th of M stable partitions of the tests", "N/M")
         .optflag("h", "help", "show this message")
         .reqopt("", "channel", "current Rust channel", "CHANNEL")
         .optopt("", "edition", "default Rust edition", "EDITION");
@@ -297,7 +300,23 @@ fn make_absolute(path: PathBuf) -> PathBuf {
         npm: matches.opt_str("npm"),
 
         force_rerun: matches.opt_present("force-rerun"),
+        rerun_failed: matches.opt_present("rerun-failed"),
+        shard: matches.opt_str("shard").map(|shard| {
+            parse_shard(&shard)
+                .unwrap_or_else(|| panic!("invalid `--shard` option `{}` given", shard))
+        }),
+    }
+}
+
+/// Parses a `--shard` argument of the form `N/M` into `(N, M)`.
+fn parse_shard(shard: &str) -> Option<(u64, u64)> {
+    let (index, count) = shard.split_once('/')?;
+    let index = index.trim().parse().ok()?;
+    let count = count.trim().parse().ok()?;
+    if index == 0 || index > count {
+        return None;
     }
+    Some((index, count))
 }
 
 pub fn log_config(config: &Config) {
@@ -316,6 +335,8 @@ pub fn log_config(config: &Config) {
     logv(c, format!("filters: {:?}", config.filters));
     logv(c, format!("skip: {:?}", config.skip));
     logv(c, format!("filter_exact: {}", config.filter_exact));
+    logv(c, format!("rerun_failed: {}", config.rerun_failed));
+    logv(c, format!("shard: {:?}", config.shard));
     logv(
         c,
         format!("force_pass_mode: {}", opt_str(&config.force_pass_mode.map(|m| format!("{}", m))),),
@@ -401,6 +422,24 @@ pub fn run_tests(config: Config) {
         make_tests(c, &mut tests);
     }
 
+    if config.rerun_failed {
+        let results_path = config.results_path();
+        match read_failed_tests(&results_path) {
+            Some(failed) => {
+                tests.retain(|test| failed.contains(test.desc.name.as_slice()));
+                println!(
+                    "rerunning {} test(s) that failed in the previous run (from {})",
+                    tests.len(),
+                    results_path.display()
+                );
+            }
+            None => println!(
+                "no results of a previous run found at {}, running all tests",
+                results_path.display()
+            ),
+        }
+    }
+
     let res = test::run_tests_console(&opts, tests);
     match res {
         Ok(true) => {}
@@ -498,7 +537,8 @@ pub fn test_opts(config: &Config) -> test::TestOpts {
         filter_exact: config.filter_exact,
         run_ignored: if config.run_ignored { test::RunIgnored::Yes } else { test::RunIgnored::No },
         format: if config.quiet { test::OutputFormat::Terse } else { test::OutputFormat::Pretty },
-        logfile: config.logfile.clone(),
+        // Always record the results, so that a later run can use `--rerun-failed`.
+        logfile: Some(config.results_path()),
         run_tests: true,
         bench_benchmarks: true,
         nocapture: match env::var("RUST_TEST_NOCAPTURE") {
@@ -524,6 +564,35 @@ pub fn make_tests(config: &Config, tests: &mut Vec<test::TestDescAndFn>) {
         .unwrap_or_else(|_| panic!("Could not read tests from {}", config.src_base.display()));
 }
 
+/// Reads the names of the tests recorded as failed in the libtest log file of
+/// a previous run, or returns `None` if there is no such file.
+fn read_failed_tests(path: &Path) -> Option<HashSet<String>> {
+    let contents = fs::read_to_string(path).ok()?;
+    Some(contents.lines().filter_map(parse_failed_test).map(str::to_owned).collect())
+}
+
+/// Extracts the test name from a libtest log line such as `failed [ui] src/test/ui/foo.rs`.
+///
+/// Compiletest neither sets time limits nor expects panic messages, so failures
+/// are always logged as a bare `failed` followed by the test name.
+fn parse_failed_test(line: &str) -> Option<&str> {
+    line.strip_prefix("failed ").map(str::trim_end)
+}
+
+/// Returns whether the test at `testpaths` belongs to the shard selected with
+/// `--shard`. Tests are assigned to shards by a hash of their path relative to
+/// `src_base`, so adding or removing a test does not move any other test.
+fn is_in_shard(config: &Config, testpaths: &TestPaths) -> bool {
+    let (index, count) = match config.shard {
+        Some(shard) => shard,
+        None => return true,
+    };
+    let mut components: Vec<_> =
+        testpaths.relative_dir.iter().map(|c| c.to_str().unwrap()).collect();
+    components.push(testpaths.file.file_name().unwrap().to_str().unwrap());
+    util::stable_hash(components.join("/").as_bytes()) % count == index - 1
+}
+
 /// Returns a stamp constructed from input files common to all test cases.
 fn common_inputs_stamp(config: &Config) -> Stamp {
     let rust_src_dir = config.find_rust_src_root().expect("Could not find Rust source root");
@@ -575,7 +644,9 @@ fn collect_tests_from_dir(
             file: dir.to_path_buf(),
             relative_dir: relative_dir_path.parent().unwrap().to_path_buf(),
         };
-        tests.extend(make_test(config, &paths, inputs));
+        if is_in_shard(config, &paths) {
+            tests.extend(make_test(config, &paths, inputs));
+        }
         return Ok(());
     }
 
@@ -599,7 +670,9 @@ fn collect_tests_from_dir(
             let paths =
                 TestPaths { file: file_path, relative_dir: relative_dir_path.to_path_buf() };
 
-            tests.extend(make_test(config, &paths, inputs))
+            if is_in_shard(config, &paths) {
+                tests.extend(make_test(config, &paths, inputs))
+            }
         } else if file_path.is_dir() {
             let relative_file_path = relative_dir_path.join(file.file_name());
             if &file_name != "auxiliary" {
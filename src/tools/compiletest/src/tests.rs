@@ -76,3 +76,21 @@ fn test_extract_llvm_version() {
     assert_eq!(extract_llvm_version("12.0.0-rc3"), Some(120000));
     assert_eq!(extract_llvm_version("13.0.0git"), Some(130000));
 }
+
+#[test]
+fn test_parse_shard() {
+    assert_eq!(parse_shard("1/1"), Some((1, 1)));
+    assert_eq!(parse_shard("3/4"), Some((3, 4)));
+    assert_eq!(parse_shard("0/4"), None);
+    assert_eq!(parse_shard("5/4"), None);
+    assert_eq!(parse_shard("1"), None);
+    assert_eq!(parse_shard("a/b"), None);
+}
+
+#[test]
+fn test_parse_failed_test() {
+    assert_eq!(parse_failed_test("failed [ui] src/test/ui/a.rs"), Some("[ui] src/test/ui/a.rs"));
+    assert_eq!(parse_failed_test("failed [ui] ui/b.rs#x"), Some("[ui] ui/b.rs#x"));
+    assert_eq!(parse_failed_test("ok [ui] src/test/ui/c.rs"), None);
+    assert_eq!(parse_failed_test("ignored [ui] src/test/ui/d.rs"), None);
+}
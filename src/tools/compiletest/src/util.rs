@@ -211,6 +211,17 @@ fn path_div() -> &'static str {
     ";"
 }
 
+/// Computes the 64-bit FNV-1a hash of `bytes`. Unlike `DefaultHasher`, the
+/// result does not depend on the Rust version compiletest was built with.
+pub fn stable_hash(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for &byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
 pub fn logv(config: &Config, s: String) {
     debug!("{}", s);
     if config.verbose {
@@ -0,0 +1,270 @@
+//! Finds the MIR optimization that miscompiles a crate.
+//!
+//! Usage: `mir-bisect [--crate <name>] [--passes <A,B,...>] [--verbose] -- <command> [<args>...]`
+//!
+//! `<command>` must build the crate and test it, and exit successfully if and only if the test
+//! passes. It is run repeatedly, each time with the flags being tried appended to the `RUSTFLAGS`
+//! environment variable, which makes Cargo rebuild the crate with them. The flags alone are also
+//! passed in `MIR_BISECT_FLAGS`, for commands that invoke `rustc` directly. As these are `-Z`
+//! flags, the compiler must be a nightly one.
+//!
+//! The tool first bisects over the optimization passes, enabling more and more of them in
+//! pipeline order with `-Z mir-enable-passes`, to find the first pass that makes the test fail.
+//! Then, if `--crate` is given, it bisects over `-Z fuel=<crate>=<n>` to find the single
+//! transformation of that crate that makes the test fail, and reports the body it was applied to.
+
+use std::env;
+use std::ffi::OsString;
+use std::process::{self, Command};
+
+#[cfg(test)]
+mod tests;
+
+/// The MIR optimization passes in the order in which `run_optimization_passes` runs them, without
+/// the passes that are required for correct code generation such as `StateTransform`.
+const OPTIMIZATION_PASSES: &[&str] = &[
+    "LowerSliceLenCalls",
+    "NormalizeArrayLen",
+    "UnreachablePropagation",
+    "UninhabitedEnumBranching",
+    "Inline",
+    "RemoveStorageMarkers",
+    "RemoveZsts",
+    "ConstGoto",
+    "RemoveUnneededDrops",
+    "MatchBranchSimplification",
+    "MultipleReturnTerminators",
+    "InstCombine",
+    "SeparateConstSwitch",
+    "ConstProp",
+    "EarlyOtherwiseBranch",
+    "SimplifyComparisonIntegral",
+    "SimplifyArmIdentity",
+    "SimplifyBranchSame",
+    "DestinationPropagation",
+    "RemoveNoopLandingPads",
+    "RenameReturnPlace",
+    "ConstDebugInfo",
+    "SimplifyLocals",
+    "DeduplicateBlocks",
+];
+
+/// The largest fuel tried before giving up on the fuel bisection.
+const MAX_FUEL: u64 = 1 << 32;
+
+/// The prefix of the warning emitted by the compiler when it runs out of fuel, followed by a
+/// description of the optimization that was not applied.
+const FUEL_EXHAUSTED: &str = "optimization-fuel-exhausted: ";
+
+const USAGE: &str =
+    "usage: mir-bisect [--crate <name>] [--passes <A,B,...>] [--verbose] -- <command> [<args>...]";
+
+struct Config {
+    /// The crate to bisect the optimization fuel of.
+    crate_name: Option<String>,
+    /// The passes to bisect over, in pipeline order.
+    passes: Vec<String>,
+    verbose: bool,
+    command: Vec<String>,
+}
+
+/// The result of one run of the test command.
+struct Outcome {
+    failed: bool,
+    /// The stdout and stderr of the command.
+    output: String,
+}
+
+struct Bisector {
+    config: Config,
+    runs: usize,
+}
+
+impl Bisector {
+    /// Runs the test command with `flags`.
+    fn run(&mut self, flags: &[String]) -> Outcome {
+        self.runs += 1;
+        let flags = flags.join(" ");
+        let mut rustflags = env::var_os("RUSTFLAGS").unwrap_or_default();
+        if !rustflags.is_empty() && !flags.is_empty() {
+            rustflags.push(" ");
+        }
+        rustflags.push(&flags);
+
+        let output = Command::new(&self.config.command[0])
+            .args(&self.config.command[1..])
+            .env("RUSTFLAGS", rustflags)
+            .env("MIR_BISECT_FLAGS", &flags)
+            .output()
+            .unwrap_or_else(|e| {
+                fatal(&format!("failed to run `{}`: {}", self.config.command[0], e))
+            });
+        let failed = !output.status.success();
+        let output = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        eprintln!(
+            "mir-bisect: run {}: `{}`: {}",
+            self.runs,
+            flags,
+            if failed { "fails" } else { "passes" }
+        );
+        if self.config.verbose {
+            eprint!("{}", output);
+        }
+        Outcome { failed, output }
+    }
+
+    /// Returns the flags that enable the first `n` passes and disable the others.
+    fn pass_flags(&self, n: usize) -> Vec<String> {
+        let disabled = &self.config.passes[n..];
+        if disabled.is_empty() {
+            return vec![];
+        }
+        let disabled = disabled.iter().map(|pass| format!("-{}", pass)).collect::<Vec<_>>();
+        vec![format!("-Zmir-enable-passes={}", disabled.join(","))]
+    }
+
+    /// Finds the first pass that makes the test fail, and returns the flags that enable the
+    /// passes up to and including it.
+    fn bisect_passes(&mut self) -> Vec<String> {
+        let all = self.config.passes.len();
+        if self.run(&self.pass_flags(0)).failed {
+            eprintln!(
+                "mir-bisect: the test fails even with all {} optimization passes disabled",
+                all
+            );
+            return self.pass_flags(0);
+        }
+        let n = bisect(0, all as u64, |n| self.run(&self.pass_flags(n as usize)).failed) as usize;
+        eprintln!(
+            "mir-bisect: the test fails once `{}` is enabled (pass {} of {})",
+            self.config.passes[n - 1],
+            n,
+            all
+        );
+        self.pass_flags(n)
+    }
+
+    /// Finds the single transformation of `crate_name` that makes the test fail, with the
+    /// `base` flags in addition to the fuel.
+    fn bisect_fuel(&mut self, crate_name: &str, base: &[String]) {
+        let fuel_flags = |fuel: u64| {
+            let mut flags = base.to_vec();
+            flags.push(format!("-Zfuel={}={}", crate_name, fuel));
+            flags
+        };
+        if self.run(&fuel_flags(0)).failed {
+            eprintln!(
+                "mir-bisect: the test fails even without the fuel-aware optimizations of `{}`",
+                crate_name
+            );
+            return;
+        }
+
+        // Find an upper bound first, as the amount of fuel a crate uses is not known.
+        let mut good = 0;
+        let mut bad = 1;
+        while !self.run(&fuel_flags(bad)).failed {
+            good = bad;
+            bad *= 2;
+            if bad > MAX_FUEL {
+                eprintln!("mir-bisect: the test passes with any amount of fuel");
+                return;
+            }
+        }
+        let fuel = bisect(good, bad, |fuel| self.run(&fuel_flags(fuel)).failed);
+        eprintln!(
+            "mir-bisect: the test fails with fuel {} but passes with fuel {}",
+            fuel,
+            fuel - 1
+        );
+
+        // With one less fuel, the compiler names the optimization it could not apply.
+        let outcome = self.run(&fuel_flags(fuel - 1));
+        match outcome.output.lines().find_map(|line| line.split_once(FUEL_EXHAUSTED)) {
+            Some((_, optimization)) => {
+                eprintln!("mir-bisect: the offending optimization is: {}", optimization)
+            }
+            None => eprintln!(
+                "mir-bisect: the test command did not print the compiler's \
+                `{}` warning, run it with `{}` to see the offending optimization",
+                FUEL_EXHAUSTED.trim_end_matches(": "),
+                fuel_flags(fuel - 1).join(" ")
+            ),
+        }
+    }
+}
+
+/// Returns the smallest `n` in `good + 1..=bad` for which `is_bad(n)` holds, given that
+/// `is_bad(bad)` holds and `is_bad(good)` does not.
+fn bisect(mut good: u64, mut bad: u64, mut is_bad: impl FnMut(u64) -> bool) -> u64 {
+    while bad - good > 1 {
+        let mid = good + (bad - good) / 2;
+        if is_bad(mid) {
+            bad = mid;
+        } else {
+            good = mid;
+        }
+    }
+    bad
+}
+
+fn parse_args(args: impl Iterator<Item = OsString>) -> Result<Config, String> {
+    let mut config = Config {
+        crate_name: None,
+        passes: OPTIMIZATION_PASSES.iter().map(|pass| pass.to_string()).collect(),
+        verbose: false,
+        command: vec![],
+    };
+    let mut args = args.map(|arg| arg.into_string().map_err(|_| "non-UTF-8 argument".to_string()));
+    while let Some(arg) = args.next() {
+        match &*arg? {
+            "--crate" => config.crate_name = Some(args.next().ok_or("missing crate name")??),
+            "--passes" => {
+                let passes = args.next().ok_or("missing pass list")??;
+                config.passes = passes.split(',').map(|pass| pass.to_string()).collect();
+            }
+            "--verbose" => config.verbose = true,
+            "--" => {
+                config.command = args.collect::<Result<_, _>>()?;
+                break;
+            }
+            other => return Err(format!("unknown argument `{}`", other)),
+        }
+    }
+    if config.command.is_empty() {
+        return Err("missing test command".to_string());
+    }
+    // Passes that run several times are enabled or disabled together.
+    let mut seen = std::collections::HashSet::new();
+    config.passes.retain(|pass| seen.insert(pass.clone()));
+    Ok(config)
+}
+
+fn fatal(msg: &str) -> ! {
+    eprintln!("mir-bisect: error: {}", msg);
+    process::exit(1);
+}
+
+fn main() {
+    let config =
+        parse_args(env::args_os().skip(1)).unwrap_or_else(|e| fatal(&format!("{}\n{}", e, USAGE)));
+    let mut bisector = Bisector { config, runs: 0 };
+
+    if !bisector.run(&[]).failed {
+        fatal("the test passes without any flags, there is nothing to bisect");
+    }
+    let pass_flags = bisector.bisect_passes();
+    if let Some(crate_name) = bisector.config.crate_name.clone() {
+        bisector.bisect_fuel(&crate_name, &pass_flags);
+    }
+    eprintln!(
+        "mir-bisect: done after {} runs, pass `-Zdump-mir=<pass>` to see the MIR before and \
+        after a pass",
+        bisector.runs
+    );
+}
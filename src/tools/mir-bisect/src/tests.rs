@@ -0,0 +1,39 @@
+use super::*;
+
+fn args(args: &[&str]) -> impl Iterator<Item = OsString> {
+    args.iter().map(OsString::from).collect::<Vec<_>>().into_iter()
+}
+
+#[test]
+fn test_bisect() {
+    for first_bad in 1..=10 {
+        assert_eq!(bisect(0, 10, |n| n >= first_bad), first_bad);
+    }
+    assert_eq!(bisect(4, 5, |_| unreachable!()), 5);
+}
+
+#[test]
+fn test_parse_args() {
+    let config =
+        parse_args(args(&["--crate", "foo", "--passes", "A,B,A", "--", "cargo", "test"])).unwrap();
+    assert_eq!(config.crate_name.as_deref(), Some("foo"));
+    assert_eq!(config.passes, ["A", "B"]);
+    assert_eq!(config.command, ["cargo", "test"]);
+
+    let config = parse_args(args(&["--", "./test.sh", "--crate"])).unwrap();
+    assert_eq!(config.crate_name, None);
+    assert_eq!(config.passes.len(), OPTIMIZATION_PASSES.len());
+    assert_eq!(config.command, ["./test.sh", "--crate"]);
+
+    assert!(parse_args(args(&["--crate", "foo"])).is_err());
+    assert!(parse_args(args(&["--foo", "--", "cargo"])).is_err());
+}
+
+#[test]
+fn test_pass_flags() {
+    let config = parse_args(args(&["--passes", "A,B,C", "--", "true"])).unwrap();
+    let bisector = Bisector { config, runs: 0 };
+    assert_eq!(bisector.pass_flags(0), ["-Zmir-enable-passes=-A,-B,-C"]);
+    assert_eq!(bisector.pass_flags(2), ["-Zmir-enable-passes=-C"]);
+    assert!(bisector.pass_flags(3).is_empty());
+}
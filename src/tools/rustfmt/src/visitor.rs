@@ -576,7 +576,7 @@ pub(crate) fn visit_item(&mut self, item: &ast::Item) {
                     use ItemVisitorKind::Item;
                     self.visit_ty_alias_kind(ty_alias, &Item(item), item.span);
                 }
-                ast::ItemKind::GlobalAsm(..) => {
+                ast::ItemKind::GlobalAsm(..) | ast::ItemKind::Err => {
                     let snippet = Some(self.snippet(item.span).to_owned());
                     self.push_rewrite(item.span, snippet);
                 }